@@ -0,0 +1,98 @@
+// Sonda de `libro generar booleanos`: tablas de verdad, precedencia y
+// cortocircuito.
+//
+// Imprime líneas separadas por tabuladores:
+//   celda <tabla> <fila> <columna> <valor>       (ver `src/matriz.rs`)
+//   nivel <operadores>                            de mayor a menor precedencia
+//   precedencia <alto> <bajo> <expr> <valor> <agrupada> <valor> <alternativa> <valor | ->
+// Una alternativa con valor `-` no debería compilar; el generador lo comprueba.
+
+#![allow(unused_parens)]
+
+use std::cell::Cell;
+use std::fmt::Display;
+
+fn celda(tabla: &str, fila: &str, columna: &str, valor: impl Display) {
+    println!("celda\t{tabla}\t{fila}\t{columna}\t{valor}");
+}
+
+macro_rules! verdad {
+    ($tabla:literal, $op:tt) => {
+        for (i, (a, b)) in [(true, true), (true, false), (false, true), (false, false)]
+            .into_iter()
+            .enumerate()
+        {
+            let fila = i.to_string();
+            celda($tabla, &fila, "A", a);
+            celda($tabla, &fila, "B", b);
+            celda($tabla, &fila, concat!("A ", stringify!($op), " B"), a $op b);
+        }
+    };
+}
+
+/// `$expr` debe valer lo mismo que `$agrupada` y distinto que `$alternativa`.
+macro_rules! precedencia {
+    ($alto:literal > $bajo:literal: $expr:expr, $agrupada:expr, $alternativa:expr) => {
+        println!(
+            "precedencia\t{}\t{}\t{}\t{:?}\t{}\t{:?}\t{}\t{:?}",
+            $alto,
+            $bajo,
+            stringify!($expr),
+            $expr,
+            stringify!($agrupada),
+            $agrupada,
+            stringify!($alternativa),
+            $alternativa
+        );
+    };
+    ($alto:literal > $bajo:literal: $expr:expr, $agrupada:expr, no_compila: $alternativa:literal) => {
+        println!(
+            "precedencia\t{}\t{}\t{}\t{:?}\t{}\t{:?}\t{}\t-",
+            $alto,
+            $bajo,
+            stringify!($expr),
+            $expr,
+            stringify!($agrupada),
+            $agrupada,
+            $alternativa
+        );
+    };
+}
+
+/// Evalúa `$a $op derecha()` y cuenta cuántas veces se llamó a `derecha`.
+macro_rules! cortocircuito {
+    ($($a:literal $op:tt),*) => {$(
+        let llamadas = Cell::new(0);
+        let derecha = || {
+            llamadas.set(llamadas.get() + 1);
+            true
+        };
+        let resultado = $a $op derecha();
+        let fila = concat!(stringify!($a), " ", stringify!($op), " derecha()");
+        celda("cortocircuito", fila, "Resultado", resultado);
+        celda("cortocircuito", fila, "Llamadas a derecha()", llamadas.get());
+    )*};
+}
+
+fn main() {
+    for a in [true, false] {
+        celda("not", &format!("!{a}"), "Resultado", !a);
+    }
+    verdad!("and", &&);
+    verdad!("or", ||);
+    verdad!("xor", ^);
+
+    for nivel in ["!", "&", "^", "|", "== != < > <= >=", "&&", "||"] {
+        println!("nivel\t{nivel}");
+    }
+    precedencia!("!" > "&": !false & false, (!false) & false, !(false & false));
+    precedencia!("&" > "^": true ^ true & false, true ^ (true & false), (true ^ true) & false);
+    precedencia!("^" > "|": true | true ^ true, true | (true ^ true), (true | true) ^ true);
+    precedencia!("|" > "==": 1 | 2 == 3, (1 | 2) == 3, no_compila: "1 | (2 == 3)");
+    precedencia!("==" > "&&": false == false && false, (false == false) && false, false == (false && false));
+    precedencia!("&&" > "||": true || false && false, true || (false && false), (true || false) && false);
+    precedencia!("!" > "==": !5u8 == 7, (!5u8) == 7, !(5u8 == 7));
+    precedencia!("^" > "&&": false && false ^ true, false && (false ^ true), (false && false) ^ true);
+
+    cortocircuito!(true && , false && , true || , false || , true & , false & , true | , false |);
+}
@@ -0,0 +1,78 @@
+// Sonda de `libro generar capacidad`: mide cómo crecen `Vec` y `String`.
+//
+// Imprime líneas separadas por tabuladores:
+//   crecimiento <tipo> <len> <cap antes> <cap después> <buffer movido>
+//   metodo <tipo> <operación> <len> <cap>
+
+const EMPUJES: usize = 100;
+
+fn crecimiento<T>(tipo: &str, mut v: Vec<T>, valor: impl Fn(usize) -> T) {
+    for i in 0..EMPUJES {
+        let (cap, ptr) = (v.capacity(), v.as_ptr());
+        v.push(valor(i));
+        if v.capacity() != cap {
+            let movido = cap > 0 && v.as_ptr() != ptr;
+            println!("crecimiento\t{tipo}\t{}\t{cap}\t{}\t{movido}", v.len(), v.capacity());
+        }
+    }
+}
+
+fn crecimiento_string() {
+    let mut s = String::new();
+    for _ in 0..EMPUJES {
+        let (cap, ptr) = (s.capacity(), s.as_ptr());
+        s.push('a');
+        if s.capacity() != cap {
+            let movido = cap > 0 && s.as_ptr() != ptr;
+            println!("crecimiento\tString\t{}\t{cap}\t{}\t{movido}", s.len(), s.capacity());
+        }
+    }
+}
+
+fn metodo(tipo: &str, operacion: &str, len: usize, cap: usize) {
+    println!("metodo\t{tipo}\t{operacion}\t{len}\t{cap}");
+}
+
+fn main() {
+    crecimiento("Vec<u8>", Vec::new(), |i| i as u8);
+    crecimiento("Vec<i32>", Vec::new(), |i| i as i32);
+    crecimiento("Vec<u64>", Vec::new(), |i| i as u64);
+    crecimiento_string();
+
+    let mut v: Vec<i32> = Vec::with_capacity(10);
+    metodo("Vec<i32>", "Vec::with_capacity(10)", v.len(), v.capacity());
+    v.push(1);
+    v.push(2);
+    metodo("Vec<i32>", "v.push(1); v.push(2)", v.len(), v.capacity());
+
+    let mut v = vec![1, 2, 3];
+    metodo("Vec<i32>", "vec![1, 2, 3]", v.len(), v.capacity());
+    v.reserve(10);
+    metodo("Vec<i32>", "v.reserve(10)", v.len(), v.capacity());
+    v.shrink_to_fit();
+    metodo("Vec<i32>", "v.shrink_to_fit()", v.len(), v.capacity());
+    v.reserve_exact(5);
+    metodo("Vec<i32>", "v.reserve_exact(5)", v.len(), v.capacity());
+    v.clear();
+    metodo("Vec<i32>", "v.clear()", v.len(), v.capacity());
+
+    let mut s = String::with_capacity(10);
+    metodo("String", "String::with_capacity(10)", s.len(), s.capacity());
+    s.push_str("Hola");
+    metodo("String", "s.push_str(\"Hola\")", s.len(), s.capacity());
+
+    let mut s = String::from("Hola");
+    metodo("String", "String::from(\"Hola\")", s.len(), s.capacity());
+    s.reserve(10);
+    metodo("String", "s.reserve(10)", s.len(), s.capacity());
+
+    let mut s = String::with_capacity(100);
+    s.push_str("Hola");
+    metodo("String", "with_capacity(100) + push_str(\"Hola\")", s.len(), s.capacity());
+    s.shrink_to_fit();
+    metodo("String", "s.shrink_to_fit()", s.len(), s.capacity());
+
+    let mut s = String::new();
+    s.reserve_exact(10);
+    metodo("String", "String::new(); s.reserve_exact(10)", s.len(), s.capacity());
+}
@@ -0,0 +1,56 @@
+// Sonda de `libro generar caracteres`: métodos de clasificación de `char`.
+//
+// Imprime líneas separadas por tabuladores:
+//   celda <tabla> <fila> <columna> <valor>
+// (ver `src/matriz.rs`). Cada fila es un carácter escrito con `{:?}`, así
+// los invisibles se ven como `'\u{200b}'`.
+
+use std::fmt::Display;
+
+fn celda(tabla: &str, fila: &str, columna: &str, valor: impl Display) {
+    println!("celda\t{tabla}\t{fila}\t{columna}\t{valor}");
+}
+
+macro_rules! predicados {
+    ($fila:expr, $c:expr, $($metodo:ident),*) => {$(
+        celda("clasificacion", $fila, stringify!($metodo), $c.$metodo());
+    )*};
+}
+
+fn main() {
+    let caracteres = [
+        'A', 'z', '7', '_', ' ', '\t', 'ñ', 'é', 'Ö', 'ß', '٣', '७', '½', 'Ⅻ', '字', '🦀',
+        '\u{A0}', '\u{200B}',
+    ];
+
+    for c in caracteres {
+        let fila = format!("{c:?}");
+        let fila = fila.as_str();
+        let mut utf8 = [0; 4];
+        let bytes: Vec<String> = c
+            .encode_utf8(&mut utf8)
+            .bytes()
+            .map(|b| format!("{b:02X}"))
+            .collect();
+
+        celda("unicode", fila, "Código", format!("U+{:04X}", c as u32));
+        celda("unicode", fila, "len_utf8", c.len_utf8());
+        celda("unicode", fila, "Bytes UTF-8", bytes.join(" "));
+        celda("unicode", fila, "to_uppercase", format!("{:?}", c.to_uppercase().to_string()));
+        celda("unicode", fila, "to_digit(10)", format!("{:?}", c.to_digit(10)));
+
+        predicados!(
+            fila,
+            c,
+            is_alphabetic,
+            is_alphanumeric,
+            is_numeric,
+            is_ascii_digit,
+            is_uppercase,
+            is_lowercase,
+            is_whitespace,
+            is_control,
+            is_ascii
+        );
+    }
+}
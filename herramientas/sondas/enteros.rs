@@ -0,0 +1,56 @@
+// Sonda de `libro generar enteros`: límites y tamaños de los enteros.
+//
+// Imprime líneas separadas por tabuladores:
+//   tipo <nombre> <BITS> <size_of> <MIN> <MAX>
+//   arquitectura <ARCH> <bits de puntero>
+//   sufijo <literal> <tipo> <size_of>
+//   afirmacion <expresión> <resultado {:?}>
+
+use std::any::type_name_of_val;
+use std::mem::{size_of, size_of_val};
+
+macro_rules! tipos {
+    ($($t:ty),*) => {$(
+        println!(
+            "tipo\t{}\t{}\t{}\t{}\t{}",
+            stringify!($t), <$t>::BITS, size_of::<$t>(), <$t>::MIN, <$t>::MAX
+        );
+    )*};
+}
+
+macro_rules! sufijos {
+    ($($lit:expr),*) => {$(
+        println!("sufijo\t{}\t{}\t{}", stringify!($lit), type_name_of_val(&$lit), size_of_val(&$lit));
+    )*};
+}
+
+macro_rules! afirmaciones {
+    ($($e:expr),* $(,)?) => {$(
+        println!("afirmacion\t{}\t{:?}", stringify!($e), $e);
+    )*};
+}
+
+fn main() {
+    tipos!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    println!("arquitectura\t{}\t{}", std::env::consts::ARCH, usize::BITS);
+
+    sufijos!(
+        100i8, 100i16, 100i32, 100i64, 100i128, 100isize,
+        255u8, 100u16, 100u32, 100u64, 100u128, 500usize
+    );
+
+    let x: u8 = 255;
+    afirmaciones!(
+        x.wrapping_add(1),
+        x.checked_add(1),
+        x.saturating_add(1),
+        x.overflowing_add(1),
+        0u8.wrapping_sub(1),
+        0u8.checked_sub(1),
+        i8::MIN.checked_abs(),
+        i8::MAX.wrapping_add(1),
+        1000u32 as u8,
+        -1i32 as u32,
+        300i32 as u8,
+    );
+}
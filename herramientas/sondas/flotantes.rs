@@ -0,0 +1,94 @@
+// Sonda de `libro generar flotantes`: valores especiales, redondeo y
+// formato de `f32`/`f64`.
+//
+// Imprime líneas separadas por tabuladores:
+//   celda <tabla> <fila> <columna> <valor>
+// (ver `src/matriz.rs`). El redondeo se imprime con `{:?}` para que se vea
+// `3.0` y `-0.0`.
+
+#![allow(invalid_nan_comparisons)]
+
+use std::fmt::Display;
+
+fn celda(tabla: &str, fila: &str, columna: &str, valor: impl Display) {
+    println!("celda\t{tabla}\t{fila}\t{columna}\t{valor}");
+}
+
+macro_rules! constantes {
+    ($($t:ident),*) => {$(
+        let t = stringify!($t);
+        celda("tipos", "Bits", t, std::mem::size_of::<$t>() * 8);
+        celda("tipos", "DIGITS", t, $t::DIGITS);
+        celda("tipos", "MANTISSA_DIGITS", t, $t::MANTISSA_DIGITS);
+        celda("tipos", "MIN", t, format!("{:e}", $t::MIN));
+        celda("tipos", "MAX", t, format!("{:e}", $t::MAX));
+        celda("tipos", "EPSILON", t, format!("{:e}", $t::EPSILON));
+        celda("tipos", "MIN_POSITIVE", t, format!("{:e}", $t::MIN_POSITIVE));
+        celda("tipos", "from_bits(1)", t, format!("{:e}", $t::from_bits(1)));
+    )*};
+}
+
+macro_rules! operaciones {
+    ($($e:expr),* $(,)?) => {$(
+        let fila = stringify!($e);
+        celda("operaciones", fila, "{}", $e);
+        celda("operaciones", fila, "{:?}", format!("{:?}", $e));
+    )*};
+}
+
+fn main() {
+    constantes!(f32, f64);
+
+    let entradas: [(&str, f64); 12] = [
+        ("3.7", 3.7),
+        ("-3.7", -3.7),
+        ("2.5", 2.5),
+        ("-2.5", -2.5),
+        ("0.5", 0.5),
+        ("0.0", 0.0),
+        ("-0.0", -0.0),
+        ("f64::MIN_POSITIVE", f64::MIN_POSITIVE),
+        ("f64::from_bits(1)", f64::from_bits(1)),
+        ("f64::INFINITY", f64::INFINITY),
+        ("f64::NEG_INFINITY", f64::NEG_INFINITY),
+        ("f64::NAN", f64::NAN),
+    ];
+
+    for (fila, x) in entradas {
+        celda("especiales", fila, "is_nan", x.is_nan());
+        celda("especiales", fila, "is_infinite", x.is_infinite());
+        celda("especiales", fila, "is_finite", x.is_finite());
+        celda("especiales", fila, "is_normal", x.is_normal());
+        celda("especiales", fila, "is_subnormal", x.is_subnormal());
+        celda("especiales", fila, "is_sign_negative", x.is_sign_negative());
+
+        celda("redondeo", fila, "floor", format!("{:?}", x.floor()));
+        celda("redondeo", fila, "ceil", format!("{:?}", x.ceil()));
+        celda("redondeo", fila, "round", format!("{:?}", x.round()));
+        celda("redondeo", fila, "trunc", format!("{:?}", x.trunc()));
+        celda("redondeo", fila, "fract", format!("{:?}", x.fract()));
+        celda("redondeo", fila, "abs", format!("{:?}", x.abs()));
+        celda("redondeo", fila, "signum", format!("{:?}", x.signum()));
+
+        celda("formato", fila, "{}", x);
+        celda("formato", fila, "{:?}", format!("{x:?}"));
+        celda("formato", fila, "{:.2}", format!("{x:.2}"));
+        celda("formato", fila, "{:e}", format!("{x:e}"));
+    }
+
+    operaciones!(
+        0.1 + 0.2,
+        0.1 + 0.2 == 0.3,
+        0.1_f32 + 0.2_f32,
+        1.0 / 0.0,
+        -1.0 / 0.0,
+        0.0 / 0.0,
+        (-1.0_f64).sqrt(),
+        f64::NAN == f64::NAN,
+        -0.0 == 0.0,
+        3.14159265359_f64 as f32,
+        3.99_f64 as i32,
+        f64::NAN as i32,
+        1e10_f64 as i32,
+    );
+}
@@ -0,0 +1,271 @@
+//! Verificación de los valores que la prosa afirma en comentarios.
+//!
+//! En un fragmento marcado con `afirmaciones`, cada `let` seguido de un
+//! comentario con un valor (`let a = x.wrapping_add(1);  // 0`) se
+//! comprueba ejecutando el fragmento. El valor puede ir también en una línea
+//! de comentario siguiente con la forma `// y = 232 (explicación)`.
+//!
+//! Atributos relacionados:
+//!
+//! - `release`: compila con `-O` (sin comprobaciones de desbordamiento).
+//! - `panico=<texto>`: el fragmento debe entrar en pánico, en modo debug,
+//!   con un mensaje que contenga `<texto>`.
+
+use crate::cache::Compilaciones;
+use crate::fragmentos::{Fragmento, comentario, sin_aclaracion, sin_comentario};
+use crate::rustc::EDICION;
+use crate::{Error, Libro, Resultado};
+
+/// Un valor afirmado en el texto y el que produce Rust.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Afirmacion {
+    /// Línea del fragmento (desde 1).
+    pub linea: usize,
+    /// Patrón del `let`, que también sirve como expresión: `a`, `(d, overflow)`.
+    pub patron: String,
+    pub esperado: String,
+    pub real: String,
+}
+
+impl Afirmacion {
+    pub fn cumple(&self) -> bool {
+        normalizar(&self.esperado) == normalizar(&self.real)
+    }
+}
+
+fn normalizar(valor: &str) -> String {
+    valor.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Patrón de un `let` (`let mut a: u8 = ...` → `a`).
+fn patron(linea: &str) -> Option<String> {
+    let resto = sin_comentario(linea).trim().strip_prefix("let ")?;
+    let resto = resto.strip_prefix("mut ").unwrap_or(resto);
+    let (izquierda, _) = resto.split_once('=')?;
+    let mut profundidad = 0;
+    let mut fin = izquierda.len();
+    for (i, c) in izquierda.char_indices() {
+        match c {
+            '(' | '[' => profundidad += 1,
+            ')' | ']' => profundidad -= 1,
+            ':' if profundidad == 0 => {
+                fin = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    Some(izquierda[..fin].trim().to_string())
+}
+
+fn parece_valor(texto: &str) -> bool {
+    texto.starts_with(|c: char| c.is_ascii_digit() || "-([\"'".contains(c))
+        || ["true", "false", "None", "Some(", "Ok(", "Err("]
+            .iter()
+            .any(|p| texto.starts_with(p))
+}
+
+/// Valor afirmado en `comentario` para `patron`, si lo hay.
+fn valor(comentario: &str, patron: &str, final_de_linea: bool) -> Option<String> {
+    let (texto, explicito) = match comentario.strip_prefix(patron) {
+        Some(resto) if resto.trim_start().starts_with('=') => {
+            (resto.trim_start()[1..].trim(), true)
+        }
+        _ => (comentario, false),
+    };
+    if !(explicito || final_de_linea && parece_valor(texto)) {
+        return None;
+    }
+    // Una explicación entre paréntesis tras el valor no forma parte de él.
+    Some(sin_aclaracion(texto).trim().to_string())
+}
+
+/// Afirmaciones de un fragmento: (línea, patrón, valor esperado).
+pub fn extraer(codigo: &str) -> Vec<(usize, String, String)> {
+    let lineas: Vec<&str> = codigo.lines().collect();
+    let mut afirmaciones = Vec::new();
+    for (i, linea) in lineas.iter().enumerate() {
+        let Some(patron) = patron(linea) else {
+            continue;
+        };
+        let mut esperado = comentario(linea).and_then(|c| valor(c, &patron, true));
+        for siguiente in &lineas[i + 1..] {
+            if !siguiente.trim_start().starts_with("//") {
+                break;
+            }
+            let c = comentario(siguiente).unwrap_or_default();
+            esperado = esperado.or_else(|| valor(c, &patron, false));
+        }
+        if let Some(esperado) = esperado {
+            afirmaciones.push((i + 1, patron, esperado));
+        }
+    }
+    afirmaciones
+}
+
+/// Ejecuta el fragmento y devuelve cada afirmación con su valor real.
+pub fn verificar(
+    fragmento: &Fragmento,
+    compilaciones: &mut Compilaciones,
+) -> Resultado<Vec<Afirmacion>> {
+    let esperadas = extraer(&fragmento.codigo);
+    let mut codigo = String::new();
+    for (i, linea) in fragmento.codigo.lines().enumerate() {
+        codigo.push_str(linea);
+        codigo.push('\n');
+        if let Some((n, patron, _)) = esperadas.iter().find(|(n, _, _)| *n == i + 1) {
+            codigo.push_str(&format!(
+                "println!(\"__libro_afirmacion\\t{n}\\t{{:?}}\", {patron});\n"
+            ));
+        }
+    }
+    let instrumentado = Fragmento {
+        codigo,
+        ..fragmento.clone()
+    };
+
+    let stdout = compilaciones
+        .ejecutar(&instrumentado.programa(), fragmento.opciones())
+        .map_err(|e| Error::Libro(format!("{}: {e}", fragmento.ubicacion())))?;
+
+    let mut afirmaciones = Vec::new();
+    for linea in stdout.lines() {
+        let Some(resto) = linea.strip_prefix("__libro_afirmacion\t") else {
+            continue;
+        };
+        let Some((n, real)) = resto.split_once('\t') else {
+            continue;
+        };
+        let n: usize = n.parse().unwrap_or_default();
+        if let Some((_, patron, esperado)) = esperadas.iter().find(|(m, _, _)| *m == n) {
+            afirmaciones.push(Afirmacion {
+                linea: n,
+                patron: patron.clone(),
+                esperado: esperado.clone(),
+                real: real.to_string(),
+            });
+        }
+    }
+    Ok(afirmaciones)
+}
+
+/// Comprueba que el fragmento entra en pánico con el mensaje indicado.
+pub fn verificar_panico(
+    fragmento: &Fragmento,
+    mensaje: &str,
+    compilaciones: &mut Compilaciones,
+) -> Resultado<String> {
+    let ejecucion = compilaciones
+        .correr(&fragmento.programa(), EDICION, fragmento.opciones())
+        .and_then(|e| e.map_err(Error::Compilacion))
+        .map_err(|e| Error::Libro(format!("{}: {e}", fragmento.ubicacion())))?;
+    let panico = ejecucion
+        .stderr
+        .lines()
+        .skip_while(|l| !l.contains("panicked at"))
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+    if ejecucion.exito || !panico.contains(mensaje) {
+        return Err(Error::Libro(format!(
+            "{}: el texto afirma un pánico «{mensaje}», pero el programa {}",
+            fragmento.ubicacion(),
+            if ejecucion.exito {
+                String::from("terminó bien")
+            } else {
+                format!("falló con:\n{}", ejecucion.stderr)
+            }
+        )));
+    }
+    Ok(panico)
+}
+
+/// Verifica todos los fragmentos marcados del libro. Devuelve cuántas
+/// afirmaciones se comprobaron y falla con la lista de las que no se cumplen.
+pub fn comprobar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "afirmaciones")?;
+    let mut n = 0;
+    let mut fallos = Vec::new();
+    for fragmento in libro.fragmentos()? {
+        if let Some(mensaje) = fragmento.atributos.valor("panico") {
+            verificar_panico(&fragmento, mensaje, &mut compilaciones)?;
+            n += 1;
+        }
+        if !fragmento.atributos.tiene("afirmaciones") {
+            continue;
+        }
+        for a in verificar(&fragmento, &mut compilaciones)? {
+            n += 1;
+            if !a.cumple() {
+                fallos.push(format!(
+                    "{}:{}: `{}` vale {}, pero el texto dice {}",
+                    fragmento.archivo.display(),
+                    fragmento.linea + a.linea,
+                    a.patron,
+                    a.real,
+                    a.esperado
+                ));
+            }
+        }
+    }
+    compilaciones.escribir(libro)?;
+    if !fallos.is_empty() {
+        return Err(Error::Libro(fallos.join("\n")));
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patron_sin_mut_ni_tipo() {
+        assert_eq!(patron("let a = 5;").as_deref(), Some("a"));
+        assert_eq!(
+            patron("    let mut b: u8 = 255;  // 255").as_deref(),
+            Some("b")
+        );
+        assert_eq!(
+            patron("let (d, overflow): (u8, bool) = x.overflowing_add(1);").as_deref(),
+            Some("(d, overflow)")
+        );
+        assert_eq!(patron("x = 5;  // let y = 3"), None);
+    }
+
+    #[test]
+    fn valor_explicito_o_al_final_de_la_linea() {
+        assert_eq!(
+            valor("y = 232 (explicación)", "y", false).as_deref(),
+            Some("232")
+        );
+        assert_eq!(valor("0", "a", true).as_deref(), Some("0"));
+        assert_eq!(
+            valor("(0, true)", "(d, overflow)", true).as_deref(),
+            Some("(0, true)")
+        );
+        assert_eq!(
+            valor("Some(3) (cabe en u8)", "c", true).as_deref(),
+            Some("Some(3)")
+        );
+        // Un valor suelto en una línea siguiente puede ser de otra cosa.
+        assert_eq!(valor("0", "a", false), None);
+        assert_eq!(valor("se desborda y vuelve a empezar", "a", true), None);
+    }
+
+    #[test]
+    fn extraer_valores_en_la_linea_y_en_la_siguiente() {
+        let codigo = "let x: u8 = 255;\n\
+            let (d, overflow) = x.overflowing_add(1);  // (0, true)\n\
+            let y = x.wrapping_sub(23);\n\
+            // y = 232 (explicación)\n\
+            let z = x.wrapping_add(1);  // vuelve a empezar desde cero\n";
+        assert_eq!(
+            extraer(codigo),
+            vec![
+                (2, String::from("(d, overflow)"), String::from("(0, true)")),
+                (3, String::from("y"), String::from("232")),
+            ]
+        );
+    }
+}
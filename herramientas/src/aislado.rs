@@ -0,0 +1,419 @@
+//! Ejecución aislada de los programas del libro.
+//!
+//! Los ejemplos son código pensado para el lector, no para una máquina de
+//! integración continua: hay bucles infinitos a propósito (`loop { ... }`),
+//! arrays que desbordan el stack y programas que esperan una entrada. Todo
+//! programa compilado a partir de un fragmento se ejecuta con [`ejecutar`]:
+//!
+//! - Sin red: en Linux, el hijo entra en un espacio de nombres de usuario y
+//!   de red propios (`unshare(CLONE_NEWUSER | CLONE_NEWNET)`) antes de
+//!   `exec`; si el sistema no permite espacios de usuario, se intenta solo
+//!   el de red, y si tampoco lo permite, el programa corre con red y solo
+//!   con los límites, tras un aviso.
+//! - Con límites (`setrlimit`): tiempo de CPU, memoria, tamaño de archivos
+//!   escritos, archivos abiertos y tamaño del stack del hilo principal.
+//! - Con un tiempo máximo de reloj: pasado [`Limites::tiempo`], se mata el
+//!   grupo de procesos entero.
+//! - Con la salida recortada a [`Limites::salida`] bytes por flujo.
+//!
+//! No aísla el sistema de archivos: el programa corre en su directorio
+//! temporal, pero podría leer fuera de él. Fuera de Linux en x86_64 y aarch64
+//! solo se aplican el tiempo máximo y el recorte de la salida, y fuera de
+//! Unix tampoco se mata a los procesos que haya creado el programa.
+
+use std::io::{Read, Write};
+#[cfg(unix)]
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::Resultado;
+
+/// Límites de una ejecución.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limites {
+    /// Tiempo máximo de reloj; el de CPU se limita a un segundo más.
+    pub tiempo: Duration,
+    /// Memoria virtual máxima, en bytes.
+    pub memoria: u64,
+    /// Tamaño del stack del hilo principal, en bytes.
+    pub pila: u64,
+    /// Bytes que se conservan de stdout y de stderr.
+    pub salida: usize,
+    /// Tamaño máximo de un archivo escrito, en bytes.
+    pub archivos: u64,
+    /// Si el programa puede usar la red.
+    pub red: bool,
+}
+
+impl Default for Limites {
+    /// Límites holgados para los ejemplos del libro: 10 s, 1 GiB de memoria
+    /// y 8 MiB de stack, el valor habitual en Linux.
+    fn default() -> Limites {
+        Limites {
+            tiempo: Duration::from_secs(10),
+            memoria: 1 << 30,
+            pila: 8 << 20,
+            salida: 64 << 10,
+            archivos: 1 << 20,
+            red: false,
+        }
+    }
+}
+
+/// Cómo terminó un programa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Terminacion {
+    /// Terminó con este código (`0` si salió bien, `101` tras un pánico).
+    Codigo(i32),
+    /// Lo terminó una señal (`SIGABRT` al desbordar el stack).
+    Senal(i32),
+    /// Se mató al pasar de [`Limites::tiempo`].
+    Agotado,
+}
+
+/// Resultado de [`ejecutar`].
+#[derive(Debug, Clone)]
+pub struct Informe {
+    pub stdout: String,
+    pub stderr: String,
+    pub terminacion: Terminacion,
+    /// Si se recortó stdout o stderr.
+    pub recortada: bool,
+}
+
+impl Informe {
+    pub fn exito(&self) -> bool {
+        self.terminacion == Terminacion::Codigo(0)
+    }
+
+    /// Si el programa abortó por desbordar el stack.
+    pub fn desborda_pila(&self) -> bool {
+        matches!(self.terminacion, Terminacion::Senal(_))
+            && self.stderr.contains("has overflowed its stack")
+    }
+}
+
+/// Ejecuta `orden` con `limites`, con la entrada estándar cerrada y sin
+/// las variables que cambian la salida de un pánico (`RUST_BACKTRACE`),
+/// salvo que `orden` las fije.
+pub fn ejecutar(orden: &mut Command, limites: &Limites) -> Resultado<Informe> {
+    ejecutar_con_entrada(orden, limites, None)
+}
+
+/// Como [`ejecutar`], escribiendo `entrada` en la entrada estándar (lo que
+/// teclearía el lector) y cerrándola después.
+pub fn ejecutar_con_entrada(
+    orden: &mut Command,
+    limites: &Limites,
+    entrada: Option<&str>,
+) -> Resultado<Informe> {
+    for variable in ["RUST_BACKTRACE", "RUST_LIB_BACKTRACE"] {
+        if !orden.get_envs().any(|(clave, _)| clave == variable) {
+            orden.env_remove(variable);
+        }
+    }
+    orden
+        .stdin(if entrada.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    orden.process_group(0);
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    {
+        if !limites.red && !linux::red_aislable() {
+            avisar_sin_aislamiento();
+        }
+        let limites = *limites;
+        // SAFETY: entre `fork` y `exec` solo se hacen llamadas al sistema,
+        // sin reservar memoria ni tomar cerrojos.
+        unsafe {
+            orden.pre_exec(move || linux::aplicar(&limites));
+        }
+    }
+    let mut hijo = orden.spawn()?;
+    if let (Some(mut stdin), Some(entrada)) = (hijo.stdin.take(), entrada) {
+        let entrada = entrada.to_string();
+        // Si el programa no lee todo, la escritura falla al cerrarse la
+        // tubería; no es un error del programa.
+        thread::spawn(move || {
+            let _ = stdin.write_all(entrada.as_bytes());
+        });
+    }
+    // Se leen en hilos aparte: un programa que escribe mucho se bloquearía
+    // con la tubería llena y parecería no terminar.
+    let stdout = leer_en_hilo(hijo.stdout.take(), limites.salida);
+    let stderr = leer_en_hilo(hijo.stderr.take(), limites.salida);
+
+    let inicio = Instant::now();
+    let estado = loop {
+        if let Some(estado) = hijo.try_wait()? {
+            break Some(estado);
+        }
+        if inicio.elapsed() > limites.tiempo {
+            matar_grupo(&mut hijo)?;
+            hijo.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let (stdout, recortado_stdout) = stdout.join().unwrap_or_default();
+    let (stderr, recortado_stderr) = stderr.join().unwrap_or_default();
+    let terminacion = match estado {
+        None => Terminacion::Agotado,
+        Some(estado) => match (estado.code(), senal(estado)) {
+            (Some(codigo), _) => Terminacion::Codigo(codigo),
+            (None, Some(senal)) => Terminacion::Senal(senal),
+            (None, None) => Terminacion::Codigo(-1),
+        },
+    };
+    Ok(Informe {
+        stdout,
+        stderr,
+        terminacion,
+        recortada: recortado_stdout || recortado_stderr,
+    })
+}
+
+/// Avisa, una sola vez, de que los programas van a poder usar la red.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+fn avisar_sin_aislamiento() {
+    static AVISO: std::sync::Once = std::sync::Once::new();
+    AVISO.call_once(|| {
+        eprintln!(
+            "aviso: el sistema no permite espacios de nombres de red; los \
+             programas se ejecutan con red, solo con los límites"
+        );
+    });
+}
+
+/// La señal que terminó el proceso; fuera de Unix no hay.
+fn senal(estado: ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    return estado.signal();
+    #[cfg(not(unix))]
+    {
+        let _ = estado;
+        None
+    }
+}
+
+/// Lee `flujo` hasta el final, guardando como mucho `tope` bytes.
+fn leer_en_hilo(
+    flujo: Option<impl Read + Send + 'static>,
+    tope: usize,
+) -> thread::JoinHandle<(String, bool)> {
+    thread::spawn(move || {
+        let mut texto = Vec::new();
+        let mut recortado = false;
+        if let Some(mut flujo) = flujo {
+            let mut bloque = [0; 8192];
+            while let Ok(n @ 1..) = flujo.read(&mut bloque) {
+                let cabe = n.min(tope - texto.len());
+                texto.extend_from_slice(&bloque[..cabe]);
+                recortado |= cabe < n;
+            }
+        }
+        (String::from_utf8_lossy(&texto).into_owned(), recortado)
+    })
+}
+
+/// Mata al hijo y a los procesos que haya creado.
+fn matar_grupo(hijo: &mut std::process::Child) -> Resultado<()> {
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    linux::matar_grupo(hijo.id());
+    hijo.kill()?;
+    Ok(())
+}
+
+/// Llamadas al sistema de Linux. Se declaran a mano para no depender del
+/// crate `libc`; los números y la forma de `struct rlimit` son los de x86_64
+/// y aarch64, y en otras arquitecturas el módulo no se compila.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod linux {
+    use std::ffi::{c_int, c_ulong};
+    use std::io;
+    use std::os::unix::process::CommandExt;
+    use std::process::Command;
+    use std::sync::OnceLock;
+
+    use super::Limites;
+
+    #[repr(C)]
+    struct Rlimit {
+        actual: c_ulong,
+        maximo: c_ulong,
+    }
+
+    unsafe extern "C" {
+        fn getrlimit(recurso: c_int, limite: *mut Rlimit) -> c_int;
+        fn setrlimit(recurso: c_int, limite: *const Rlimit) -> c_int;
+        fn unshare(banderas: c_int) -> c_int;
+        fn kill(pid: c_int, senal: c_int) -> c_int;
+        fn _exit(codigo: c_int) -> !;
+    }
+
+    const RLIMIT_CPU: c_int = 0;
+    const RLIMIT_FSIZE: c_int = 1;
+    const RLIMIT_STACK: c_int = 3;
+    const RLIMIT_CORE: c_int = 4;
+    const RLIMIT_NOFILE: c_int = 7;
+    const RLIMIT_AS: c_int = 9;
+
+    const CLONE_NEWUSER: c_int = 0x1000_0000;
+    const CLONE_NEWNET: c_int = 0x4000_0000;
+
+    const SIGKILL: c_int = 9;
+
+    /// Baja el límite a `valor`, o lo deja como está si ya era menor: sin
+    /// privilegios no se puede subir el límite máximo.
+    fn limitar(recurso: c_int, valor: u64) -> io::Result<()> {
+        let mut limite = Rlimit {
+            actual: 0,
+            maximo: 0,
+        };
+        // SAFETY: `limite` es un `struct rlimit` válido durante las llamadas.
+        if unsafe { getrlimit(recurso, &mut limite) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        limite.maximo = limite.maximo.min(valor as c_ulong);
+        limite.actual = limite.maximo;
+        if unsafe { setrlimit(recurso, &limite) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Intenta entrar en un espacio de nombres de red propio, con uno de
+    /// usuario si hace falta para tener permiso.
+    fn aislar_red() -> bool {
+        // SAFETY: `unshare` solo recibe banderas.
+        unsafe { unshare(CLONE_NEWUSER | CLONE_NEWNET) == 0 || unshare(CLONE_NEWNET) == 0 }
+    }
+
+    /// Si el sistema permite aislar la red. Se prueba una vez, en un hijo que
+    /// termina antes de llegar a `exec`.
+    pub fn red_aislable() -> bool {
+        static AISLABLE: OnceLock<bool> = OnceLock::new();
+        *AISLABLE.get_or_init(|| {
+            let mut prueba = Command::new("true");
+            // SAFETY: entre `fork` y `_exit` solo se hacen llamadas al sistema.
+            unsafe {
+                prueba.pre_exec(|| _exit(if aislar_red() { 0 } else { 1 }));
+            }
+            prueba.status().is_ok_and(|estado| estado.success())
+        })
+    }
+
+    /// Se ejecuta en el hijo, entre `fork` y `exec`. Si no se puede aislar la
+    /// red, se sigue solo con los límites: [`red_aislable`] ya lo avisó.
+    pub fn aplicar(limites: &Limites) -> io::Result<()> {
+        if !limites.red {
+            aislar_red();
+        }
+        limitar(RLIMIT_CPU, limites.tiempo.as_secs() + 1)?;
+        limitar(RLIMIT_AS, limites.memoria)?;
+        limitar(RLIMIT_STACK, limites.pila)?;
+        limitar(RLIMIT_FSIZE, limites.archivos)?;
+        limitar(RLIMIT_NOFILE, 64)?;
+        limitar(RLIMIT_CORE, 0)?;
+        Ok(())
+    }
+
+    pub fn matar_grupo(pid: u32) {
+        // SAFETY: el hijo es líder de su grupo (`process_group(0)`); un pid
+        // negativo envía la señal a todo el grupo.
+        unsafe {
+            kill(-(pid as c_int), SIGKILL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rustc;
+
+    fn limites(tiempo_ms: u64) -> Limites {
+        Limites {
+            tiempo: Duration::from_millis(tiempo_ms),
+            ..Limites::default()
+        }
+    }
+
+    #[test]
+    fn mata_un_bucle_al_agotar_el_tiempo() {
+        let programa = rustc::compilar("fn main() { loop { std::hint::black_box(0); } }").unwrap();
+        let inicio = Instant::now();
+        let informe = programa.correr_con(&limites(300)).unwrap();
+        assert_eq!(informe.terminacion, Terminacion::Agotado);
+        assert!(!informe.exito());
+        assert!(inicio.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn recorta_la_salida_larga() {
+        let programa =
+            rustc::compilar(r#"fn main() { for _ in 0..10_000 { println!("0123456789"); } }"#)
+                .unwrap();
+        let informe = programa
+            .correr_con(&Limites {
+                salida: 1000,
+                ..Limites::default()
+            })
+            .unwrap();
+        assert!(informe.exito());
+        assert!(informe.recortada);
+        assert_eq!(informe.stdout.len(), 1000);
+        assert!(informe.stdout.starts_with("0123456789\n"));
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[test]
+    fn sin_red_no_se_conecta_a_localhost() {
+        let escucha = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let puerto = escucha.local_addr().unwrap().port();
+        let programa = rustc::compilar(&format!(
+            r#"fn main() {{
+                match std::net::TcpStream::connect("127.0.0.1:{puerto}") {{
+                    Ok(_) => println!("conectado"),
+                    Err(_) => println!("sin red"),
+                }}
+            }}"#
+        ))
+        .unwrap();
+        let con_red = programa
+            .correr_con(&Limites {
+                red: true,
+                ..Limites::default()
+            })
+            .unwrap();
+        assert_eq!(con_red.stdout, "conectado\n");
+        if !linux::red_aislable() {
+            eprintln!("el sistema no permite espacios de nombres de red");
+            return;
+        }
+        let sin_red = programa.correr_con(&Limites::default()).unwrap();
+        assert_eq!(sin_red.stdout, "sin red\n");
+    }
+}
@@ -0,0 +1,118 @@
+//! Árboles de directorios dibujados a partir del disco, al estilo de `tree`.
+//!
+//! ```text
+//! hola_mundo/
+//! ├── Cargo.toml
+//! └── src/
+//!     └── main.rs
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use crate::Resultado;
+
+/// Dibuja `dir` con `raiz` como nombre de la primera línea. Los directorios
+/// llevan `/` al final; los ocultos (`.git`, `.fingerprint`) no se muestran,
+/// igual que en `tree`. Por debajo de `profundidad` niveles, los
+/// directorios aparecen sin su contenido.
+pub fn dibujar(dir: &Path, raiz: &str, profundidad: usize) -> Resultado<String> {
+    let mut salida = format!("{}/\n", raiz.trim_end_matches('/'));
+    ramas(dir, "", profundidad, &mut salida)?;
+    Ok(salida)
+}
+
+fn ramas(dir: &Path, prefijo: &str, profundidad: usize, salida: &mut String) -> Resultado<()> {
+    if profundidad == 0 {
+        return Ok(());
+    }
+    let mut entradas: Vec<(String, bool)> = Vec::new();
+    for entrada in fs::read_dir(dir)? {
+        let entrada = entrada?;
+        let nombre = entrada.file_name().to_string_lossy().into_owned();
+        if !nombre.starts_with('.') {
+            entradas.push((nombre, entrada.file_type()?.is_dir()));
+        }
+    }
+    entradas.sort();
+
+    for (i, (nombre, es_dir)) in entradas.iter().enumerate() {
+        let ultima = i + 1 == entradas.len();
+        let (rama, continuacion) = if ultima {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let barra = if *es_dir { "/" } else { "" };
+        salida.push_str(&format!("{prefijo}{rama}{nombre}{barra}\n"));
+        if *es_dir {
+            ramas(
+                &dir.join(nombre),
+                &format!("{prefijo}{continuacion}"),
+                profundidad - 1,
+                salida,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Rutas de `dir` (relativas, con `/` final en los directorios), sin los
+/// ocultos ni lo que esté en `ignorar` (p. ej. `target`).
+pub fn rutas(dir: &Path, ignorar: &[&str]) -> Resultado<Vec<String>> {
+    let mut rutas = Vec::new();
+    recorrer(dir, "", ignorar, &mut rutas)?;
+    rutas.sort();
+    Ok(rutas)
+}
+
+fn recorrer(dir: &Path, prefijo: &str, ignorar: &[&str], rutas: &mut Vec<String>) -> Resultado<()> {
+    for entrada in fs::read_dir(dir)? {
+        let entrada = entrada?;
+        let nombre = entrada.file_name().to_string_lossy().into_owned();
+        if nombre.starts_with('.') || ignorar.contains(&nombre.as_str()) {
+            continue;
+        }
+        if entrada.file_type()?.is_dir() {
+            let ruta = format!("{prefijo}{nombre}/");
+            rutas.push(ruta.clone());
+            recorrer(&entrada.path(), &ruta, &[], rutas)?;
+        } else {
+            rutas.push(format!("{prefijo}{nombre}"));
+        }
+    }
+    Ok(())
+}
+
+/// Interpreta un árbol dibujado a mano. Devuelve el nombre de la raíz y las
+/// rutas, como [`rutas`]. Acepta `├──`/`└──` y `╠──`/`╚──`, y descarta
+/// los comentarios que siguen al nombre (`# ...`, `<- ...`).
+pub fn leer(dibujo: &str) -> Option<(String, Vec<String>)> {
+    let mut lineas = dibujo.lines().filter(|l| !l.trim().is_empty());
+    let raiz = lineas.next()?.split_whitespace().next()?.to_string();
+    // (columna de la rama, ruta del directorio)
+    let mut pila: Vec<(usize, String)> = Vec::new();
+    let mut rutas = Vec::new();
+    for linea in lineas {
+        let columna = linea
+            .char_indices()
+            .find(|(_, c)| matches!(c, '├' | '└' | '╠' | '╚'))
+            .map(|(i, _)| linea[..i].chars().count())?;
+        let nombre = linea
+            .split_once("── ")
+            .map(|(_, resto)| resto)?
+            .split_whitespace()
+            .next()?;
+        while pila.last().is_some_and(|(c, _)| *c >= columna) {
+            pila.pop();
+        }
+        let padre = pila.last().map(|(_, r)| r.as_str()).unwrap_or("");
+        let ruta = format!("{padre}{nombre}");
+        if ruta.ends_with('/') {
+            pila.push((columna, ruta.clone()));
+        }
+        rutas.push(ruta);
+    }
+    rutas.sort();
+    Some((raiz, rutas))
+}
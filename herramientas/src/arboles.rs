@@ -0,0 +1,202 @@
+//! Árboles de directorios de proyectos reales.
+//!
+//! Los ejemplos de varios archivos se marcan con `proyecto=<nombre>` y
+//! `archivo=<ruta>`; todos los fragmentos de un mismo proyecto se escriben
+//! en un proyecto `cargo` que debe compilar. Su carpeta `src/` se dibuja en
+//! `build/generado/arboles/<nombre>.typ`.
+//!
+//! Un árbol dibujado a mano (por ejemplo, con comentarios) se marca con
+//! `// libro: arbol=proyecto/<nombre>` o `// libro: arbol=cargo-new/<nombre>`
+//! antes de su bloque. Si sus archivos no coinciden con los del proyecto
+//! real, `libro generar` falla. Si la raíz dibujada es `src/`, se compara
+//! solo esa carpeta.
+
+use std::fs;
+use std::path::Path;
+
+use crate::cargo::{Proyecto, Sesion};
+use crate::fragmentos::{self, Atributos, Fragmento};
+use crate::{Error, Libro, Resultado, arbol, generado, typst};
+
+/// Directorios que no cuentan al comparar un proyecto entero.
+const IGNORADOS: &[&str] = &["target", "Cargo.lock"];
+
+/// Un árbol dibujado a mano en el libro.
+#[derive(Debug, Clone)]
+pub struct Dibujo {
+    /// `archivo:línea` del bloque.
+    pub ubicacion: String,
+    /// `proyecto/<nombre>` o `cargo-new/<nombre>`.
+    pub origen: String,
+    pub texto: String,
+}
+
+/// Busca los bloques marcados con `arbol=` en los archivos del libro.
+pub fn dibujos(libro: &Libro) -> Resultado<Vec<Dibujo>> {
+    let mut dibujos = Vec::new();
+    for archivo in fragmentos::archivos(&libro.raiz)? {
+        let texto = fs::read_to_string(&archivo)?;
+        let relativo = archivo.strip_prefix(&libro.raiz).unwrap_or(&archivo);
+        let lineas: Vec<&str> = texto.lines().collect();
+        for (i, linea) in lineas.iter().enumerate() {
+            let Some(resto) = linea.trim_start().strip_prefix("// libro:") else {
+                continue;
+            };
+            let Some(origen) = Atributos::parsear(resto).valor("arbol").map(String::from) else {
+                continue;
+            };
+            let ubicacion = format!("{}:{}", relativo.display(), i + 1);
+            // El bloque puede ir precedido de una llamada `#codly(...)`.
+            let Some(apertura) =
+                (i + 1..lineas.len()).find(|&j| lineas[j].trim_start().starts_with("```"))
+            else {
+                return Err(Error::Libro(format!("{ubicacion}: `arbol=` sin bloque")));
+            };
+            let cuerpo: Vec<&str> = lineas[apertura + 1..]
+                .iter()
+                .take_while(|l| !l.trim_start().starts_with("```"))
+                .copied()
+                .collect();
+            dibujos.push(Dibujo {
+                ubicacion,
+                origen,
+                texto: cuerpo.join("\n"),
+            });
+        }
+    }
+    Ok(dibujos)
+}
+
+/// Fragmentos de cada proyecto, en el orden del libro.
+fn proyectos(fragmentos: &[Fragmento]) -> Resultado<Vec<(String, Vec<&Fragmento>)>> {
+    let mut proyectos: Vec<(String, Vec<&Fragmento>)> = Vec::new();
+    for f in fragmentos {
+        let Some(nombre) = f.atributos.valor("proyecto") else {
+            continue;
+        };
+        if f.atributos.valor("archivo").is_none() {
+            return Err(Error::Libro(format!(
+                "{}: un fragmento con `proyecto=` necesita `archivo=<ruta>`",
+                f.ubicacion()
+            )));
+        }
+        match proyectos.iter_mut().find(|(n, _)| n == nombre) {
+            Some((_, lista)) => lista.push(f),
+            None => proyectos.push((nombre.to_string(), vec![f])),
+        }
+    }
+    Ok(proyectos)
+}
+
+/// Escribe el proyecto y comprueba que compila.
+fn ensamblar(nombre: &str, fragmentos: &[&Fragmento]) -> Resultado<Proyecto> {
+    let archivos: Vec<(&str, &str)> = fragmentos
+        .iter()
+        .map(|f| {
+            (
+                f.atributos.valor("archivo").unwrap_or_default(),
+                f.codigo.as_str(),
+            )
+        })
+        .collect();
+    for (i, (ruta, _)) in archivos.iter().enumerate() {
+        if archivos[..i].iter().any(|(r, _)| r == ruta) {
+            return Err(Error::Libro(format!(
+                "{}: `{ruta}` aparece dos veces en el proyecto `{nombre}`",
+                fragmentos[i].ubicacion()
+            )));
+        }
+    }
+    let proyecto = Proyecto::con_archivos(&archivos)?;
+    proyecto.compilar().map_err(|e| {
+        Error::Libro(format!(
+            "{}: el proyecto `{nombre}` no compila: {e}",
+            fragmentos[0].ubicacion()
+        ))
+    })?;
+    Ok(proyecto)
+}
+
+/// Compara un dibujo con el directorio real.
+fn comparar(dibujo: &Dibujo, dir: &Path) -> Resultado<Option<String>> {
+    let Some((raiz, dibujadas)) = arbol::leer(&dibujo.texto) else {
+        return Err(Error::Libro(format!(
+            "{}: no se entiende el árbol dibujado",
+            dibujo.ubicacion
+        )));
+    };
+    let reales = if raiz.trim_end_matches('/') == "src" {
+        arbol::rutas(&dir.join("src"), &[])?
+    } else {
+        arbol::rutas(dir, IGNORADOS)?
+    };
+    let sobran: Vec<&String> = dibujadas.iter().filter(|r| !reales.contains(r)).collect();
+    let faltan: Vec<&String> = reales.iter().filter(|r| !dibujadas.contains(r)).collect();
+    if sobran.is_empty() && faltan.is_empty() {
+        return Ok(None);
+    }
+    let lista = |v: &[&String]| v.iter().map(|r| r.as_str()).collect::<Vec<_>>().join(", ");
+    let mut mensaje = format!(
+        "{}: el árbol no coincide con {}",
+        dibujo.ubicacion, dibujo.origen
+    );
+    if !sobran.is_empty() {
+        mensaje.push_str(&format!("\n  no existen: {}", lista(&sobran)));
+    }
+    if !faltan.is_empty() {
+        mensaje.push_str(&format!("\n  faltan: {}", lista(&faltan)));
+    }
+    Ok(Some(mensaje))
+}
+
+/// Genera los árboles de `build/generado/arboles/` y comprueba los
+/// dibujados a mano.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let fragmentos = libro.fragmentos()?;
+    let mut ensamblados = Vec::new();
+    for (nombre, lista) in proyectos(&fragmentos)? {
+        let proyecto = ensamblar(&nombre, &lista)?;
+        let dibujo = arbol::dibujar(&proyecto.ruta().join("src"), "src", usize::MAX)?;
+        let contenido = format!(
+            "{}#raw({}, block: true, lang: \"yaml\")\n",
+            generado::CABECERA,
+            typst::cadena(dibujo.trim_end())
+        );
+        generado::escribir(libro, format!("arboles/{nombre}.typ"), &contenido)?;
+        ensamblados.push((nombre, proyecto));
+    }
+
+    let mut fallos = Vec::new();
+    for dibujo in dibujos(libro)? {
+        let diferencia = match dibujo.origen.split_once('/') {
+            Some(("proyecto", nombre)) => {
+                let Some((_, proyecto)) = ensamblados.iter().find(|(n, _)| n == nombre) else {
+                    return Err(Error::Libro(format!(
+                        "{}: no hay fragmentos con `proyecto={nombre}`",
+                        dibujo.ubicacion
+                    )));
+                };
+                comparar(&dibujo, proyecto.ruta())?
+            }
+            Some(("cargo-new", nombre)) => {
+                let sesion = Sesion::nueva()?;
+                let t = sesion.cargo(".", &["new", nombre])?;
+                if !t.exito {
+                    return Err(Error::Ejecucion(t.salida));
+                }
+                comparar(&dibujo, &sesion.ruta().join(nombre))?
+            }
+            _ => {
+                return Err(Error::Libro(format!(
+                    "{}: origen desconocido `{}` (usa `proyecto/<nombre>` o `cargo-new/<nombre>`)",
+                    dibujo.ubicacion, dibujo.origen
+                )));
+            }
+        };
+        fallos.extend(diferencia);
+    }
+    if !fallos.is_empty() {
+        return Err(Error::Libro(fallos.join("\n")));
+    }
+    Ok(ensamblados.len())
+}
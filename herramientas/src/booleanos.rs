@@ -0,0 +1,300 @@
+//! Tablas de verdad, precedencia y cortocircuito de `bool`.
+//!
+//! `sondas/booleanos.rs` evalúa cada combinación de operandos y una
+//! expresión de prueba por cada par de niveles de precedencia vecinos: la
+//! expresión debe valer lo mismo que su agrupación y distinto que la
+//! agrupación contraria (o esta no debe compilar). Así el orden que muestra
+//! el libro está demostrado, no copiado.
+
+use crate::cache::Compilaciones;
+use crate::matriz::{self, Matriz};
+use crate::rustc::EDICION;
+use crate::{Error, Libro, Resultado, generado, typst};
+
+const SONDA: &str = include_str!("../sondas/booleanos.rs");
+
+/// Una expresión que demuestra que `alto` se aplica antes que `bajo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prueba {
+    pub alto: String,
+    pub bajo: String,
+    pub expresion: String,
+    pub valor: String,
+    pub agrupada: String,
+    pub valor_agrupada: String,
+    pub alternativa: String,
+    /// `None` si la alternativa no compila.
+    pub valor_alternativa: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Medicion {
+    pub matrices: Vec<Matriz>,
+    /// Operadores de cada nivel, de mayor a menor precedencia.
+    pub niveles: Vec<String>,
+    pub pruebas: Vec<Prueba>,
+    pub rustc: String,
+}
+
+/// Compila y ejecuta la sonda de booleanos.
+pub fn medir(compilaciones: &mut Compilaciones) -> Resultado<Medicion> {
+    let salida = compilaciones.ejecutar(SONDA, &[])?;
+    let mut medicion = Medicion {
+        matrices: matriz::parsear(&salida, "booleanos")?,
+        rustc: compilaciones.version().to_string(),
+        ..Medicion::default()
+    };
+    for linea in salida.lines() {
+        match linea.split('\t').collect::<Vec<_>>()[..] {
+            ["nivel", operadores] => medicion.niveles.push(operadores.to_string()),
+            [
+                "precedencia",
+                alto,
+                bajo,
+                expresion,
+                valor,
+                agrupada,
+                valor_agrupada,
+                alternativa,
+                valor_alternativa,
+            ] => medicion.pruebas.push(Prueba {
+                alto: alto.to_string(),
+                bajo: bajo.to_string(),
+                expresion: expresion.to_string(),
+                valor: valor.to_string(),
+                agrupada: agrupada.to_string(),
+                valor_agrupada: valor_agrupada.to_string(),
+                alternativa: alternativa.to_string(),
+                valor_alternativa: (valor_alternativa != "-")
+                    .then(|| valor_alternativa.to_string()),
+            }),
+            ["celda", ..] => {}
+            _ => return Err(Error::Libro(format!("sonda de booleanos: `{linea}`"))),
+        }
+    }
+    Ok(medicion)
+}
+
+/// Índice del nivel que contiene el operador `op`.
+fn nivel(niveles: &[String], op: &str) -> Option<usize> {
+    niveles
+        .iter()
+        .position(|n| n.split_whitespace().any(|o| o == op))
+}
+
+/// Comprueba que cada prueba demuestra lo que dice y que cada par de
+/// niveles vecinos tiene la suya.
+pub fn verificar(medicion: &Medicion, compilaciones: &mut Compilaciones) -> Resultado<()> {
+    let mut fallos = Vec::new();
+    for p in &medicion.pruebas {
+        let (Some(alto), Some(bajo)) = (
+            nivel(&medicion.niveles, &p.alto),
+            nivel(&medicion.niveles, &p.bajo),
+        ) else {
+            fallos.push(format!("`{}`: operador sin nivel", p.expresion));
+            continue;
+        };
+        if alto >= bajo {
+            fallos.push(format!(
+                "`{}`: la tabla pone `{}` por debajo de `{}`",
+                p.expresion, p.alto, p.bajo
+            ));
+        }
+        if p.valor != p.valor_agrupada {
+            fallos.push(format!(
+                "`{}` vale {}, pero `{}` vale {}",
+                p.expresion, p.valor, p.agrupada, p.valor_agrupada
+            ));
+        }
+        match &p.valor_alternativa {
+            Some(v) if *v == p.valor => fallos.push(format!(
+                "`{}` y `{}` valen lo mismo ({v}): la prueba no demuestra nada",
+                p.expresion, p.alternativa
+            )),
+            Some(_) => {}
+            None => {
+                let fuente = format!("fn main() {{\n    let _ = {};\n}}\n", p.alternativa);
+                match compilaciones.correr(&fuente, EDICION, &[])? {
+                    Err(_) => {}
+                    Ok(_) => fallos.push(format!(
+                        "`{}` compila, pero la sonda afirma que no",
+                        p.alternativa
+                    )),
+                }
+            }
+        }
+    }
+    for (i, par) in medicion.niveles.windows(2).enumerate() {
+        let demostrado = medicion.pruebas.iter().any(|p| {
+            nivel(&medicion.niveles, &p.alto) == Some(i)
+                && nivel(&medicion.niveles, &p.bajo) == Some(i + 1)
+        });
+        if !demostrado {
+            fallos.push(format!(
+                "ninguna prueba demuestra que `{}` va antes que `{}`",
+                par[0], par[1]
+            ));
+        }
+    }
+    if !fallos.is_empty() {
+        return Err(Error::Libro(format!(
+            "precedencia de operadores booleanos:\n{}",
+            fallos.join("\n")
+        )));
+    }
+    Ok(())
+}
+
+fn medido_con(medicion: &Medicion) -> String {
+    format!("#{}", typst::codigo(&medicion.rustc))
+}
+
+fn celda_codigo(texto: &str) -> String {
+    format!("[#raw({}, lang: \"rust\")]", typst::cadena(texto))
+}
+
+/// Tabla de verdad: sus columnas ya incluyen los operandos.
+fn tabla_verdad(matriz: &Matriz, con_fila: bool) -> String {
+    let mut encabezados = Vec::new();
+    if con_fila {
+        encabezados.push(String::from("[*Expresión*]"));
+    }
+    encabezados.extend(matriz.columnas.iter().map(|c| format!("[*{c}*]")));
+    let filas: Vec<Vec<String>> = matriz
+        .filas
+        .iter()
+        .map(|fila| {
+            let mut celdas = Vec::new();
+            if con_fila {
+                celdas.push(celda_codigo(fila));
+            }
+            celdas.extend(
+                matriz
+                    .columnas
+                    .iter()
+                    .map(|c| celda_codigo(matriz.valor(fila, c).unwrap_or(""))),
+            );
+            celdas
+        })
+        .collect();
+    typst::tabla(&encabezados, &filas).replacen("\n", "\n  align: center,\n", 1)
+}
+
+fn tabla_niveles(medicion: &Medicion) -> String {
+    let encabezados = ["[*Nivel*]", "[*Operadores*]"].map(String::from);
+    let filas: Vec<Vec<String>> = medicion
+        .niveles
+        .iter()
+        .enumerate()
+        .map(|(i, n)| {
+            let operadores: Vec<String> = n
+                .split_whitespace()
+                .map(|o| format!("#raw({})", typst::cadena(o)))
+                .collect();
+            vec![
+                typst::texto(&(i + 1).to_string()),
+                format!("[{}]", operadores.join(" ")),
+            ]
+        })
+        .collect();
+    let leyenda = format!(
+        "[Precedencia de los operadores que producen o combinan `bool`, de mayor a menor. \
+         Cada orden está demostrado en la @tabla-bool-pruebas ({})]",
+        medido_con(medicion)
+    );
+    typst::figura(
+        &typst::tabla(&encabezados, &filas),
+        &leyenda,
+        Some("tabla-bool-precedencia"),
+    )
+}
+
+fn tabla_pruebas(medicion: &Medicion) -> String {
+    let encabezados = [
+        "[*Orden*]",
+        "[*Expresión*]",
+        "[*Se evalúa como*]",
+        "[*Si fuera al revés*]",
+    ]
+    .map(String::from);
+    let filas: Vec<Vec<String>> = medicion
+        .pruebas
+        .iter()
+        .map(|p| {
+            let alternativa = match &p.valor_alternativa {
+                Some(v) => format!("{} → {v}", p.alternativa),
+                None => format!("{} → no compila", p.alternativa),
+            };
+            vec![
+                format!(
+                    "[#raw({}) antes que #raw({})]",
+                    typst::cadena(&p.alto),
+                    typst::cadena(&p.bajo)
+                ),
+                celda_codigo(&format!("{} → {}", p.expresion, p.valor)),
+                celda_codigo(&p.agrupada),
+                celda_codigo(&alternativa),
+            ]
+        })
+        .collect();
+    let leyenda = format!(
+        "[Expresiones que demuestran cada orden de precedencia: el resultado coincide con \
+         una agrupación y no con la otra ({})]",
+        medido_con(medicion)
+    );
+    format!(
+        "#show table: set text(size: 8pt)\n\n{}",
+        typst::figura(
+            &typst::tabla(&encabezados, &filas),
+            &leyenda,
+            Some("tabla-bool-pruebas"),
+        )
+    )
+}
+
+/// Genera las tablas de `build/generado/booleanos/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "booleanos")?;
+    let medicion = medir(&mut compilaciones)?;
+    verificar(&medicion, &mut compilaciones)?;
+    compilaciones.escribir(libro)?;
+    let con = medido_con(&medicion);
+    let buscar = |nombre| matriz::buscar(&medicion.matrices, nombre, "booleanos");
+
+    let mut archivos = Vec::new();
+    for (nombre, operador, con_fila) in [
+        ("not", "NOT", true),
+        ("and", "AND", false),
+        ("or", "OR", false),
+        ("xor", "XOR", false),
+    ] {
+        archivos.push((
+            nombre,
+            typst::figura(
+                &tabla_verdad(buscar(nombre)?, con_fila),
+                &format!("[Tabla de verdad del operador {operador} ({con})]"),
+                Some(&format!("tabla-bool-{nombre}")),
+            ),
+        ));
+    }
+    archivos.push(("precedencia", tabla_niveles(&medicion)));
+    archivos.push(("pruebas", tabla_pruebas(&medicion)));
+    archivos.push((
+        "cortocircuito",
+        typst::figura(
+            &tabla_verdad(buscar("cortocircuito")?, true),
+            &format!(
+                "[Cuántas veces se evalúa el operando derecho. `&&` y `||` lo omiten \
+                 cuando el izquierdo ya decide el resultado; `&` y `|` siempre lo evalúan \
+                 ({con})]"
+            ),
+            Some("tabla-bool-cortocircuito"),
+        ),
+    ));
+
+    for (nombre, contenido) in &archivos {
+        let contenido = format!("{}{contenido}", generado::CABECERA);
+        generado::escribir(libro, format!("booleanos/{nombre}.typ"), &contenido)?;
+    }
+    Ok(archivos.len())
+}
@@ -0,0 +1,270 @@
+//! Caché de resultados entre una ejecución de `libro generar` y la
+//! siguiente.
+//!
+//! Compilar y ejecutar cientos de fragmentos lleva tiempo, y casi siempre da
+//! lo mismo que la vez anterior. Cada resultado se guarda en
+//! `build/cache/<nombre>.json` bajo una [`huella`] de todo lo que lo
+//! determina: el código, la edición, la versión de `rustc`... Si algo de eso
+//! cambia, cambia la huella y el resultado se vuelve a calcular. Borrar
+//! `build/cache/` obliga a calcularlo todo.
+//!
+//! Los pasos que compilan programas de uno en uno (las sondas de las tablas,
+//! los diagramas de memoria, las afirmaciones, los diagnósticos...) pasan
+//! por [`Compilaciones`], que guarda lo que dio `rustc` y lo que imprimió el
+//! programa bajo la huella del código, las opciones y la versión de
+//! `rustc`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::json::{self, Valor};
+use crate::rustc::{self, EDICION, Ejecucion};
+use crate::{Error, Libro, Resultado, generado};
+
+/// Directorio (relativo a la raíz) de las cachés.
+pub const DIRECTORIO: &str = "build/cache";
+
+/// Huella (FNV-1a de 64 bits, en hexadecimal) de `partes`.
+pub fn huella(partes: &[&str]) -> String {
+    let mut huella: u64 = 0xcbf2_9ce4_8422_2325;
+    for parte in partes {
+        // El separador distingue `["ab", "c"]` de `["a", "bc"]`.
+        for byte in parte.bytes().chain([0]) {
+            huella ^= u64::from(byte);
+            huella = huella.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{huella:016x}")
+}
+
+/// Una caché: los resultados de la vez anterior y los de esta.
+#[derive(Debug)]
+pub struct Cache {
+    archivo: PathBuf,
+    anteriores: BTreeMap<String, Valor>,
+    actuales: BTreeMap<String, Valor>,
+}
+
+impl Cache {
+    /// Lee `build/cache/<nombre>.json`. Si no existe o no se entiende, la
+    /// caché empieza vacía.
+    pub fn leer(libro: &Libro, nombre: &str) -> Cache {
+        let archivo = PathBuf::from(format!("{nombre}.json"));
+        let anteriores = fs::read_to_string(libro.raiz.join(DIRECTORIO).join(&archivo))
+            .ok()
+            .and_then(|texto| json::parsear(&texto).ok())
+            .map(|valor| valor.pares().iter().cloned().collect())
+            .unwrap_or_default();
+        Cache {
+            archivo,
+            anteriores,
+            actuales: BTreeMap::new(),
+        }
+    }
+
+    /// El resultado guardado con `huella`, si lo hay.
+    pub fn obtener(&mut self, huella: &str) -> Option<&Valor> {
+        let valor = self.anteriores.remove(huella)?;
+        Some(self.actuales.entry(huella.to_string()).or_insert(valor))
+    }
+
+    pub fn guardar(&mut self, huella: String, valor: Valor) {
+        self.actuales.insert(huella, valor);
+    }
+
+    /// Escribe los resultados usados o guardados en esta ejecución; los que
+    /// ya no se usan se descartan.
+    pub fn escribir(&self, libro: &Libro) -> Resultado<PathBuf> {
+        let lineas: Vec<String> = self
+            .actuales
+            .iter()
+            .map(|(huella, valor)| format!("  {}: {}", json::cadena(huella), valor.serializar()))
+            .collect();
+        generado::escribir_en(
+            libro,
+            DIRECTORIO,
+            &self.archivo,
+            &format!("{{\n{}\n}}\n", lineas.join(",\n")),
+        )
+    }
+}
+
+/// Compilaciones y ejecuciones con `rustc` guardadas en una [`Cache`].
+#[derive(Debug)]
+pub struct Compilaciones {
+    cache: Cache,
+    version: String,
+}
+
+impl Compilaciones {
+    /// Lee `build/cache/<nombre>.json` y la versión de `rustc`.
+    pub fn leer(libro: &Libro, nombre: &str) -> Resultado<Compilaciones> {
+        Ok(Compilaciones {
+            cache: Cache::leer(libro, nombre),
+            version: rustc::version()?,
+        })
+    }
+
+    /// Versión de `rustc` con la que se compila.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Compila `fuente` en `edicion` con `opciones` y, si compila, la
+    /// ejecuta como [`rustc::Programa::correr`]. Devuelve la ejecución o
+    /// la salida de `rustc` si no compila.
+    pub fn correr(
+        &mut self,
+        fuente: &str,
+        edicion: &str,
+        opciones: &[&str],
+    ) -> Resultado<Result<Ejecucion, String>> {
+        let huella = huella(&[
+            &self.version,
+            "correr",
+            edicion,
+            &opciones.join(" "),
+            fuente,
+        ]);
+        if let Some(guardada) = self.cache.obtener(&huella).and_then(ejecucion) {
+            return Ok(guardada);
+        }
+        let resultado = match rustc::compilar_en(fuente, edicion, opciones) {
+            Ok(programa) => Ok(programa.correr()?),
+            Err(Error::Compilacion(salida)) => Err(salida),
+            Err(e) => return Err(e),
+        };
+        let texto =
+            |clave: &str, texto: &str| (clave.to_string(), Valor::Cadena(texto.to_string()));
+        let valor = match &resultado {
+            Ok(e) => Valor::Objeto(vec![
+                texto("stdout", &e.stdout),
+                texto("stderr", &e.stderr),
+                (String::from("exito"), Valor::Bool(e.exito)),
+            ]),
+            Err(salida) => Valor::Objeto(vec![texto("rustc", salida)]),
+        };
+        self.cache.guardar(huella, valor);
+        Ok(resultado)
+    }
+
+    /// Como [`rustc::ejecutar`]: compila `fuente` con la edición del libro
+    /// y `opciones`, y devuelve su stdout; falla si no compila o termina con
+    /// error.
+    pub fn ejecutar(&mut self, fuente: &str, opciones: &[&str]) -> Resultado<String> {
+        match self.correr(fuente, EDICION, opciones)? {
+            Ok(e) if e.exito => Ok(e.stdout),
+            Ok(e) => Err(Error::Ejecucion(format!("{}{}", e.stderr, e.stdout))),
+            Err(salida) => Err(Error::Compilacion(salida)),
+        }
+    }
+
+    /// Como [`rustc::diagnosticos`]. Solo se guarda si no compila, que es
+    /// lo esperado.
+    pub fn diagnosticos(&mut self, fuente: &str, opciones: &[&str]) -> Resultado<String> {
+        let huella = huella(&[&self.version, "diagnosticos", &opciones.join(" "), fuente]);
+        if let Some(guardado) = self.cache.obtener(&huella).and_then(Valor::cadena) {
+            return Ok(guardado.to_string());
+        }
+        let salida = rustc::diagnosticos(fuente, opciones)?;
+        self.cache.guardar(huella, Valor::Cadena(salida.clone()));
+        Ok(salida)
+    }
+
+    pub fn escribir(&self, libro: &Libro) -> Resultado<PathBuf> {
+        self.cache.escribir(libro)
+    }
+}
+
+/// Lee lo que guardó [`Compilaciones::correr`].
+fn ejecucion(valor: &Valor) -> Option<Result<Ejecucion, String>> {
+    let texto = |clave: &str| valor.campo(clave).and_then(Valor::cadena).map(String::from);
+    if let Some(salida) = texto("rustc") {
+        return Some(Err(salida));
+    }
+    Some(Ok(Ejecucion {
+        stdout: texto("stdout")?,
+        stderr: texto("stderr")?,
+        exito: matches!(valor.campo("exito")?, Valor::Bool(true)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rustc::DirTemporal;
+
+    #[test]
+    fn huella_fnv() {
+        assert_eq!(huella(&[]), "cbf29ce484222325");
+        assert_eq!(huella(&[""]), "af63bd4c8601b7df");
+        assert_eq!(
+            huella(&["fn main() {}", "2024"]),
+            huella(&["fn main() {}", "2024"])
+        );
+    }
+
+    #[test]
+    fn huella_separa_las_partes() {
+        assert_ne!(huella(&["ab", "c"]), huella(&["a", "bc"]));
+        assert_ne!(huella(&["2021"]), huella(&["2024"]));
+    }
+
+    #[test]
+    fn solo_quedan_los_resultados_usados() {
+        let dir = DirTemporal::nuevo("cache").unwrap();
+        let libro = Libro {
+            raiz: dir.ruta().to_path_buf(),
+            bless: false,
+        };
+        let mut cache = Cache::leer(&libro, "prueba");
+        assert_eq!(cache.obtener("a"), None);
+        cache.guardar(String::from("a"), Valor::Numero(1.0));
+        cache.guardar(String::from("b"), Valor::Numero(2.0));
+        cache.escribir(&libro).unwrap();
+
+        let mut cache = Cache::leer(&libro, "prueba");
+        assert_eq!(cache.obtener("b"), Some(&Valor::Numero(2.0)));
+        cache.escribir(&libro).unwrap();
+
+        let mut cache = Cache::leer(&libro, "prueba");
+        assert_eq!(cache.obtener("a"), None);
+        assert_eq!(cache.obtener("b"), Some(&Valor::Numero(2.0)));
+    }
+
+    #[test]
+    fn compilaciones_guardadas_no_se_repiten() {
+        let dir = DirTemporal::nuevo("cache").unwrap();
+        let libro = Libro {
+            raiz: dir.ruta().to_path_buf(),
+            bless: false,
+        };
+        let mut compilaciones = Compilaciones::leer(&libro, "prueba").unwrap();
+        let fuente = "fn main() { println!(\"hola\"); }";
+        assert_eq!(compilaciones.ejecutar(fuente, &[]).unwrap(), "hola\n");
+        let error = compilaciones
+            .correr("fn main() { x }", EDICION, &[])
+            .unwrap();
+        assert!(error.unwrap_err().contains("E0425"));
+        compilaciones.escribir(&libro).unwrap();
+
+        // Lo guardado manda: si se compilara otra vez, imprimiría `hola`.
+        let mut compilaciones = Compilaciones::leer(&libro, "prueba").unwrap();
+        let clave = huella(&[compilaciones.version(), "correr", EDICION, "", fuente]);
+        let guardado = compilaciones.cache.anteriores.get_mut(&clave).unwrap();
+        *guardado = Valor::Objeto(vec![
+            (
+                String::from("stdout"),
+                Valor::Cadena(String::from("guardado\n")),
+            ),
+            (String::from("stderr"), Valor::Cadena(String::new())),
+            (String::from("exito"), Valor::Bool(true)),
+        ]);
+        assert_eq!(compilaciones.ejecutar(fuente, &[]).unwrap(), "guardado\n");
+        let error = compilaciones
+            .correr("fn main() { x }", EDICION, &[])
+            .unwrap();
+        assert!(error.unwrap_err().contains("E0425"));
+    }
+}
@@ -0,0 +1,317 @@
+//! Tablas de crecimiento de capacidad de `Vec` y `String`.
+//!
+//! La política de crecimiento es un detalle de la biblioteca estándar y
+//! puede cambiar entre versiones de Rust, así que se mide con el toolchain
+//! local (`sondas/capacidad.rs`) y cada tabla indica con qué `rustc` se
+//! obtuvo.
+
+use crate::cache::Compilaciones;
+use crate::{Error, Libro, Resultado, generado, typst};
+
+const SONDA: &str = include_str!("../sondas/capacidad.rs");
+
+/// Una realocación observada al hacer `push`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Realocacion {
+    pub tipo: String,
+    /// Longitud tras el `push` que provocó la realocación.
+    pub len: usize,
+    pub cap_antes: usize,
+    pub cap_despues: usize,
+    /// Si el buffer cambió de dirección (no pudo crecer en su lugar).
+    pub movido: bool,
+}
+
+/// `len` y `cap` después de una operación de capacidad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operacion {
+    pub tipo: String,
+    pub codigo: String,
+    pub len: usize,
+    pub cap: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Medicion {
+    pub realocaciones: Vec<Realocacion>,
+    pub operaciones: Vec<Operacion>,
+    pub rustc: String,
+}
+
+impl Medicion {
+    /// Tipos medidos cuyo nombre empieza con `prefijo`, en orden.
+    fn tipos(&self, prefijo: &str) -> Vec<&str> {
+        let mut tipos: Vec<&str> = Vec::new();
+        for r in &self.realocaciones {
+            if r.tipo.starts_with(prefijo) && !tipos.contains(&r.tipo.as_str()) {
+                tipos.push(&r.tipo);
+            }
+        }
+        tipos
+    }
+
+    /// Capacidad de `tipo` después de `len` empujes.
+    fn capacidad(&self, tipo: &str, len: usize) -> usize {
+        self.realocaciones
+            .iter()
+            .filter(|r| r.tipo == tipo && r.len <= len)
+            .map(|r| r.cap_despues)
+            .next_back()
+            .unwrap_or(0)
+    }
+
+    fn realocacion(&self, tipo: &str, len: usize) -> Option<&Realocacion> {
+        self.realocaciones
+            .iter()
+            .find(|r| r.tipo == tipo && r.len == len)
+    }
+}
+
+/// Compila y ejecuta la sonda de capacidad.
+pub fn medir(compilaciones: &mut Compilaciones) -> Resultado<Medicion> {
+    let mut medicion = Medicion {
+        rustc: compilaciones.version().to_string(),
+        ..Medicion::default()
+    };
+    for linea in compilaciones.ejecutar(SONDA, &[])?.lines() {
+        let campos: Vec<&str> = linea.split('\t').collect();
+        let numero = |i: usize| -> Resultado<usize> {
+            campos[i]
+                .parse()
+                .map_err(|_| Error::Libro(format!("sonda de capacidad: `{linea}`")))
+        };
+        match campos[..] {
+            ["crecimiento", tipo, _, _, _, movido] => medicion.realocaciones.push(Realocacion {
+                tipo: tipo.to_string(),
+                len: numero(2)?,
+                cap_antes: numero(3)?,
+                cap_despues: numero(4)?,
+                movido: movido == "true",
+            }),
+            ["metodo", tipo, codigo, _, _] => medicion.operaciones.push(Operacion {
+                tipo: tipo.to_string(),
+                codigo: codigo.to_string(),
+                len: numero(3)?,
+                cap: numero(4)?,
+            }),
+            _ => return Err(Error::Libro(format!("sonda de capacidad: `{linea}`"))),
+        }
+    }
+    Ok(medicion)
+}
+
+/// Tabla `len` → capacidad para cada tipo del grupo. Una celda en negrita
+/// marca el `push` que realocó; `*` indica que el buffer se movió.
+pub fn tabla_crecimiento(medicion: &Medicion, prefijo: &str) -> String {
+    let tipos = medicion.tipos(prefijo);
+    let mut lens: Vec<usize> = medicion
+        .realocaciones
+        .iter()
+        .filter(|r| tipos.contains(&r.tipo.as_str()))
+        .map(|r| r.len)
+        .collect();
+    lens.sort_unstable();
+    lens.dedup();
+
+    let mut encabezados = vec![String::from("[`len`]")];
+    encabezados.extend(tipos.iter().map(|t| typst::codigo(t)));
+
+    let filas: Vec<Vec<String>> = lens
+        .iter()
+        .map(|&len| {
+            let mut fila = vec![typst::texto(&len.to_string())];
+            for tipo in &tipos {
+                let cap = medicion.capacidad(tipo, len).to_string();
+                fila.push(match medicion.realocacion(tipo, len) {
+                    Some(r) if r.movido => format!("[*{cap}*\\*]"),
+                    Some(_) => format!("[*{cap}*]"),
+                    None => typst::texto(&cap),
+                });
+            }
+            fila
+        })
+        .collect();
+
+    let leyenda = format!(
+        "[Capacidad después de `len` llamadas a `push`. En negrita, los `push` que \
+         realocan; `*`: el buffer cambió de dirección. Medido con {}.]",
+        medido_con(medicion)
+    );
+    typst::figura(&typst::tabla(&encabezados, &filas), &leyenda, None)
+}
+
+/// Tabla de `len` y `cap` tras cada operación de capacidad del grupo.
+pub fn tabla_operaciones(medicion: &Medicion, prefijo: &str) -> String {
+    let encabezados = [
+        String::from("[Operación]"),
+        String::from("[`len`]"),
+        String::from("[`cap`]"),
+    ];
+    let filas: Vec<Vec<String>> = medicion
+        .operaciones
+        .iter()
+        .filter(|o| o.tipo.starts_with(prefijo))
+        .map(|o| {
+            vec![
+                format!("[#raw({}, lang: \"rust\")]", typst::cadena(&o.codigo)),
+                typst::texto(&o.len.to_string()),
+                typst::texto(&o.cap.to_string()),
+            ]
+        })
+        .collect();
+    let leyenda = format!(
+        "[`len` y `capacity()` tras cada operación, medidos con {}.]",
+        medido_con(medicion)
+    );
+    typst::figura(&typst::tabla(&encabezados, &filas), &leyenda, None)
+}
+
+/// `#raw("rustc 1.xx ...")`, para las leyendas.
+fn medido_con(medicion: &Medicion) -> String {
+    format!("#{}", typst::codigo(&medicion.rustc))
+}
+
+const COLORES: &[&str] = &["rgb(\"#CE412B\")", "rgb(\"#4a90e2\")", "rgb(\"#27ae60\")"];
+
+/// Gráfico escalonado de capacidad frente a `len` con cetz.
+pub fn grafico(medicion: &Medicion, prefijo: &str) -> String {
+    let tipos = medicion.tipos(prefijo);
+    // Las escalas, solo con los tipos de este gráfico.
+    let realocaciones: Vec<&Realocacion> = medicion
+        .realocaciones
+        .iter()
+        .filter(|r| tipos.contains(&r.tipo.as_str()))
+        .collect();
+    let max_len = realocaciones
+        .iter()
+        .map(|r| r.len)
+        .max()
+        .unwrap_or(1)
+        .next_power_of_two();
+    let max_cap = realocaciones
+        .iter()
+        .map(|r| r.cap_despues)
+        .max()
+        .unwrap_or(1);
+    let (ancho, alto) = (10.0, 5.0);
+    let x = |len: usize| len as f64 / max_len as f64 * ancho;
+    // Escala logarítmica: cada duplicación sube lo mismo.
+    let escalones = f64::from(max_cap.ilog2() + 1);
+    let y = |cap: usize| match cap {
+        0 => 0.0,
+        cap => f64::from(cap.ilog2() + 1) / escalones * alto,
+    };
+
+    let mut dibujo = String::new();
+    dibujo.push_str(&format!(
+        "    line((0, 0), ({ancho}, 0), mark: (end: \">\"))\n    \
+         line((0, 0), (0, {}), mark: (end: \">\"))\n    \
+         content(({}, 0), [`len`], anchor: \"west\")\n    \
+         content((-0.2, {}), [`cap` (escala $log_2$)], anchor: \"south-west\")\n",
+        alto + 0.3,
+        ancho + 0.2,
+        alto + 0.4
+    ));
+    for i in 0..=4 {
+        let len = max_len * i / 4;
+        dibujo.push_str(&format!(
+            "    content(({:.3}, -0.1), text(size: 6pt)[{len}], anchor: \"north\")\n",
+            x(len)
+        ));
+    }
+
+    let mut caps: Vec<usize> = realocaciones.iter().map(|r| r.cap_despues).collect();
+    caps.sort_unstable();
+    caps.dedup();
+    for cap in caps {
+        dibujo.push_str(&format!(
+            "    line((0, {0:.3}), ({ancho}, {0:.3}), stroke: 0.3pt + luma(220))\n    \
+             content((-0.2, {0:.3}), text(size: 6pt)[{cap}], anchor: \"east\")\n",
+            y(cap)
+        ));
+    }
+
+    for (i, tipo) in tipos.iter().enumerate() {
+        let color = COLORES[i % COLORES.len()];
+        let mut puntos = Vec::new();
+        let mut cap = 0;
+        for r in medicion.realocaciones.iter().filter(|r| r.tipo == *tipo) {
+            puntos.push(format!("({:.3}, {:.3})", x(r.len), y(cap)));
+            puntos.push(format!("({:.3}, {:.3})", x(r.len), y(r.cap_despues)));
+            cap = r.cap_despues;
+        }
+        puntos.push(format!("({ancho}, {:.3})", y(cap)));
+        dibujo.push_str(&format!(
+            "    line({}, stroke: 1.2pt + {color})\n    \
+             content(({:.1}, {:.3}), text(size: 7pt, fill: {color}, raw({})), anchor: \"west\")\n",
+            puntos.join(", "),
+            ancho + 0.2,
+            alto - i as f64 * 0.4,
+            typst::cadena(tipo)
+        ));
+    }
+
+    let cuerpo = format!("cetz.canvas({{\n    import cetz.draw: *\n{dibujo}  }})");
+    let leyenda = format!(
+        "[Capacidad frente a `len` al hacer `push` (medido con {}).]",
+        medido_con(medicion)
+    );
+    format!(
+        "#import \"@preview/cetz:0.4.2\"\n\n{}",
+        typst::figura(&cuerpo, &leyenda, None)
+    )
+}
+
+/// Genera las tablas y gráficos de `build/generado/capacidad/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "capacidad")?;
+    let medicion = medir(&mut compilaciones)?;
+    compilaciones.escribir(libro)?;
+    let mut n = 0;
+    for (grupo, prefijo) in [("vec", "Vec"), ("string", "String")] {
+        let archivos = [
+            ("crecimiento", tabla_crecimiento(&medicion, prefijo)),
+            ("metodos", tabla_operaciones(&medicion, prefijo)),
+            ("grafico", grafico(&medicion, prefijo)),
+        ];
+        for (nombre, contenido) in archivos {
+            let contenido = format!("{}{contenido}", generado::CABECERA);
+            generado::escribir(libro, format!("capacidad/{grupo}-{nombre}.typ"), &contenido)?;
+            n += 1;
+        }
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn realocacion(tipo: &str, len: usize, cap_despues: usize) -> Realocacion {
+        Realocacion {
+            tipo: tipo.to_string(),
+            len,
+            cap_antes: cap_despues / 2,
+            cap_despues,
+            movido: false,
+        }
+    }
+
+    #[test]
+    fn grafico_escala_solo_con_los_tipos_del_prefijo() {
+        let medicion = Medicion {
+            realocaciones: vec![
+                realocacion("Vec<u8>", 1, 8),
+                realocacion("Vec<u8>", 9, 16),
+                realocacion("String", 1, 8),
+                realocacion("String", 1000, 1024),
+            ],
+            ..Medicion::default()
+        };
+        let grafico = grafico(&medicion, "Vec");
+        // El eje de `len` llega a 16, no a 1024 como `String`.
+        assert!(grafico.contains("text(size: 6pt)[16], anchor: \"north\""));
+        assert!(!grafico.contains("[1024]"));
+        assert!(!grafico.contains("String"));
+    }
+}
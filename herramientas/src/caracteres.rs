@@ -0,0 +1,128 @@
+//! Matriz de clasificación de caracteres Unicode.
+//!
+//! `sondas/caracteres.rs` aplica los métodos `is_*` de `char` a un conjunto
+//! de caracteres elegidos para sorprender: letras con tilde, dígitos de
+//! otras escrituras, un emoji y espacios que no se ven. Junto a cada uno se
+//! muestran su código y su codificación UTF-8.
+
+use crate::cache::Compilaciones;
+use crate::matriz::{self, Matriz};
+use crate::{Libro, Resultado, generado, typst};
+
+const SONDA: &str = include_str!("../sondas/caracteres.rs");
+
+#[derive(Debug, Default)]
+pub struct Medicion {
+    pub matrices: Vec<Matriz>,
+    pub rustc: String,
+}
+
+/// Compila y ejecuta la sonda de caracteres.
+pub fn medir(compilaciones: &mut Compilaciones) -> Resultado<Medicion> {
+    Ok(Medicion {
+        matrices: matriz::parsear(&compilaciones.ejecutar(SONDA, &[])?, "caracteres")?,
+        rustc: compilaciones.version().to_string(),
+    })
+}
+
+fn medido_con(medicion: &Medicion) -> String {
+    format!("#{}", typst::codigo(&medicion.rustc))
+}
+
+fn celda_codigo(texto: &str) -> String {
+    format!("[#raw({}, lang: \"rust\")]", typst::cadena(texto))
+}
+
+/// Encabezado de una columna: métodos como `.is_numeric()`, el resto tal cual.
+fn encabezado(columna: &str) -> String {
+    if columna.starts_with(|c: char| c.is_ascii_lowercase()) {
+        let llamada = if columna.contains('(') {
+            format!(".{columna}")
+        } else {
+            format!(".{columna}()")
+        };
+        celda_codigo(&llamada)
+    } else {
+        format!("[*{columna}*]")
+    }
+}
+
+/// Encabezado girado, para que la matriz de predicados quepa en la página.
+fn encabezado_vertical(columna: &str) -> String {
+    format!(
+        "[#rotate(-90deg, reflow: true, raw({}, lang: \"rust\"))]",
+        typst::cadena(&format!(".{columna}()"))
+    )
+}
+
+/// `true` se marca con ✓ y `false` queda vacío, para que se lea por columnas.
+fn celda_predicado(valor: &str) -> String {
+    match valor {
+        "true" => String::from("[✓]"),
+        "false" => String::from("[]"),
+        valor => celda_codigo(valor),
+    }
+}
+
+fn tabla(matriz: &Matriz, encabezado: fn(&str) -> String, celda: fn(&str) -> String) -> String {
+    let mut encabezados = vec![String::from("[*Carácter*]")];
+    encabezados.extend(matriz.columnas.iter().map(|c| encabezado(c)));
+    let filas: Vec<Vec<String>> = matriz
+        .filas
+        .iter()
+        .map(|fila| {
+            let mut celdas = vec![celda_codigo(fila)];
+            celdas.extend(
+                matriz
+                    .columnas
+                    .iter()
+                    .map(|c| celda(matriz.valor(fila, c).unwrap_or(""))),
+            );
+            celdas
+        })
+        .collect();
+    typst::tabla(&encabezados, &filas).replacen("\n", "\n  align: center + horizon,\n", 1)
+}
+
+/// Genera las tablas de `build/generado/caracteres/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "caracteres")?;
+    let medicion = medir(&mut compilaciones)?;
+    compilaciones.escribir(libro)?;
+    let con = medido_con(&medicion);
+
+    let unicode = typst::figura(
+        &tabla(
+            matriz::buscar(&medicion.matrices, "unicode", "caracteres")?,
+            encabezado,
+            celda_codigo,
+        ),
+        &format!(
+            "[Código, tamaño en UTF-8 y conversiones de cada carácter. Un `char` siempre \
+             ocupa 4 bytes; en un `String` ocupa `len_utf8()` ({con})]"
+        ),
+        Some("tabla-char-unicode"),
+    );
+    let clasificacion = typst::figura(
+        &tabla(
+            matriz::buscar(&medicion.matrices, "clasificacion", "caracteres")?,
+            encabezado_vertical,
+            celda_predicado,
+        ),
+        &format!("[Métodos de clasificación de `char`; ✓ indica `true` ({con})]"),
+        Some("tabla-char-clasificacion"),
+    );
+
+    let archivos = [
+        ("unicode", unicode),
+        (
+            "clasificacion",
+            format!("#show table: set text(size: 8pt)\n\n{clasificacion}"),
+        ),
+    ];
+    for (nombre, contenido) in &archivos {
+        let contenido = format!("{}{contenido}", generado::CABECERA);
+        generado::escribir(libro, format!("caracteres/{nombre}.typ"), &contenido)?;
+    }
+    Ok(archivos.len())
+}
@@ -0,0 +1,262 @@
+//! Proyectos y sesiones de `cargo` desechables, para capturar lo que ve el
+//! lector al escribir `cargo new`, `cargo build` o `cargo run` en su terminal.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::aislado::{self, Limites, Terminacion};
+use crate::rustc::{DirTemporal, EDICION};
+use crate::{Error, Resultado};
+
+/// Nombre del paquete de los proyectos desechables; es el que aparece en
+/// las rutas y en los backtraces (`mi_proyecto::main`).
+pub const PAQUETE: &str = "mi_proyecto";
+
+/// Un proyecto `cargo` con un único `src/main.rs`, que se borra al soltarse.
+#[derive(Debug)]
+pub struct Proyecto {
+    dir: DirTemporal,
+}
+
+/// Salida de una orden, con stdout y stderr intercalados como en la terminal.
+#[derive(Debug, Clone)]
+pub struct Transcripcion {
+    pub salida: String,
+    pub exito: bool,
+    /// Código de salida (`101` tras un pánico).
+    pub codigo: Option<i32>,
+}
+
+impl Proyecto {
+    /// Crea el proyecto con `main` como `src/main.rs`.
+    pub fn nuevo(main: &str) -> Resultado<Proyecto> {
+        Proyecto::con_archivos(&[("src/main.rs", main)])
+    }
+
+    /// Crea el proyecto con varios archivos `(ruta, contenido)`, p. ej.
+    /// `src/main.rs` y `src/utilidades.rs`.
+    pub fn con_archivos(archivos: &[(&str, &str)]) -> Resultado<Proyecto> {
+        let dir = DirTemporal::nuevo("cargo")?;
+        fs::write(
+            dir.ruta().join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{PAQUETE}\"\nversion = \"0.1.0\"\nedition = \"{EDICION}\"\n\n\
+                 [dependencies]\n"
+            ),
+        )?;
+        for (ruta, contenido) in archivos {
+            let destino = dir.ruta().join(ruta);
+            if let Some(padre) = destino.parent() {
+                fs::create_dir_all(padre)?;
+            }
+            fs::write(destino, contenido)?;
+        }
+        Ok(Proyecto { dir })
+    }
+
+    pub fn ruta(&self) -> &Path {
+        self.dir.ruta()
+    }
+
+    /// `cargo build` sin salida; falla con los errores si no compila.
+    pub fn compilar(&self) -> Resultado<()> {
+        let t = self.cargo(&["build", "--quiet"], &[])?;
+        if !t.exito {
+            return Err(Error::Compilacion(t.salida));
+        }
+        Ok(())
+    }
+
+    /// Ejecuta `cargo <args>` en el proyecto con las variables `entorno`.
+    /// Las rutas absolutas del proyecto se cambian por `.`, como las vería
+    /// el lector desde la raíz de su proyecto, y los tiempos se normalizan
+    /// como en [`Sesion::cargo`]. Con `run`, `cargo` solo compila y el
+    /// programa corre aislado ([`aislado::ejecutar`]).
+    pub fn cargo(&self, args: &[&str], entorno: &[(&str, &str)]) -> Resultado<Transcripcion> {
+        let mut t = correr(self.ruta(), args, entorno)?;
+        let ruta = self.ruta().display().to_string();
+        t.salida = normalizar_tiempos(
+            &t.salida
+                .replace(&format!("{ruta}/"), "./")
+                .replace(&ruta, "."),
+        );
+        Ok(t)
+    }
+}
+
+/// Un directorio de trabajo donde se ejecutan órdenes de `cargo` una tras
+/// otra, como en la terminal del lector (`cargo new`, `cd`, `cargo build`...).
+#[derive(Debug)]
+pub struct Sesion {
+    dir: DirTemporal,
+}
+
+impl Sesion {
+    pub fn nueva() -> Resultado<Sesion> {
+        Ok(Sesion {
+            dir: DirTemporal::nuevo("sesion")?,
+        })
+    }
+
+    pub fn ruta(&self) -> &Path {
+        self.dir.ruta()
+    }
+
+    /// Ejecuta `cargo <args>` en el subdirectorio `dir` de la sesión.
+    ///
+    /// La salida se normaliza para que no dependa de la máquina: la ruta de
+    /// la sesión pasa a ser `/ruta` y los tiempos (`in 0.43s`) pasan a
+    /// `in X.XXs`.
+    pub fn cargo(&self, dir: &str, args: &[&str]) -> Resultado<Transcripcion> {
+        let mut t = correr(&self.ruta().join(dir), args, &[])?;
+        t.salida = normalizar_tiempos(
+            &t.salida
+                .replace(&self.ruta().display().to_string(), "/ruta"),
+        );
+        Ok(t)
+    }
+}
+
+/// Cambia la duración de las líneas `Finished ... in 1.23s` (o `in 1m
+/// 02s`) por `X.XXs`.
+fn normalizar_tiempos(texto: &str) -> String {
+    texto
+        .lines()
+        .map(|linea| match linea.rsplit_once(" in ") {
+            Some((antes, tiempo))
+                if linea.trim_start().starts_with("Finished")
+                    && tiempo.ends_with('s')
+                    && tiempo[..tiempo.len() - 1]
+                        .bytes()
+                        .all(|b| b.is_ascii_digit() || b". m".contains(&b)) =>
+            {
+                format!("{antes} in X.XXs")
+            }
+            _ => linea.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + if texto.ends_with('\n') { "\n" } else { "" }
+}
+
+/// Ejecuta `cargo <args> --offline` en `dir`, con stdout y stderr en una
+/// misma tubería. Se quitan las variables de entorno que cambiarían la
+/// salida (backtraces, directorio `target` compartido, colores).
+///
+/// `cargo run` no ejecuta el programa: `cargo build` lo compila y el
+/// binario se ejecuta aislado ([`aislado::ejecutar`]), tras la línea
+/// `Running` que escribiría `cargo`. Su stderr va después de su stdout.
+fn correr(dir: &Path, args: &[&str], entorno: &[(&str, &str)]) -> Resultado<Transcripcion> {
+    let Some((&"run", resto)) = args.split_first() else {
+        return correr_cargo(dir, args, entorno);
+    };
+    let mut compilacion = correr_cargo(dir, &[&["build"], resto].concat(), entorno)?;
+    if !compilacion.exito {
+        return Ok(compilacion);
+    }
+    let perfil = if resto.contains(&"--release") {
+        "release"
+    } else {
+        "debug"
+    };
+    let binario = format!("target/{perfil}/{}", paquete(dir)?);
+    let mut orden = Command::new(dir.join(&binario));
+    orden.current_dir(dir).envs(entorno.iter().copied());
+    let informe = aislado::ejecutar(&mut orden, &Limites::default())?;
+    compilacion.salida.push_str(&format!(
+        "     Running `{binario}`\n{}{}",
+        informe.stdout, informe.stderr
+    ));
+    Ok(Transcripcion {
+        salida: compilacion.salida,
+        exito: informe.exito(),
+        codigo: match informe.terminacion {
+            Terminacion::Codigo(codigo) => Some(codigo),
+            Terminacion::Senal(_) | Terminacion::Agotado => None,
+        },
+    })
+}
+
+/// Nombre del paquete del `Cargo.toml` de `dir`.
+fn paquete(dir: &Path) -> Resultado<String> {
+    let manifiesto = fs::read_to_string(dir.join("Cargo.toml"))?;
+    manifiesto
+        .lines()
+        .find_map(|l| l.strip_prefix("name = \"")?.strip_suffix('"'))
+        .map(str::to_string)
+        .ok_or_else(|| Error::Libro(format!("{}: Cargo.toml sin `name`", dir.display())))
+}
+
+/// [`correr`] sin el caso de `cargo run`.
+fn correr_cargo(dir: &Path, args: &[&str], entorno: &[(&str, &str)]) -> Resultado<Transcripcion> {
+    let (mut lector, escritor) = std::io::pipe()?;
+    let mut orden = Command::new(cargo());
+    orden
+        .args(args)
+        .arg("--offline")
+        .current_dir(dir)
+        .env_remove("RUST_BACKTRACE")
+        .env_remove("RUST_LIB_BACKTRACE")
+        .env_remove("CARGO_TARGET_DIR")
+        .env_remove("CARGO_BUILD_TARGET")
+        .env("CARGO_TERM_COLOR", "never")
+        .env("CARGO_TERM_PROGRESS_WHEN", "never")
+        .stdin(Stdio::null())
+        .stdout(escritor.try_clone()?)
+        .stderr(escritor);
+    for (clave, valor) in entorno {
+        orden.env(clave, valor);
+    }
+    let mut hijo = orden.spawn()?;
+    // La orden guarda una copia del extremo de escritura; sin soltarla, la
+    // lectura no terminaría nunca.
+    drop(orden);
+    let mut salida = String::new();
+    lector.read_to_string(&mut salida)?;
+    let estado = hijo.wait()?;
+    Ok(Transcripcion {
+        salida,
+        exito: estado.success(),
+        codigo: estado.code(),
+    })
+}
+
+/// `$CARGO` (definida cuando las herramientas corren con `cargo run`) o
+/// `cargo` del `PATH`.
+pub fn cargo() -> PathBuf {
+    std::env::var_os("CARGO")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("cargo"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizar_tiempos_de_finished() {
+        let salida = "   Compiling mi_proyecto v0.1.0 (/ruta)\n    Finished `dev` profile \
+                      [unoptimized + debuginfo] target(s) in 0.43s\n";
+        assert_eq!(
+            normalizar_tiempos(salida),
+            "   Compiling mi_proyecto v0.1.0 (/ruta)\n    Finished `dev` profile \
+             [unoptimized + debuginfo] target(s) in X.XXs\n"
+        );
+        assert_eq!(
+            normalizar_tiempos("    Finished `release` profile [optimized] target(s) in 1m 02s"),
+            "    Finished `release` profile [optimized] target(s) in X.XXs"
+        );
+        assert_eq!(
+            normalizar_tiempos("    Finished `test` profile target(s) in 12.5s"),
+            "    Finished `test` profile target(s) in X.XXs"
+        );
+    }
+
+    #[test]
+    fn normalizar_tiempos_solo_en_finished() {
+        let salida = "Tardó in 3s\nen main in 2s";
+        assert_eq!(normalizar_tiempos(salida), salida);
+    }
+}
@@ -0,0 +1,375 @@
+//! Clasificación de los comentarios de los fragmentos.
+//!
+//! Los capítulos de colecciones dicen lo que imprime un ejemplo con un
+//! comentario al final de la línea (`println!("{:?}", v);  // [1, 2, 3]`),
+//! igual que explican el código (`v.reserve(10);  // reservar más
+//! capacidad`). En el PDF los dos se ven iguales. Cada comentario se
+//! clasifica como:
+//!
+//! - salida: su texto aparece en lo que el programa imprimió de verdad (una
+//!   línea igual o que termina igual; se ignora una aclaración final entre
+//!   paréntesis, como en `// true (comparación lexicográfica)`);
+//! - error: habla de un error de compilación (`// error: ...`, `// E0382`);
+//! - pánico: habla de un `panic`;
+//! - explicación: todo lo demás.
+//!
+//! Sin salida verificada (el fragmento no compila solo) ningún comentario es
+//! de salida. [`crate::resultados`] guarda los que no son explicaciones, y la
+//! plantilla los resalta con `codly(highlights: ...)`.
+//!
+//! Un comentario `// Salida: ...` afirma lo que se imprime, y puede resumir
+//! varias líneas: dentro de un bucle, `println!("{}", i);  // Salida: 0 1 2
+//! 3 4` imprime cinco. El resumen se separa en piezas por espacios o comas
+//! y se compara con lo que imprime esa sentencia en todas sus iteraciones,
+//! separado igual: [`comprobar`] compila una copia del fragmento que, antes
+//! de cada sentencia con resumen, imprime una marca con su línea, y otra
+//! después que la cierra. La copia pasa por la caché
+//! ([`crate::cache::Compilaciones`]): solo se vuelve a compilar si cambia el
+//! fragmento o `rustc`. Si la copia no compila (el comentario no está tras
+//! una sentencia), basta con que el resumen aparezca seguido en la salida.
+//! También se comprueban los bloques ```yaml escritos a mano tras `Salida:`
+//! o `Resultado:`, que deben coincidir línea a línea.
+//!
+//! El texto del comentario y su aclaración entre paréntesis se separan con
+//! [`crate::fragmentos::comentario`] y [`crate::fragmentos::sin_aclaracion`],
+//! como en [`crate::afirmaciones`].
+
+use crate::Resultado;
+use crate::cache::Compilaciones;
+use crate::fragmentos::{Fragmento, comentario, sin_aclaracion, sin_comentario};
+
+/// Prefijo de los comentarios que afirman la salida.
+const RESUMEN: &str = "salida:";
+
+/// Qué dice un comentario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tipo {
+    Salida,
+    Error,
+    Panico,
+    Explicacion,
+}
+
+impl Tipo {
+    /// Nombre en `resultados.json`.
+    pub fn nombre(self) -> &'static str {
+        match self {
+            Tipo::Salida => "salida",
+            Tipo::Error => "error",
+            Tipo::Panico => "panico",
+            Tipo::Explicacion => "explicacion",
+        }
+    }
+}
+
+/// Un comentario `//` de un fragmento.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comentario {
+    /// Línea del fragmento (desde 1).
+    pub linea: usize,
+    /// Columna (desde 1, en caracteres) donde empieza `//`.
+    pub columna: usize,
+    pub texto: String,
+    pub tipo: Tipo,
+}
+
+/// Clasifica los comentarios de `codigo` con `salida`, lo que imprimió el
+/// programa (si se ejecutó). Los comentarios de línea completa solo se
+/// devuelven si no son explicaciones.
+pub fn clasificar(codigo: &str, salida: Option<&str>) -> Vec<Comentario> {
+    let mut comentarios = Vec::new();
+    for (n, linea) in codigo.lines().enumerate() {
+        let Some(texto) = comentario(linea) else {
+            continue;
+        };
+        let antes = sin_comentario(linea);
+        let texto = texto.to_string();
+        let tipo = tipo(&texto, salida);
+        if antes.trim().is_empty() && tipo == Tipo::Explicacion {
+            continue;
+        }
+        comentarios.push(Comentario {
+            linea: n + 1,
+            columna: antes.chars().count() + 1,
+            texto,
+            tipo,
+        });
+    }
+    comentarios
+}
+
+fn tipo(texto: &str, salida: Option<&str>) -> Tipo {
+    // Lo que el programa imprimió manda: `// 0 (wrapping sin pánico)`.
+    if let Some(salida) = salida
+        && es_salida(texto, salida)
+    {
+        return Tipo::Salida;
+    }
+    let minusculas = texto.to_lowercase();
+    let panico = ["panic", "pánico"]
+        .iter()
+        .any(|p| minusculas.contains(p) && !minusculas.contains(&format!("sin {p}")));
+    if panico {
+        return Tipo::Panico;
+    }
+    if minusculas.starts_with("error")
+        || minusculas.contains("no compila")
+        || es_codigo_error(texto)
+    {
+        return Tipo::Error;
+    }
+    Tipo::Explicacion
+}
+
+/// `E0382` o un texto que lo contiene como palabra.
+fn es_codigo_error(texto: &str) -> bool {
+    texto
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|p| p.len() == 5 && p.starts_with('E') && p[1..].chars().all(|c| c.is_ascii_digit()))
+}
+
+fn es_salida(texto: &str, salida: &str) -> bool {
+    if let Some(resumen) = resumen(texto) {
+        return resume(resumen, salida);
+    }
+    [texto, sin_aclaracion(texto)]
+        .iter()
+        .filter(|t| !t.is_empty())
+        .any(|t| salida.lines().any(|l| termina_en(l.trim_end(), t)))
+}
+
+/// El resumen de `Salida: 0 1 2`, sin el prefijo.
+fn resumen(texto: &str) -> Option<&str> {
+    let prefijo = texto.get(..RESUMEN.len())?;
+    prefijo
+        .eq_ignore_ascii_case(RESUMEN)
+        .then(|| texto[RESUMEN.len()..].trim())
+}
+
+/// El texto separado por espacios y comas.
+fn piezas(texto: &str) -> Vec<&str> {
+    texto
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Si las piezas de `resumen` aparecen seguidas en `salida`: `0 1 2` resume
+/// las líneas `0`, `1` y `2`, y `1: uno, 2: dos` las líneas `1: uno` y
+/// `2: dos`.
+fn resume(resumen: &str, salida: &str) -> bool {
+    let resumen = piezas(resumen);
+    !resumen.is_empty() && piezas(salida).windows(resumen.len()).any(|v| v == resumen)
+}
+
+/// Separa la marca de [`por_sentencia`] del número de línea.
+const MARCA: char = '\u{1e}';
+
+/// Cierra lo que imprimió una sentencia marcada.
+const FIN: char = '\u{1f}';
+
+/// Lo que imprime cada sentencia con un resumen `// Salida: ...`, por su
+/// línea en el fragmento (desde 1), una vez por cada vez que se ejecuta.
+/// `None` si la copia marcada no compila.
+fn por_sentencia(
+    fragmento: &Fragmento,
+    edicion: &str,
+    compilaciones: &mut Compilaciones,
+) -> Resultado<Option<Vec<(usize, String)>>> {
+    let mut codigo = String::new();
+    for (n, linea) in fragmento.codigo.lines().enumerate() {
+        let antes = sin_comentario(linea);
+        let texto = comentario(linea).unwrap_or_default();
+        let marcada = resumen(texto).is_some() && antes.trim_end().ends_with(';');
+        let sangria = &linea[..linea.len() - linea.trim_start().len()];
+        if marcada {
+            codigo.push_str(&format!("{sangria}print!(\"{MARCA}{}{MARCA}\");\n", n + 1));
+        }
+        codigo.push_str(linea);
+        codigo.push('\n');
+        if marcada {
+            codigo.push_str(&format!("{sangria}print!(\"{FIN}\");\n"));
+        }
+    }
+    let marcado = Fragmento {
+        codigo,
+        ..fragmento.clone()
+    };
+    Ok(compilaciones
+        .correr(&marcado.programa(), edicion, fragmento.opciones())?
+        .ok()
+        .map(|ejecucion| repartir(&ejecucion.stdout)))
+}
+
+/// Reparte la salida de la copia marcada entre las sentencias: cada
+/// `\u{1e}<línea>\u{1e}...\u{1f}` es lo que imprimió una vez la sentencia
+/// de esa línea. Un `print!` en un bucle deja varias en la misma línea de
+/// la salida. Si una sentencia no llega a cerrar (un `break;`), lo suyo
+/// acaba en la siguiente marca.
+fn repartir(salida: &str) -> Vec<(usize, String)> {
+    let mut impresiones = Vec::new();
+    let mut resto = salida;
+    while let Some(inicio) = resto.find(MARCA) {
+        let Some((linea, tras)) = resto[inicio + MARCA.len_utf8()..].split_once(MARCA) else {
+            break;
+        };
+        let fin = tras.find([FIN, MARCA]).unwrap_or(tras.len());
+        if let Ok(linea) = linea.parse() {
+            impresiones.push((linea, tras[..fin].to_string()));
+        }
+        resto = &tras[fin..];
+    }
+    impresiones
+}
+
+/// Compara con `salida`, lo que imprime de verdad el fragmento en
+/// `edicion`, sus comentarios `// Salida: ...` y su bloque de salida escrito
+/// a mano. Devuelve una línea por cada uno que no coincide.
+pub fn comprobar(
+    fragmento: &Fragmento,
+    edicion: &str,
+    salida: &str,
+    compilaciones: &mut Compilaciones,
+) -> Resultado<Vec<String>> {
+    let mut fallos = Vec::new();
+    let mut sentencias = None;
+    for (n, linea) in fragmento.codigo.lines().enumerate() {
+        let antes = sin_comentario(linea);
+        let texto = comentario(linea).unwrap_or_default();
+        let Some(resumen) = resumen(texto) else {
+            continue;
+        };
+        if sentencias.is_none() {
+            sentencias = Some(por_sentencia(fragmento, edicion, compilaciones)?);
+        }
+        let propias: Option<Vec<&str>> = sentencias
+            .as_ref()
+            .and_then(Option::as_ref)
+            .filter(|_| antes.trim_end().ends_with(';'))
+            .map(|lineas| {
+                lineas
+                    .iter()
+                    .filter(|(m, _)| *m == n + 1)
+                    .map(|(_, l)| l.as_str())
+                    .collect()
+            });
+        let (coincide, real) = match propias {
+            Some(propias) => {
+                let real = piezas(&propias.join("\n")).join(" ");
+                (piezas(resumen).join(" ") == real, real)
+            }
+            None => (resume(resumen, salida), piezas(salida).join(" ")),
+        };
+        if !coincide {
+            fallos.push(format!(
+                "  {}:{}: `// {texto}`, pero imprime: {real}",
+                fragmento.archivo.display(),
+                fragmento.linea + n + 1,
+            ));
+        }
+    }
+    if let Some((linea, escrita)) = &fragmento.salida_escrita {
+        let real: Vec<&str> = salida.trim_end().lines().map(str::trim).collect();
+        if escrita.trim_end().lines().collect::<Vec<_>>() != real {
+            fallos.push(format!(
+                "  {}:{linea}: la salida escrita no coincide con la real:\n    \
+                 escrita: {:?}\n    real:    {:?}",
+                fragmento.archivo.display(),
+                escrita.trim_end(),
+                real.join("\n")
+            ));
+        }
+    }
+    Ok(fallos)
+}
+
+/// Si `linea` termina en `texto` y este no empieza a mitad de una palabra
+/// (`10` está en `Primer elemento: 10`, pero no en `110`).
+fn termina_en(linea: &str, texto: &str) -> bool {
+    linea.strip_suffix(texto).is_some_and(|antes| {
+        !(antes.ends_with(char::is_alphanumeric) && texto.starts_with(char::is_alphanumeric))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::Libro;
+    use crate::fragmentos::extraer;
+    use crate::rustc::{DirTemporal, EDICION};
+
+    fn fragmento(codigo: &str) -> Fragmento {
+        let texto = format!("```rust\n{codigo}```\n");
+        extraer(Path::new("prueba.typ"), &texto).remove(0)
+    }
+
+    #[test]
+    fn repartir_separa_cada_impresion() {
+        let salida = "\u{1e}2\u{1e}0 \u{1f}\u{1e}2\u{1e}1 \u{1f}\u{1e}2\u{1e}2 \u{1f}\nfin\n";
+        let esperado = [(2, "0 "), (2, "1 "), (2, "2 ")];
+        assert_eq!(
+            repartir(salida),
+            esperado.map(|(n, t)| (n, t.to_string())).to_vec()
+        );
+    }
+
+    #[test]
+    fn repartir_sin_cierre_acaba_en_la_siguiente_marca() {
+        let salida = "\u{1e}3\u{1e}a\n\u{1e}5\u{1e}b\n\u{1f}";
+        assert_eq!(
+            repartir(salida),
+            vec![(3, String::from("a\n")), (5, String::from("b\n"))]
+        );
+    }
+
+    #[test]
+    fn resumen_de_un_print_en_un_bucle() {
+        let dir = DirTemporal::nuevo("comentarios").unwrap();
+        let libro = Libro {
+            raiz: dir.ruta().to_path_buf(),
+            bless: false,
+        };
+        let mut compilaciones = Compilaciones::leer(&libro, "comentarios").unwrap();
+        let f = fragmento(
+            "for i in 0..5 {\n    print!(\"{i} \");  // Salida: 0 1 2 3 4\n}\nprintln!();\n",
+        );
+        assert_eq!(
+            comprobar(&f, EDICION, "0 1 2 3 4 \n", &mut compilaciones).unwrap(),
+            Vec::<String>::new()
+        );
+
+        let f =
+            fragmento("for i in 0..5 {\n    print!(\"{i} \");  // Salida: 0 1 2\n}\nprintln!();\n");
+        let fallos = comprobar(&f, EDICION, "0 1 2 3 4 \n", &mut compilaciones).unwrap();
+        assert_eq!(fallos.len(), 1);
+        assert!(fallos[0].ends_with("pero imprime: 0 1 2 3 4"), "{fallos:?}");
+    }
+
+    #[test]
+    fn resume_piezas_seguidas() {
+        assert!(resume("0 1 2", "0\n1\n2\n"));
+        assert!(resume("1: uno, 2: dos", "1: uno\n2: dos\n3: tres\n"));
+        assert!(resume("2 3", "1 2 3 4"));
+        assert!(!resume("1 3", "1 2 3"));
+        assert!(!resume("3 2", "1 2 3"));
+        assert!(!resume("", "1 2 3"));
+    }
+
+    #[test]
+    fn termina_en_una_palabra_entera() {
+        assert!(termina_en("Primer elemento: 10", "10"));
+        assert!(termina_en("10", "10"));
+        assert!(termina_en("x = -10", "-10"));
+        assert!(!termina_en("110", "10"));
+        assert!(!termina_en("10 elementos", "10"));
+    }
+
+    #[test]
+    fn resumen_sin_el_prefijo() {
+        assert_eq!(resumen("Salida: 0 1 2"), Some("0 1 2"));
+        assert_eq!(resumen("salida:  hola "), Some("hola"));
+        assert_eq!(resumen("16"), None);
+    }
+}
@@ -0,0 +1,335 @@
+//! Diagnósticos reales de `rustc` para los fragmentos que no compilan.
+//!
+//! Un fragmento marcado con `no_compila` (o `no_compila=E0382` para exigir
+//! un código de error) se compila con `--error-format=json`. Si además tiene
+//! `id`, el texto ANSI que `rustc` incluye en cada diagnóstico se guarda como
+//! instantánea en `herramientas/diagnosticos/<id>.ansi` y se convierte en un
+//! bloque Typst con los colores de la terminal.
+//!
+//! `libro generar` falla si el diagnóstico actual no coincide con la
+//! instantánea; `libro generar --bless` la reescribe (p. ej. tras cambiar de
+//! toolchain).
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cache::Compilaciones;
+use crate::fragmentos::Fragmento;
+use crate::{Error, Libro, Resultado, generado, json, typst};
+
+/// Directorio (relativo a la raíz) de las instantáneas.
+pub const INSTANTANEAS: &str = "herramientas/diagnosticos";
+
+/// Un diagnóstico de `rustc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostico {
+    /// `error`, `warning`, `failure-note`...
+    pub nivel: String,
+    pub codigo: Option<String>,
+    pub mensaje: String,
+    /// Salida de terminal con secuencias ANSI.
+    pub renderizado: String,
+}
+
+/// Compila el fragmento y devuelve sus diagnósticos, en orden.
+pub fn capturar(
+    fragmento: &Fragmento,
+    compilaciones: &mut Compilaciones,
+) -> Resultado<Vec<Diagnostico>> {
+    let salida = compilaciones
+        .diagnosticos(&fragmento.programa(), &["-A", "unused"])
+        .map_err(|e| Error::Libro(format!("{}: {e}", fragmento.ubicacion())))?;
+    let mut diagnosticos = Vec::new();
+    for linea in salida.lines().filter(|l| l.starts_with('{')) {
+        let valor = json::parsear(linea)?;
+        if valor.campo("$message_type").and_then(json::Valor::cadena) != Some("diagnostic") {
+            continue;
+        }
+        let texto = |campo: &str| {
+            valor
+                .campo(campo)
+                .and_then(json::Valor::cadena)
+                .unwrap_or_default()
+                .to_string()
+        };
+        diagnosticos.push(Diagnostico {
+            nivel: texto("level"),
+            codigo: valor
+                .campo("code")
+                .and_then(|c| c.campo("code"))
+                .and_then(json::Valor::cadena)
+                .map(String::from),
+            mensaje: texto("message"),
+            renderizado: texto("rendered"),
+        });
+    }
+    Ok(diagnosticos)
+}
+
+/// Texto ANSI de todos los diagnósticos, con la ruta que mostraría cargo.
+pub fn texto(diagnosticos: &[Diagnostico]) -> String {
+    let texto: String = diagnosticos
+        .iter()
+        .map(|d| d.renderizado.as_str())
+        .collect();
+    texto.replace("main.rs:", "src/main.rs:")
+}
+
+/// Un tramo de texto con el mismo estilo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tramo {
+    pub texto: String,
+    /// Nombre del color en `src/utils/diagnostico.typ`.
+    pub color: Option<&'static str>,
+    pub negrita: bool,
+}
+
+/// Nombre del color de índice `indice` (0-7, o 8-15 en su versión brillante).
+fn color(indice: u8) -> Option<&'static str> {
+    match indice % 8 {
+        1 => Some("rojo"),
+        2 => Some("verde"),
+        3 => Some("amarillo"),
+        4 => Some("azul"),
+        5 => Some("magenta"),
+        6 => Some("cian"),
+        _ => None,
+    }
+}
+
+/// Separa el texto ANSI en líneas de tramos con estilo.
+pub fn tramos(ansi: &str) -> Vec<Vec<Tramo>> {
+    let mut lineas = Vec::new();
+    let (mut color_actual, mut negrita) = (None, false);
+    for linea in ansi.trim_end().lines() {
+        let mut tramos: Vec<Tramo> = Vec::new();
+        let mut resto = linea;
+        while !resto.is_empty() {
+            if let Some(secuencia) = resto.strip_prefix("\u{1b}[") {
+                let fin = secuencia.find('m').unwrap_or(secuencia.len());
+                // `\e[m` es `\e[0m`, y una secuencia puede juntar varios
+                // códigos: `\e[1;31m`.
+                let codigos: Vec<u8> = secuencia[..fin]
+                    .split(';')
+                    .map(|c| c.parse().unwrap_or(0))
+                    .collect();
+                let mut i = 0;
+                while i < codigos.len() {
+                    match codigos[i..] {
+                        [0, ..] => (color_actual, negrita) = (None, false),
+                        [1, ..] => negrita = true,
+                        [22, ..] => negrita = false,
+                        [38, 5, n, ..] => {
+                            if n < 16 {
+                                color_actual = color(n);
+                            }
+                            i += 2;
+                        }
+                        [n @ 30..=37, ..] => color_actual = color(n - 30),
+                        [n @ 90..=97, ..] => color_actual = color(n - 90),
+                        [39, ..] => color_actual = None,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                resto = &secuencia[(fin + 1).min(secuencia.len())..];
+                continue;
+            }
+            let fin = resto.find('\u{1b}').unwrap_or(resto.len());
+            let texto = &resto[..fin];
+            match tramos.last_mut() {
+                Some(t) if t.color == color_actual && t.negrita == negrita => {
+                    t.texto.push_str(texto)
+                }
+                _ => tramos.push(Tramo {
+                    texto: texto.to_string(),
+                    color: color_actual,
+                    negrita,
+                }),
+            }
+            resto = &resto[fin..];
+        }
+        lineas.push(tramos);
+    }
+    lineas
+}
+
+/// Bloque Typst `#diagnostico(...)` para el texto ANSI.
+pub fn bloque(ansi: &str) -> String {
+    let mut salida = String::from("#import \"/src/utils/diagnostico.typ\": diagnostico\n\n");
+    salida.push_str("#diagnostico((\n");
+    for linea in tramos(ansi) {
+        let tramos: Vec<String> = linea
+            .iter()
+            .map(|t| {
+                let color = t
+                    .color
+                    .map(typst::cadena)
+                    .unwrap_or_else(|| String::from("none"));
+                format!("({}, {color}, {})", typst::cadena(&t.texto), t.negrita)
+            })
+            .collect();
+        match tramos.len() {
+            0 => salida.push_str("  (),\n"),
+            1 => salida.push_str(&format!("  ({},),\n", tramos[0])),
+            _ => salida.push_str(&format!("  ({}),\n", tramos.join(", "))),
+        }
+    }
+    salida.push_str("))\n");
+    salida
+}
+
+fn instantanea(libro: &Libro, id: &str) -> PathBuf {
+    libro.raiz.join(INSTANTANEAS).join(format!("{id}.ansi"))
+}
+
+/// Genera `build/generado/diagnosticos/<id>.typ` para cada fragmento
+/// marcado con `no_compila`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "diagnosticos")?;
+    let mut ids = Vec::new();
+    let mut fallos = Vec::new();
+    for fragmento in libro.fragmentos()? {
+        if !fragmento.atributos.tiene("no_compila") {
+            continue;
+        }
+        let diagnosticos = capturar(&fragmento, &mut compilaciones)?;
+        if let Some(codigo) = fragmento.atributos.valor("no_compila")
+            && !diagnosticos
+                .iter()
+                .any(|d| d.codigo.as_deref() == Some(codigo))
+        {
+            let errores: Vec<&str> = diagnosticos
+                .iter()
+                .filter(|d| d.nivel == "error")
+                .map(|d| d.mensaje.as_str())
+                .collect();
+            fallos.push(format!(
+                "{}: se esperaba el error {codigo}, pero rustc dijo: {}",
+                fragmento.ubicacion(),
+                errores.join("; ")
+            ));
+            continue;
+        }
+        // Sin `id` (```` ```rust,compile_fail ````) solo se comprueba el código
+        // de error: el libro no muestra el diagnóstico.
+        let Some(id) = fragmento.id() else {
+            continue;
+        };
+
+        let actual = texto(&diagnosticos);
+        let ruta = instantanea(libro, id);
+        if libro.bless {
+            if let Some(padre) = ruta.parent() {
+                fs::create_dir_all(padre)?;
+            }
+            if fs::read_to_string(&ruta).ok().as_deref() != Some(actual.as_str()) {
+                fs::write(&ruta, &actual)?;
+            }
+        } else {
+            match fs::read_to_string(&ruta) {
+                Ok(guardado) if guardado == actual => {}
+                Ok(_) => {
+                    fallos.push(format!(
+                        "{}: el diagnóstico de `{id}` cambió respecto a {}",
+                        fragmento.ubicacion(),
+                        ruta.strip_prefix(&libro.raiz).unwrap_or(&ruta).display()
+                    ));
+                    continue;
+                }
+                Err(_) => {
+                    fallos.push(format!(
+                        "{}: falta la instantánea de `{id}`",
+                        fragmento.ubicacion()
+                    ));
+                    continue;
+                }
+            }
+        }
+
+        let contenido = format!("{}{}", generado::CABECERA, bloque(&actual));
+        generado::escribir(libro, format!("diagnosticos/{id}.typ"), &contenido)?;
+        ids.push(id.to_string());
+    }
+
+    if libro.bless {
+        // Las instantáneas de fragmentos que ya no existen sobran.
+        if let Ok(entradas) = fs::read_dir(libro.raiz.join(INSTANTANEAS)) {
+            for entrada in entradas {
+                let ruta = entrada?.path();
+                let id = ruta.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+                if !ids.iter().any(|i| i == id) {
+                    fs::remove_file(&ruta)?;
+                }
+            }
+        }
+    }
+    compilaciones.escribir(libro)?;
+    if !fallos.is_empty() {
+        return Err(Error::Libro(format!(
+            "{}\n(ejecuta `libro generar --bless diagnosticos` si el cambio es esperado)",
+            fallos.join("\n")
+        )));
+    }
+    Ok(ids.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tramo(texto: &str, color: Option<&'static str>, negrita: bool) -> Tramo {
+        Tramo {
+            texto: texto.to_string(),
+            color,
+            negrita,
+        }
+    }
+
+    #[test]
+    fn reinicio_del_estilo() {
+        let ansi = "\u{1b}[1m\u{1b}[91merror\u{1b}[0m: uno \u{1b}[1mdos\u{1b}[m tres";
+        assert_eq!(
+            tramos(ansi),
+            vec![vec![
+                tramo("error", Some("rojo"), true),
+                tramo(": uno ", None, false),
+                tramo("dos", None, true),
+                tramo(" tres", None, false),
+            ]]
+        );
+    }
+
+    #[test]
+    fn negrita_y_color_en_la_misma_secuencia() {
+        let ansi = "\u{1b}[1;38;5;12m-->\u{1b}[0m src/main.rs \u{1b}[1;33mwarning\u{1b}[22m!";
+        assert_eq!(
+            tramos(ansi),
+            vec![vec![
+                tramo("-->", Some("azul"), true),
+                tramo(" src/main.rs ", None, false),
+                tramo("warning", Some("amarillo"), true),
+                tramo("!", Some("amarillo"), false),
+            ]]
+        );
+    }
+
+    #[test]
+    fn una_linea_empieza_con_el_estilo_de_la_anterior() {
+        let ansi = "\u{1b}[1m\u{1b}[94m1\u{1b}[0m \u{1b}[94m|\n2 |\u{1b}[0m fin\n";
+        assert_eq!(
+            tramos(ansi),
+            vec![
+                vec![
+                    tramo("1", Some("azul"), true),
+                    tramo(" ", None, false),
+                    tramo("|", Some("azul"), false)
+                ],
+                vec![
+                    tramo("2 |", Some("azul"), false),
+                    tramo(" fin", None, false)
+                ],
+            ]
+        );
+    }
+}
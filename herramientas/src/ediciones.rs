@@ -0,0 +1,523 @@
+//! Matriz de ediciones: cada fragmento se compila y ejecuta en la edición
+//! 2021 y en la 2024.
+//!
+//! El libro muestra `edition = "2021"` en `Cargo.toml`, pero quien cree hoy
+//! un proyecto con `cargo new` obtiene la 2024. Algunas reglas cambian entre
+//! ediciones (temporales de la expresión final de un bloque, `IntoIterator`
+//! para `Box<[T]>`, la palabra reservada `gen`, `unsafe extern`...), así que
+//! un ejemplo puede compilar en una y no en la otra, o imprimir otra cosa.
+//!
+//! Un fragmento cuyo comportamiento cambia debe marcarse con
+//! `edicion=<año>`, la edición que describe el texto; si no, `libro generar`
+//! falla. Todos los que cambian se listan en
+//! `build/generado/ediciones.typ`.
+//!
+//! Los programas se ejecutan aislados ([`crate::aislado`]) y los que no
+//! terminan en [`LIMITE`] se cortan. Como aquí se ejecutan todos los
+//! fragmentos, también se comprueban dos atributos sobre la edición del
+//! libro:
+//!
+//! - `no_termina`: el fragmento es un bucle infinito a propósito. Uno que no
+//!   termina sin llevarlo hace fallar `libro generar`, y al revés.
+//! - `stack_overflow`: el programa debe abortar al desbordar el stack
+//!   (8 MiB, como en Linux).
+//!
+//! Lo mismo con los de la línea de apertura ([`crate::fragmentos`]): un
+//! fragmento `should_panic` (o `panico=`, o `transcripcion`) debe entrar en
+//! pánico y uno `compile_fail` (`no_compila`), no compilar; uno sin marcar
+//! que entra en pánico o no compila también hace fallar `libro generar`. Los
+//! `no_run` se compilan sin ejecutarse, y los `ignore` y `fragment` (un trozo
+//! al que le falta código de otro ejemplo) no se prueban.
+//!
+//! Cada programa se ejecuta [`EJECUCIONES`] veces: si en la misma edición
+//! imprime cosas distintas (direcciones de memoria, orden de un `HashMap`),
+//! solo se compara si termina bien o con error.
+//!
+//! La salida escrita a mano en el libro se compara con la real
+//! ([`crate::comentarios::comprobar`]).
+//!
+//! Lo que hace cada fragmento en su edición queda además en
+//! `build/generado/resultados.json` ([`crate::resultados`]).
+//!
+//! Los fragmentos se compilan por lotes ([`crate::lotes`]) y se ejecutan en
+//! paralelo. Lo que hace cada uno se guarda en `build/cache/ediciones.json`
+//! ([`crate::cache`]): la siguiente vez solo se prueban los fragmentos cuyo
+//! código cambió, o todos si cambió el toolchain.
+
+use std::time::Duration;
+
+use crate::aislado::{Limites, Terminacion};
+use crate::cache::{self, Cache, Compilaciones};
+use crate::fragmentos::Fragmento;
+use crate::json::Valor;
+use crate::lotes::{self, Ejecutable};
+use crate::rustc::{self, EDICION, EDICIONES};
+use crate::{Error, Libro, Resultado, comentarios, generado, resultados, typst};
+
+/// Tiempo máximo de ejecución de cada fragmento.
+pub const LIMITE: Duration = Duration::from_secs(2);
+
+/// Veces que se ejecuta cada programa que termina.
+pub const EJECUCIONES: usize = 5;
+
+/// Lo que hace un fragmento en una edición.
+#[derive(Debug, Clone)]
+pub enum Comportamiento {
+    /// No compila; contiene el primer error de `rustc`.
+    NoCompila(String),
+    /// Se cortó al pasar de [`LIMITE`].
+    NoTermina,
+    /// Abortó al desbordar el stack.
+    DesbordaPila,
+    /// Terminó con un pánico; contiene lo que imprimió antes.
+    Panico(String),
+    /// Compila, pero no se ejecuta (`no_run`).
+    NoEjecutado,
+    Termina {
+        /// Salidas distintas observadas, en orden de aparición.
+        salidas: Vec<String>,
+        exito: bool,
+    },
+}
+
+impl Comportamiento {
+    fn equivale(&self, otro: &Comportamiento) -> bool {
+        use Comportamiento::*;
+        match (self, otro) {
+            (NoCompila(_), NoCompila(_))
+            | (NoTermina, NoTermina)
+            | (DesbordaPila, DesbordaPila)
+            | (Panico(_), Panico(_))
+            | (NoEjecutado, NoEjecutado) => true,
+            (
+                Termina {
+                    salidas: a,
+                    exito: ea,
+                },
+                Termina {
+                    salidas: b,
+                    exito: eb,
+                },
+            ) => ea == eb && (a.len() > 1 || b.len() > 1 || a == b),
+            _ => false,
+        }
+    }
+
+    /// Lo que imprimió por stdout, si se ejecutó.
+    pub fn salida(&self) -> Option<&str> {
+        match self {
+            Comportamiento::Panico(salida) => Some(salida),
+            Comportamiento::Termina { salidas, .. } => salidas.first().map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// Cómo se guarda en la caché.
+    fn valor(&self) -> Valor {
+        let tipo = |nombre: &str| (String::from("tipo"), Valor::Cadena(nombre.to_string()));
+        let texto =
+            |clave: &str, texto: &str| (clave.to_string(), Valor::Cadena(texto.to_string()));
+        Valor::Objeto(match self {
+            Comportamiento::NoCompila(error) => vec![tipo("no_compila"), texto("error", error)],
+            Comportamiento::NoTermina => vec![tipo("no_termina")],
+            Comportamiento::DesbordaPila => vec![tipo("desborda_pila")],
+            Comportamiento::Panico(salida) => vec![tipo("panico"), texto("salida", salida)],
+            Comportamiento::NoEjecutado => vec![tipo("no_ejecutado")],
+            Comportamiento::Termina { salidas, exito } => vec![
+                tipo("termina"),
+                (
+                    String::from("salidas"),
+                    Valor::Lista(salidas.iter().map(|s| Valor::Cadena(s.clone())).collect()),
+                ),
+                (String::from("exito"), Valor::Bool(*exito)),
+            ],
+        })
+    }
+
+    /// Lee lo que escribió [`Comportamiento::valor`].
+    fn de(valor: &Valor) -> Option<Comportamiento> {
+        let texto = |clave: &str| valor.campo(clave).and_then(Valor::cadena).map(String::from);
+        Some(match valor.campo("tipo")?.cadena()? {
+            "no_compila" => Comportamiento::NoCompila(texto("error")?),
+            "no_termina" => Comportamiento::NoTermina,
+            "desborda_pila" => Comportamiento::DesbordaPila,
+            "panico" => Comportamiento::Panico(texto("salida")?),
+            "no_ejecutado" => Comportamiento::NoEjecutado,
+            "termina" => Comportamiento::Termina {
+                salidas: valor
+                    .campo("salidas")?
+                    .lista()
+                    .iter()
+                    .map(|s| s.cadena().map(String::from))
+                    .collect::<Option<_>>()?,
+                exito: matches!(valor.campo("exito")?, Valor::Bool(true)),
+            },
+            _ => return None,
+        })
+    }
+
+    /// Descripción corta para la tabla del libro.
+    pub fn resumen(&self) -> String {
+        match self {
+            Comportamiento::NoCompila(error) => format!("no compila: {error}"),
+            Comportamiento::NoTermina => String::from("no termina"),
+            Comportamiento::DesbordaPila => String::from("desborda el stack"),
+            Comportamiento::Panico(_) => String::from("entra en pánico"),
+            Comportamiento::NoEjecutado => String::from("compila"),
+            Comportamiento::Termina { exito: false, .. } => String::from("termina con error"),
+            Comportamiento::Termina { salidas, .. } => {
+                match salidas[0].lines().next().filter(|l| !l.is_empty()) {
+                    Some(linea) => format!("imprime «{linea}»"),
+                    None => String::from("compila y termina"),
+                }
+            }
+        }
+    }
+}
+
+/// Un fragmento y lo que hace en cada una de [`EDICIONES`].
+#[derive(Debug)]
+pub struct Fila<'a> {
+    pub fragmento: &'a Fragmento,
+    pub comportamientos: Vec<Comportamiento>,
+}
+
+impl Fila<'_> {
+    /// Comportamiento en `edicion`. Falla si no es de [`EDICIONES`] (un
+    /// `edicion=2018` en el capítulo, por ejemplo).
+    pub fn en(&self, edicion: &str) -> Resultado<&Comportamiento> {
+        let indice = EDICIONES
+            .iter()
+            .position(|e| *e == edicion)
+            .ok_or_else(|| {
+                Error::Libro(format!(
+                    "{}: edición desconocida `{edicion}` (se prueban {})",
+                    self.fragmento.ubicacion(),
+                    EDICIONES.join(" y ")
+                ))
+            })?;
+        Ok(&self.comportamientos[indice])
+    }
+
+    /// Si se comporta distinto según la edición.
+    pub fn difiere(&self) -> bool {
+        self.comportamientos
+            .windows(2)
+            .any(|par| !par[0].equivale(&par[1]))
+    }
+}
+
+/// Compila y ejecuta `fragmento` en `edicion`.
+pub fn probar(fragmento: &Fragmento, edicion: &str) -> Resultado<Comportamiento> {
+    let compilado =
+        lotes::compilar(&[(fragmento.programa(), edicion, fragmento.opciones())])?.remove(0);
+    ejecutar(fragmento, &compilado)
+}
+
+/// Lo que hace `fragmento` ya compilado (o la salida de `rustc`, si no
+/// compiló).
+fn ejecutar(
+    fragmento: &Fragmento,
+    compilado: &Result<Ejecutable, String>,
+) -> Resultado<Comportamiento> {
+    let programa = match compilado {
+        Ok(programa) => programa,
+        Err(salida) => {
+            let error = salida
+                .lines()
+                .find(|l| l.starts_with("error"))
+                .unwrap_or("error")
+                .to_string();
+            return Ok(Comportamiento::NoCompila(error));
+        }
+    };
+    if fragmento.atributos.tiene("no_run") {
+        return Ok(Comportamiento::NoEjecutado);
+    }
+    let limites = Limites {
+        tiempo: LIMITE,
+        ..Limites::default()
+    };
+    let primera = programa.correr_con(&limites)?;
+    if primera.terminacion == Terminacion::Agotado {
+        return Ok(Comportamiento::NoTermina);
+    }
+    if primera.desborda_pila() {
+        return Ok(Comportamiento::DesbordaPila);
+    }
+    if primera.terminacion == Terminacion::Codigo(101) && primera.stderr.contains("panicked") {
+        return Ok(Comportamiento::Panico(primera.stdout));
+    }
+    let exito = primera.exito();
+    let mut salidas = vec![primera.stdout];
+    for _ in 1..EJECUCIONES {
+        let otra = programa.correr_con(&limites)?;
+        if otra.terminacion != Terminacion::Agotado && !salidas.contains(&otra.stdout) {
+            salidas.push(otra.stdout);
+        }
+    }
+    Ok(Comportamiento::Termina { salidas, exito })
+}
+
+/// Prueba cada fragmento del libro en todas las ediciones. Los que no se
+/// pueden compilar solos ([`Fragmento::compilable`]) no se prueban, y los
+/// que no cambiaron desde la última vez se toman de la caché.
+pub fn probar_todos<'a>(libro: &Libro, fragmentos: &'a [Fragmento]) -> Resultado<Vec<Fila<'a>>> {
+    let version = rustc::version()?;
+    let limite = LIMITE.as_millis().to_string();
+    let ejecuciones = EJECUCIONES.to_string();
+    let mut cache = Cache::leer(libro, "ediciones");
+
+    let compilables: Vec<&Fragmento> = fragmentos.iter().filter(|f| f.compilable()).collect();
+    // Una prueba por fragmento y edición, en ese orden.
+    let mut huellas = Vec::new();
+    let mut comportamientos = Vec::new();
+    let mut pendientes = Vec::new();
+    for fragmento in &compilables {
+        let programa = fragmento.programa();
+        let no_run = if fragmento.atributos.tiene("no_run") {
+            "no_run"
+        } else {
+            ""
+        };
+        let opciones = fragmento.opciones();
+        for edicion in EDICIONES {
+            let huella = cache::huella(&[
+                &version,
+                edicion,
+                &programa,
+                &opciones.join(" "),
+                no_run,
+                &limite,
+                &ejecuciones,
+            ]);
+            let guardado = cache.obtener(&huella).and_then(Comportamiento::de);
+            if guardado.is_none() {
+                let fuente = (programa.clone(), *edicion, opciones);
+                pendientes.push((huellas.len(), *fragmento, fuente));
+            }
+            huellas.push(huella);
+            comportamientos.push(guardado);
+        }
+    }
+
+    let programas: Vec<lotes::Fuente> = pendientes.iter().map(|(_, _, p)| p.clone()).collect();
+    let compilados: Vec<_> = pendientes
+        .iter()
+        .zip(lotes::compilar(&programas)?)
+        .collect();
+    let nuevos = lotes::en_paralelo(&compilados, |((_, fragmento, _), compilado)| {
+        ejecutar(fragmento, compilado)
+    })?;
+    for ((i, _, _), comportamiento) in pendientes.iter().zip(nuevos) {
+        cache.guardar(huellas[*i].clone(), comportamiento.valor());
+        comportamientos[*i] = Some(comportamiento);
+    }
+    cache.escribir(libro)?;
+
+    let mut comportamientos = comportamientos.into_iter().flatten();
+    Ok(compilables
+        .into_iter()
+        .map(|fragmento| Fila {
+            fragmento,
+            comportamientos: comportamientos.by_ref().take(EDICIONES.len()).collect(),
+        })
+        .collect())
+}
+
+/// Comprueba `no_termina`, `stack_overflow`, `should_panic` y
+/// `no_compila` en la edición del fragmento (la del libro si no declara
+/// otra).
+fn comprobar_atributos(filas: &[Fila]) -> Resultado<()> {
+    let mut fallos = Vec::new();
+    for fila in filas {
+        let atributos = &fila.fragmento.atributos;
+        let edicion = atributos.valor("edicion").unwrap_or(EDICION);
+        let comportamiento = fila.en(edicion)?;
+        let panico = ["should_panic", "panico", "transcripcion"];
+        for (atributo, marcado, esperado, marca) in [
+            (
+                "no_termina",
+                atributos.tiene("no_termina"),
+                matches!(comportamiento, Comportamiento::NoTermina),
+                "`no_termina`",
+            ),
+            (
+                "stack_overflow",
+                atributos.tiene("stack_overflow"),
+                matches!(comportamiento, Comportamiento::DesbordaPila),
+                "`stack_overflow`",
+            ),
+            (
+                "should_panic",
+                panico.iter().any(|a| atributos.tiene(a)),
+                matches!(comportamiento, Comportamiento::Panico(_)),
+                "`should_panic`",
+            ),
+            (
+                "no_compila",
+                atributos.tiene("no_compila"),
+                matches!(comportamiento, Comportamiento::NoCompila(_)),
+                "`no_compila` (o `fragment` si le falta código de otro ejemplo)",
+            ),
+        ] {
+            match (marcado, esperado) {
+                (true, false) => fallos.push(format!(
+                    "  {}: marcado con `{atributo}`, pero {} en la edición {edicion}",
+                    fila.fragmento.ubicacion(),
+                    comportamiento.resumen()
+                )),
+                (false, true) => fallos.push(format!(
+                    "  {}: {} en la edición {edicion}; si es a propósito, márcalo con {marca}",
+                    fila.fragmento.ubicacion(),
+                    comportamiento.resumen()
+                )),
+                _ => {}
+            }
+        }
+    }
+    if fallos.is_empty() {
+        return Ok(());
+    }
+    Err(Error::Libro(format!(
+        "comportamiento inesperado:\n{}",
+        fallos.join("\n")
+    )))
+}
+
+/// Comprueba la salida escrita en el libro (`// Salida: ...` y los bloques
+/// ```yaml tras `Salida:`) con la real en la edición de cada fragmento. Los
+/// que imprimen cosas distintas en cada ejecución no se comprueban.
+fn comprobar_salidas(libro: &Libro, filas: &[Fila]) -> Resultado<()> {
+    let mut compilaciones = Compilaciones::leer(libro, "comentarios")?;
+    let mut fallos = Vec::new();
+    for fila in filas {
+        let edicion = fila.fragmento.atributos.valor("edicion").unwrap_or(EDICION);
+        let comportamiento = fila.en(edicion)?;
+        if let Comportamiento::Termina { salidas, .. } = comportamiento
+            && salidas.len() > 1
+        {
+            continue;
+        }
+        if let Some(salida) = comportamiento.salida() {
+            fallos.extend(comentarios::comprobar(
+                fila.fragmento,
+                edicion,
+                salida,
+                &mut compilaciones,
+            )?);
+        }
+    }
+    compilaciones.escribir(libro)?;
+    if fallos.is_empty() {
+        return Ok(());
+    }
+    Err(Error::Libro(format!(
+        "salidas escritas que no coinciden con las reales:\n{}",
+        fallos.join("\n")
+    )))
+}
+
+/// Genera `build/generado/ediciones.typ` y falla si algún fragmento que
+/// cambia con la edición no lo declara.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let fragmentos = libro.fragmentos()?;
+    let filas = probar_todos(libro, &fragmentos)?;
+    comprobar_atributos(&filas)?;
+    comprobar_salidas(libro, &filas)?;
+    let distintas: Vec<&Fila> = filas.iter().filter(|f| f.difiere()).collect();
+
+    let sin_marcar: Vec<String> = distintas
+        .iter()
+        .filter(|f| {
+            !f.fragmento
+                .atributos
+                .valor("edicion")
+                .is_some_and(|e| EDICIONES.contains(&e))
+        })
+        .map(|f| {
+            let detalle: Vec<String> = EDICIONES
+                .iter()
+                .zip(&f.comportamientos)
+                .map(|(edicion, c)| format!("{edicion}: {}", c.resumen()))
+                .collect();
+            format!(
+                "  {}: cambia con la edición ({})",
+                f.fragmento.ubicacion(),
+                detalle.join("; ")
+            )
+        })
+        .collect();
+    if !sin_marcar.is_empty() {
+        return Err(Error::Libro(format!(
+            "fragmentos que dependen de la edición sin `edicion=<año>`:\n{}",
+            sin_marcar.join("\n")
+        )));
+    }
+
+    let compilan = filas
+        .iter()
+        .filter(|f| {
+            f.comportamientos
+                .iter()
+                .any(|c| !matches!(c, Comportamiento::NoCompila(_)))
+        })
+        .count();
+    let mut contenido = format!(
+        "{}De los {} fragmentos que compilan en alguna edición, {} se {} \
+         distinto en {}.\n",
+        generado::CABECERA,
+        compilan,
+        distintas.len(),
+        if distintas.len() == 1 {
+            "comporta"
+        } else {
+            "comportan"
+        },
+        EDICIONES.join(" y ")
+    );
+    if !distintas.is_empty() {
+        let mut encabezados = vec![String::from("[*Ejemplo*]")];
+        encabezados.extend(EDICIONES.iter().map(|e| format!("[*Edición {e}*]")));
+        let filas: Vec<Vec<String>> = distintas
+            .iter()
+            .map(|f| {
+                let mut fila = vec![format!("[#{}]", typst::codigo(&f.fragmento.ubicacion()))];
+                fila.extend(f.comportamientos.iter().map(|c| typst::texto(&c.resumen())));
+                fila
+            })
+            .collect();
+        contenido.push('\n');
+        contenido.push_str(&typst::figura(
+            &typst::tabla(&encabezados, &filas),
+            "[Ejemplos que cambian según la edición]",
+            Some("ediciones"),
+        ));
+    }
+    generado::escribir(libro, "ediciones.typ", &contenido)?;
+    resultados::escribir(libro, &fragmentos, &filas)?;
+    Ok(distintas.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::fragmentos::extraer;
+
+    #[test]
+    fn en_una_edicion_que_no_se_prueba_es_un_error() {
+        let fragmento = extraer(Path::new("prueba.typ"), "```rust\nlet gen = 5;\n```\n").remove(0);
+        let fila = Fila {
+            fragmento: &fragmento,
+            comportamientos: vec![Comportamiento::NoEjecutado, Comportamiento::NoTermina],
+        };
+        assert!(matches!(fila.en("2024"), Ok(Comportamiento::NoTermina)));
+        let Err(Error::Libro(mensaje)) = fila.en("2018") else {
+            panic!("la edición 2018 no se prueba");
+        };
+        assert!(mensaje.contains("prueba.typ"), "{mensaje}");
+        assert!(mensaje.contains("`2018`"), "{mensaje}");
+    }
+}
@@ -0,0 +1,507 @@
+//! Workspace de Cargo con todos los ejemplos del libro.
+//!
+//! `libro generar ejemplos` escribe en `ejemplos/` un workspace con un crate
+//! por capítulo. Los módulos siguen las carpetas del capítulo, sin
+//! `sections` y con los nombres en español ([`TRADUCCIONES`]):
+//! `05-Variables/sections/collections/vec.rs` es `variables::colecciones::vec`.
+//! Cada fragmento es un módulo con su código y un `#[test]` que ejecuta su
+//! `main`, llamado como el `id` del fragmento o `linea_<n>`:
+//!
+//! ```text
+//! cargo test --manifest-path ejemplos/Cargo.toml -- colecciones::vec
+//! ```
+//!
+//! Lo que el libro sabe de cada fragmento queda en la prueba:
+//!
+//! - Los que entran en pánico llevan `#[should_panic]`; los que no terminan,
+//!   desbordan el stack, terminan con error o son `no_run` se marcan con
+//!   `#[ignore]` (se compilan, pero no se ejecutan). Los `release` solo se
+//!   ejecutan con `cargo test --release`.
+//! - Los que no compilan (los `no_compila`) van en `tests/ui/<nombre>.rs`
+//!   con el diagnóstico de `rustc` en `tests/ui/<nombre>.stderr`, al estilo
+//!   de `trybuild`: `tests/ui.rs` comprueba que siguen sin compilar y
+//!   compara el diagnóstico.
+//! - Los que tienen `escenario=` ([`crate::escenarios`]) se compilan como
+//!   binario y `tests/<nombre>.rs` lo ejecuta en cada escenario con sus archivos, su entrada, su entorno y sus
+//!   argumentos.
+//! - Los de otra edición van en un crate aparte, `<capítulo>_<edición>`.
+//! - Los que usan rutas `crate::` o atributos `#![...]` no pueden ir dentro
+//!   de un módulo: cada uno es una prueba de integración en `tests/`.
+//!
+//! Los `fragment`, los `ignore`, los archivos de un `proyecto=` y los
+//! fragmentos de fuera de los capítulos no se incluyen: `omitidos.txt` los
+//! lista con el motivo. Las líneas ocultas (`# `) de un fragmento sí forman
+//! parte de su prueba. El directorio no se versiona; abrirlo con
+//! rust-analyzer permite navegar cualquier ejemplo.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::ediciones::{self, Comportamiento};
+use crate::escenarios::{self, Escenario};
+use crate::fragmentos::Fragmento;
+use crate::rustc::{self, DirTemporal, EDICION};
+use crate::{Libro, Resultado, generado};
+
+/// Directorio (relativo a la raíz) del workspace.
+pub const DIRECTORIO: &str = "ejemplos";
+
+/// Carpetas del libro cuyo nombre de módulo no sale de quitarles el número:
+/// las que están en inglés y las que son palabras reservadas.
+pub const TRADUCCIONES: &[(&str, &str)] = &[
+    ("collections", "colecciones"),
+    ("compound_type", "compuestos"),
+    ("scalar_type", "escalares"),
+    ("generic", "genericos"),
+    ("struct", "estructuras"),
+];
+
+/// Carpetas que no dan un módulo.
+const OMITIDAS: &[&str] = &["sections"];
+
+/// Código que solo tiene sentido en la raíz de un crate: esos fragmentos
+/// van solos en `tests/`.
+const SUELTOS: &[&str] = &["crate::", "#!["];
+
+/// Opciones con que se compilan las pruebas `tests/ui`. Se compilan del
+/// todo, no solo `--emit=metadata`: algunos errores, como el de
+/// `unconditional_panic`, solo salen al generar el código.
+const OPCIONES_UI: &[&str] = &["--crate-type", "bin", "-A", "unused"];
+
+/// Nombre de módulo de una carpeta o archivo: `05-Variables` → `variables`.
+pub fn nombre(segmento: &str) -> String {
+    let sin_numero = segmento.trim_start_matches(|c: char| c.is_ascii_digit());
+    let sin_numero = sin_numero.strip_prefix('-').unwrap_or(sin_numero);
+    let nombre = sin_numero.to_lowercase().replace('-', "_");
+    TRADUCCIONES
+        .iter()
+        .find(|(carpeta, _)| *carpeta == nombre)
+        .map_or(nombre, |(_, traduccion)| traduccion.to_string())
+}
+
+/// Capítulo (el crate) y módulos de un archivo del libro, o `None` si no es
+/// de un capítulo.
+fn ubicar(archivo: &Path) -> Option<(String, Vec<String>)> {
+    let relativo = archivo.strip_prefix("src/chapters").ok()?;
+    let mut partes: Vec<String> = relativo
+        .iter()
+        .filter_map(|p| p.to_str())
+        .filter(|p| !OMITIDAS.contains(p))
+        .map(|p| nombre(p.split('.').next().unwrap_or(p)))
+        .collect();
+    if partes.last().is_some_and(|p| p == "index") {
+        partes.pop();
+    }
+    let capitulo = partes.remove(0);
+    Some((capitulo, partes))
+}
+
+/// Nombre del módulo y la prueba de un fragmento.
+fn nombre_prueba(fragmento: &Fragmento) -> String {
+    match fragmento.id() {
+        Some(id) => id.replace('-', "_"),
+        None => format!("linea_{}", fragmento.linea),
+    }
+}
+
+/// Atributos de la prueba según lo que hace el fragmento, si compila.
+fn atributos(comportamiento: &Comportamiento) -> &'static str {
+    match comportamiento {
+        Comportamiento::NoCompila(_) => unreachable!("va en `tests/ui/`"),
+        Comportamiento::Panico(_) => "#[should_panic]\n",
+        Comportamiento::NoEjecutado => "#[ignore = \"no_run\"]\n",
+        Comportamiento::NoTermina => "#[ignore = \"no termina\"]\n",
+        Comportamiento::DesbordaPila => "#[ignore = \"desborda el stack\"]\n",
+        Comportamiento::Termina { exito: false, .. } => "#[ignore = \"termina con error\"]\n",
+        Comportamiento::Termina { exito: true, .. } => "",
+    }
+}
+
+/// Por qué un fragmento no tiene prueba, o `None` si la tiene.
+fn motivo_omision(fragmento: &Fragmento) -> Option<String> {
+    if let Some(proyecto) = fragmento.atributos.valor("proyecto") {
+        return Some(format!("archivo del proyecto `{proyecto}`"));
+    }
+    for atributo in ["ignore", "fragment"] {
+        if fragmento.atributos.tiene(atributo) {
+            return Some(format!("`{atributo}`"));
+        }
+    }
+    ubicar(&fragmento.archivo)
+        .is_none()
+        .then(|| "fuera de los capítulos".to_string())
+}
+
+/// Diagnóstico de `rustc` para `tests/ui/<nombre>.rs`, compilado como lo
+/// hace `tests/ui.rs`.
+fn diagnostico_ui(nombre: &str, fuente: &str, edicion: &str) -> Resultado<String> {
+    let dir = DirTemporal::nuevo("ui")?;
+    fs::write(dir.ruta().join(format!("{nombre}.rs")), fuente)?;
+    let salida = Command::new(rustc::rustc())
+        .current_dir(dir.ruta())
+        .args(["--edition", edicion])
+        .args(OPCIONES_UI)
+        .arg("--out-dir")
+        .arg(dir.ruta().join("salida"))
+        .arg(format!("{nombre}.rs"))
+        .output()?;
+    Ok(String::from_utf8_lossy(&salida.stderr).into_owned())
+}
+
+/// Un crate del workspace.
+#[derive(Debug, Default)]
+struct Crate {
+    edicion: String,
+    /// Código de cada módulo (vacío para la raíz), sin las declaraciones
+    /// de submódulos.
+    modulos: BTreeMap<Vec<String>, String>,
+    /// `tests/ui/<nombre>.rs`: (nombre, fuente, diagnóstico).
+    ui: Vec<(String, String, String)>,
+    /// `src/bin/<nombre>.rs` y `tests/<nombre>.rs`.
+    binarios: Vec<(String, String, String)>,
+    /// `tests/<nombre>.rs` de los fragmentos que no pueden ir en un módulo.
+    sueltos: Vec<(String, String)>,
+}
+
+impl Crate {
+    fn modulo(&mut self, ruta: &[String]) -> &mut String {
+        // Se registran también los intermedios, para declararlos.
+        for n in 0..ruta.len() {
+            self.modulos.entry(ruta[..n].to_vec()).or_default();
+        }
+        self.modulos.entry(ruta.to_vec()).or_default()
+    }
+
+    /// Archivos del crate, relativos a su carpeta.
+    fn archivos(&self, nombre: &str) -> Vec<(PathBuf, String)> {
+        let mut archivos = Vec::new();
+        let mut manifiesto = format!(
+            "{}[package]\nname = \"{nombre}\"\nversion = \"0.0.0\"\nedition = \"{}\"\npublish = false\n\n\
+             [lib]\ndoctest = false\n",
+            CABECERA_TOML, self.edicion
+        );
+        for (binario, _, _) in &self.binarios {
+            manifiesto.push_str(&format!(
+                "\n[[bin]]\nname = \"{binario}\"\npath = \"src/bin/{binario}.rs\"\ntest = false\n"
+            ));
+        }
+        archivos.push((PathBuf::from("Cargo.toml"), manifiesto));
+
+        for (ruta, codigo) in &self.modulos {
+            let mut contenido = String::from(generado::CABECERA);
+            if ruta.is_empty() {
+                contenido.push_str("#![allow(warnings, clippy::all)]\n");
+            }
+            for otra in self.modulos.keys() {
+                if otra.len() == ruta.len() + 1 && otra.starts_with(ruta) {
+                    contenido.push_str(&format!("\npub mod {};\n", otra[ruta.len()]));
+                }
+            }
+            contenido.push_str(codigo);
+            let archivo = match ruta.split_last() {
+                None => PathBuf::from("src/lib.rs"),
+                Some((ultimo, padres)) => {
+                    let mut archivo = PathBuf::from("src");
+                    archivo.extend(padres);
+                    archivo.join(format!("{ultimo}.rs"))
+                }
+            };
+            archivos.push((archivo, contenido));
+        }
+
+        if !self.ui.is_empty() {
+            let mut pruebas = format!(
+                "{}//! Ejemplos que no deben compilar, al estilo de `trybuild`: cada\n\
+                 //! `tests/ui/<nombre>.rs` debe fallar con el diagnóstico de\n\
+                 //! `tests/ui/<nombre>.stderr`.\n\n{UI}",
+                generado::CABECERA
+            );
+            for (nombre, fuente, diagnostico) in &self.ui {
+                pruebas.push_str(&format!(
+                    "\n#[test]\nfn {nombre}() {{\n    comprobar(\"{nombre}\", \"{}\");\n}}\n",
+                    self.edicion
+                ));
+                archivos.push((
+                    PathBuf::from(format!("tests/ui/{nombre}.rs")),
+                    fuente.clone(),
+                ));
+                archivos.push((
+                    PathBuf::from(format!("tests/ui/{nombre}.stderr")),
+                    diagnostico.clone(),
+                ));
+            }
+            archivos.push((PathBuf::from("tests/ui.rs"), pruebas));
+        }
+
+        for (nombre, prueba) in &self.sueltos {
+            archivos.push((PathBuf::from(format!("tests/{nombre}.rs")), prueba.clone()));
+        }
+        for (binario, programa, pruebas) in &self.binarios {
+            archivos.push((
+                PathBuf::from(format!("src/bin/{binario}.rs")),
+                format!("{}{programa}", generado::CABECERA),
+            ));
+            archivos.push((
+                PathBuf::from(format!("tests/{binario}.rs")),
+                pruebas.clone(),
+            ));
+        }
+        archivos
+    }
+}
+
+const CABECERA_TOML: &str = "# Generado por `libro generar`. No editar a mano.\n";
+
+/// Cuerpo común de `tests/ui.rs`.
+const UI: &str = r#"use std::path::Path;
+use std::process::Command;
+
+fn comprobar(nombre: &str, edicion: &str) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/ui");
+    let salida = std::env::temp_dir().join(format!("ejemplos-ui-{}-{nombre}", std::process::id()));
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let resultado = Command::new(rustc)
+        .current_dir(&dir)
+        .args(["--edition", edicion])
+        .args(["--crate-type", "bin", "-A", "unused"])
+        .arg("--out-dir")
+        .arg(&salida)
+        .arg(format!("{nombre}.rs"))
+        .output()
+        .expect("no se pudo ejecutar rustc");
+    let _ = std::fs::remove_dir_all(&salida);
+    assert!(!resultado.status.success(), "{nombre}.rs compila, pero no debería");
+    let esperado = std::fs::read_to_string(dir.join(format!("{nombre}.stderr"))).unwrap();
+    assert_eq!(String::from_utf8_lossy(&resultado.stderr), esperado);
+}
+"#;
+
+/// Cuerpo común de `tests/<binario>.rs`.
+const ESCENARIO: &str = r#"use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Ejecuta el binario en un directorio nuevo con `archivos`, `entrada`,
+/// `entorno` y `args`; devuelve su stdout y su código de salida.
+fn ejecutar(
+    escenario: &str,
+    archivos: &[(&str, &str)],
+    entrada: Option<&str>,
+    entorno: &[(&str, &str)],
+    args: &[&str],
+) -> (String, Option<i32>) {
+    let dir = std::env::temp_dir().join(format!(
+        "ejemplos-{}-{escenario}-{}",
+        env!("CARGO_CRATE_NAME"),
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    for (ruta, contenido) in archivos {
+        let destino = dir.join(ruta);
+        std::fs::create_dir_all(destino.parent().unwrap()).unwrap();
+        std::fs::write(destino, contenido).unwrap();
+    }
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut hijo = Command::new(BINARIO)
+        .current_dir(&dir)
+        .args(args)
+        .envs(entorno.iter().copied())
+        .stdin(if entrada.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    if let (Some(mut stdin), Some(entrada)) = (hijo.stdin.take(), entrada) {
+        let _ = stdin.write_all(entrada.as_bytes());
+    }
+    let salida = hijo.wait_with_output().unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    (String::from_utf8_lossy(&salida.stdout).into_owned(), salida.status.code())
+}
+"#;
+
+/// `&[("a", "b")]` de una lista de pares.
+fn pares(pares: &[(String, String)]) -> String {
+    let pares: Vec<String> = pares
+        .iter()
+        .map(|(k, v)| format!("({k:?}, {v:?})"))
+        .collect();
+    format!("&[{}]", pares.join(", "))
+}
+
+/// Pruebas de `tests/<binario>.rs` para `escenarios`.
+fn pruebas_escenarios(binario: &str, escenarios: &[Escenario]) -> String {
+    let mut pruebas = format!(
+        "{}//! Escenarios de `{binario}` (ver `herramientas/escenarios/`).\n\n\
+         const BINARIO: &str = env!(\"CARGO_BIN_EXE_{binario}\");\n\n{ESCENARIO}",
+        generado::CABECERA
+    );
+    for escenario in escenarios {
+        let args: Vec<String> = escenario.args.iter().map(|a| format!("{a:?}")).collect();
+        pruebas.push_str(&format!(
+            "\n/// Rama `{}`.\n#[test]\nfn {}() {{\n    let (salida, codigo) = ejecutar(\n        {:?},\n        {},\n        {:?},\n        {},\n        &[{}],\n    );\n    assert_eq!(salida, {:?});\n    assert_eq!(codigo, Some({}));\n}}\n",
+            escenario.rama,
+            escenario.nombre.replace('-', "_"),
+            escenario.nombre,
+            pares(&escenario.archivos),
+            escenario.entrada,
+            pares(&escenario.entorno),
+            args.join(", "),
+            escenario.salida,
+            escenario.codigo,
+        ));
+    }
+    pruebas
+}
+
+/// Escribe el workspace `ejemplos/`. Devuelve cuántas pruebas tiene.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut crates: BTreeMap<String, Crate> = BTreeMap::new();
+    let mut pruebas = 0;
+    let fragmentos = libro.fragmentos()?;
+    let omitidos: Vec<String> = fragmentos
+        .iter()
+        .filter_map(|f| Some(format!("{}: {}", f.ubicacion(), motivo_omision(f)?)))
+        .collect();
+    for fila in ediciones::probar_todos(libro, &fragmentos)? {
+        let fragmento = fila.fragmento;
+        let Some((capitulo, modulos)) = ubicar(&fragmento.archivo) else {
+            continue;
+        };
+        let edicion = fragmento.atributos.valor("edicion").unwrap_or(EDICION);
+        let nombre_crate = if edicion == EDICION {
+            capitulo
+        } else {
+            format!("{capitulo}_{edicion}")
+        };
+        let prueba = nombre_prueba(fragmento);
+        let origen = format!("/// `{}`\n", fragmento.ubicacion());
+
+        if fragmento.atributos.tiene(escenarios::ATRIBUTO) {
+            let escenarios = escenarios::de(libro, fragmento)?;
+            let krate = crates.entry(nombre_crate).or_default();
+            krate.edicion = edicion.to_string();
+            krate.binarios.push((
+                prueba.clone(),
+                format!(
+                    "//! `{}`\n\n{}",
+                    fragmento.ubicacion(),
+                    fragmento.programa()
+                ),
+                pruebas_escenarios(&prueba, &escenarios),
+            ));
+            pruebas += escenarios.len();
+            continue;
+        }
+
+        let comportamiento = fila.en(edicion)?;
+        if let Comportamiento::NoCompila(_) = comportamiento {
+            let fuente = format!(
+                "//! `{}`\n\n{}",
+                fragmento.ubicacion(),
+                fragmento.programa()
+            );
+            let diagnostico = diagnostico_ui(&prueba, &fuente, edicion)?;
+            let krate = crates.entry(nombre_crate).or_default();
+            krate.edicion = edicion.to_string();
+            krate.ui.push((prueba, fuente, diagnostico));
+            pruebas += 1;
+            continue;
+        }
+        let mut atributos = atributos(comportamiento).to_string();
+        if fragmento.atributos.tiene("release") {
+            // Lo que hace con `-O`: sin `--release`, `cargo test` comprueba
+            // los desbordamientos.
+            atributos.insert_str(
+                0,
+                "#[cfg_attr(debug_assertions, ignore = \"release: cargo test --release\")]\n",
+            );
+        }
+        // `#[should_panic]` exige que la prueba devuelva `()`.
+        let llamada = if atributos.contains("#[should_panic]") {
+            "fn ejecutar() {\n    main();\n}"
+        } else {
+            "fn ejecutar() -> impl std::process::Termination {\n    main()\n}"
+        };
+        let krate = crates.entry(nombre_crate).or_default();
+        krate.edicion = edicion.to_string();
+        let programa = fragmento.programa();
+        if SUELTOS.iter().any(|s| programa.contains(s)) {
+            let mut nombre = modulos.clone();
+            nombre.push(prueba);
+            krate.sueltos.push((
+                nombre.join("_"),
+                format!(
+                    "{}#![allow(warnings, clippy::all)]\n//! `{}`\n\n{}\n\n#[test]\n{atributos}{llamada}\n",
+                    generado::CABECERA,
+                    fragmento.ubicacion(),
+                    programa.trim_end()
+                ),
+            ));
+        } else {
+            krate.modulo(&modulos).push_str(&format!(
+                "\n{origen}pub mod {prueba} {{\n{}\n\n#[test]\n{atributos}{llamada}\n}}\n",
+                programa.trim_end()
+            ));
+        }
+        pruebas += 1;
+    }
+
+    let mut escritos = Vec::new();
+    let miembros: Vec<String> = crates.keys().map(|c| format!("    \"{c}\",")).collect();
+    let workspace = format!(
+        "{CABECERA_TOML}# Un crate por capítulo del libro: `cargo test` ejecuta todos los ejemplos.\n\
+         [workspace]\nresolver = \"3\"\nmembers = [\n{}\n]\n",
+        miembros.join("\n")
+    );
+    escritos.push(generado::escribir_en(
+        libro,
+        DIRECTORIO,
+        "Cargo.toml",
+        &workspace,
+    )?);
+    escritos.push(generado::escribir_en(
+        libro,
+        DIRECTORIO,
+        "omitidos.txt",
+        &format!(
+            "{CABECERA_TOML}# Fragmentos del libro sin prueba en este workspace.\n{}\n",
+            omitidos.join("\n")
+        ),
+    )?);
+    for (nombre, krate) in &crates {
+        for (ruta, contenido) in krate.archivos(nombre) {
+            escritos.push(generado::escribir_en(
+                libro,
+                DIRECTORIO,
+                Path::new(nombre).join(ruta),
+                &contenido,
+            )?);
+        }
+    }
+    limpiar(&libro.raiz.join(DIRECTORIO), &escritos)?;
+    Ok(pruebas)
+}
+
+/// Borra de `dir` lo que ya no se genera, salvo `target/` y `Cargo.lock`.
+fn limpiar(dir: &Path, escritos: &[PathBuf]) -> Resultado<()> {
+    for entrada in fs::read_dir(dir)? {
+        let ruta = entrada?.path();
+        let nombre = ruta
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if nombre == "target" || nombre == "Cargo.lock" {
+            continue;
+        }
+        if ruta.is_dir() {
+            limpiar(&ruta, escritos)?;
+            if fs::read_dir(&ruta)?.next().is_none() {
+                fs::remove_dir(&ruta)?;
+            }
+        } else if !escritos.contains(&ruta) {
+            fs::remove_file(&ruta)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,388 @@
+//! Tablas de rangos, tamaños y sufijos de los tipos enteros.
+//!
+//! Los valores salen de compilar `sondas/enteros.rs` con el `rustc` local,
+//! así que `i128::MAX` en el libro es el de verdad. La sonda también evalúa
+//! los métodos de desbordamiento (`wrapping_*`, `checked_*`,
+//! `saturating_*`) y el generador comprueba lo que el capítulo afirma sobre
+//! el desbordamiento en modo debug y release.
+
+use crate::cache::Compilaciones;
+use crate::rustc::EDICION;
+use crate::{Error, Libro, Resultado, generado, memoria, typst};
+
+const SONDA: &str = include_str!("../sondas/enteros.rs");
+
+/// Desbordamiento que rustc puede ver al compilar.
+const DESBORDAMIENTO_CONSTANTE: &str = "fn main() {\n    let x: u8 = 255;\n    let y = x + 1;\n}\n";
+
+/// Desbordamiento que solo aparece al ejecutar.
+const DESBORDAMIENTO_EJECUCION: &str = "fn siguiente(x: u8) -> u8 {\n    x + 1\n}\n\n\
+    fn main() {\n    println!(\"{}\", siguiente(std::hint::black_box(255)));\n}\n";
+
+/// Lo que el capítulo afirma sobre `255u8 + 1`.
+const ERROR_CONSTANTE: &str = "this arithmetic operation will overflow";
+const PANICO_DEBUG: &str = "attempt to add with overflow";
+const VALOR_RELEASE: &str = "0";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entero {
+    pub nombre: String,
+    pub bits: u32,
+    pub tamano: usize,
+    pub min: String,
+    pub max: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Medicion {
+    pub tipos: Vec<Entero>,
+    pub arquitectura: String,
+    pub bits_puntero: u32,
+    /// (literal, tipo, tamaño)
+    pub sufijos: Vec<(String, String, usize)>,
+    /// (expresión, resultado `{:?}`)
+    pub expresiones: Vec<(String, String)>,
+    pub rustc: String,
+}
+
+impl Medicion {
+    fn tipo(&self, nombre: &str) -> Option<&Entero> {
+        self.tipos.iter().find(|t| t.nombre == nombre)
+    }
+}
+
+fn error_sonda(linea: &str) -> Error {
+    Error::Libro(format!("sonda de enteros: `{linea}`"))
+}
+
+/// Compila y ejecuta la sonda de enteros.
+pub fn medir(compilaciones: &mut Compilaciones) -> Resultado<Medicion> {
+    let mut medicion = Medicion {
+        rustc: compilaciones.version().to_string(),
+        ..Medicion::default()
+    };
+    for linea in compilaciones.ejecutar(SONDA, &[])?.lines() {
+        let campos: Vec<&str> = linea.split('\t').collect();
+        match campos[..] {
+            ["tipo", nombre, bits, tamano, min, max] => medicion.tipos.push(Entero {
+                nombre: nombre.to_string(),
+                bits: bits.parse().map_err(|_| error_sonda(linea))?,
+                tamano: tamano.parse().map_err(|_| error_sonda(linea))?,
+                min: min.to_string(),
+                max: max.to_string(),
+            }),
+            ["arquitectura", arquitectura, bits] => {
+                medicion.arquitectura = arquitectura.to_string();
+                medicion.bits_puntero = bits.parse().map_err(|_| error_sonda(linea))?;
+            }
+            ["sufijo", literal, tipo, tamano] => medicion.sufijos.push((
+                literal.to_string(),
+                tipo.to_string(),
+                tamano.parse().map_err(|_| error_sonda(linea))?,
+            )),
+            ["afirmacion", expresion, valor] => medicion
+                .expresiones
+                .push((expresion.to_string(), valor.to_string())),
+            _ => return Err(error_sonda(linea)),
+        }
+    }
+    Ok(medicion)
+}
+
+fn medido_con(medicion: &Medicion) -> String {
+    format!("#{}", typst::codigo(&medicion.rustc))
+}
+
+fn celda_tipo(nombre: &str) -> String {
+    format!("[#raw({}, lang: \"\")]", typst::cadena(nombre))
+}
+
+/// Tabla de rangos para los tipos indicados.
+fn tabla_rangos(medicion: &Medicion, nombres: &[&str], leyenda: &str, etiqueta: &str) -> String {
+    let encabezados = [
+        "[*Tipo*]",
+        "[*Bits*]",
+        "[*Valor mínimo*]",
+        "[*Valor máximo*]",
+    ]
+    .map(String::from);
+    let filas: Vec<Vec<String>> = nombres
+        .iter()
+        .filter_map(|n| medicion.tipo(n))
+        .map(|t| {
+            vec![
+                celda_tipo(&t.nombre),
+                typst::texto(&t.bits.to_string()),
+                typst::texto(&typst::numero(&t.min)),
+                typst::texto(&typst::numero(&t.max)),
+            ]
+        })
+        .collect();
+    let tabla = typst::tabla(&encabezados, &filas).replacen(
+        "columns: 4,",
+        "columns: (auto, auto, 1fr, 1fr),\n  align: (center, right, right, right),",
+        1,
+    );
+    let leyenda = format!("[{leyenda}, obtenidos con {}]", medido_con(medicion));
+    typst::figura(&tabla, &leyenda, Some(etiqueta))
+}
+
+fn tabla_constantes(medicion: &Medicion) -> String {
+    let encabezados = [
+        "[*Tipo*]",
+        "[`T::BITS`]",
+        "[`size_of::<T>()`]",
+        "[`T::MIN`]",
+        "[`T::MAX`]",
+    ]
+    .map(String::from);
+    let filas: Vec<Vec<String>> = medicion
+        .tipos
+        .iter()
+        .map(|t| {
+            vec![
+                celda_tipo(&t.nombre),
+                typst::texto(&t.bits.to_string()),
+                typst::texto(&memoria::bytes(t.tamano)),
+                typst::texto(&typst::numero(&t.min)),
+                typst::texto(&typst::numero(&t.max)),
+            ]
+        })
+        .collect();
+    let tabla = typst::tabla(&encabezados, &filas).replacen(
+        "columns: 5,",
+        "columns: (auto, auto, auto, 1fr, 1fr),\n  align: (center, right, right, right, right),",
+        1,
+    );
+    let leyenda = format!(
+        "[Constantes asociadas de cada tipo entero en {} ({} bits), obtenidas con {}]",
+        medicion.arquitectura,
+        medicion.bits_puntero,
+        medido_con(medicion)
+    );
+    typst::figura(&tabla, &leyenda, Some("tabla-enteros-constantes"))
+}
+
+fn tabla_sufijos(medicion: &Medicion) -> String {
+    let encabezados = ["[*Literal*]", "[*Tipo*]", "[*Tamaño*]"].map(String::from);
+    let filas: Vec<Vec<String>> = medicion
+        .sufijos
+        .iter()
+        .map(|(literal, tipo, tamano)| {
+            vec![
+                format!("[#raw({}, lang: \"rust\")]", typst::cadena(literal)),
+                celda_tipo(tipo),
+                typst::texto(&memoria::bytes(*tamano)),
+            ]
+        })
+        .collect();
+    let leyenda = format!(
+        "[Tipo que `rustc` asigna a cada sufijo (`type_name_of_val`), obtenido con {}]",
+        medido_con(medicion)
+    );
+    typst::figura(
+        &typst::tabla(&encabezados, &filas),
+        &leyenda,
+        Some("tabla-enteros-sufijos"),
+    )
+}
+
+/// Comportamiento de `255u8 + 1` según cuándo se detecta el desbordamiento.
+#[derive(Debug, Clone)]
+pub struct Desbordamiento {
+    pub constante: String,
+    pub debug: String,
+    pub release: String,
+}
+
+/// Compila los programas de desbordamiento y comprueba lo que dice el texto.
+pub fn desbordamiento(compilaciones: &mut Compilaciones) -> Resultado<Desbordamiento> {
+    let constante = match compilaciones.correr(DESBORDAMIENTO_CONSTANTE, EDICION, &[])? {
+        Err(msg) if msg.contains(ERROR_CONSTANTE) => format!("error: {ERROR_CONSTANTE}"),
+        Err(msg) => return Err(Error::Compilacion(msg)),
+        Ok(_) => {
+            return Err(Error::Libro(format!(
+                "el texto afirma que `255u8 + 1` con constantes no compila, pero compiló \
+                 ({ERROR_CONSTANTE})"
+            )));
+        }
+    };
+
+    let debug = compilaciones
+        .correr(DESBORDAMIENTO_EJECUCION, EDICION, &[])?
+        .map_err(Error::Compilacion)?;
+    if debug.exito || !debug.stderr.contains(PANICO_DEBUG) {
+        return Err(Error::Libro(format!(
+            "el texto afirma que en debug `x + 1` entra en pánico con «{PANICO_DEBUG}», \
+             pero el programa dio:\n{}{}",
+            debug.stderr, debug.stdout
+        )));
+    }
+
+    let release = compilaciones.ejecutar(DESBORDAMIENTO_EJECUCION, &["-O"])?;
+    if release.trim() != VALOR_RELEASE {
+        return Err(Error::Libro(format!(
+            "el texto afirma que en release `x + 1` da {VALOR_RELEASE}, pero dio {}",
+            release.trim()
+        )));
+    }
+
+    Ok(Desbordamiento {
+        constante,
+        debug: format!("pánico: {PANICO_DEBUG}"),
+        release: release.trim().to_string(),
+    })
+}
+
+fn tabla_desbordamiento(medicion: &Medicion, desbordamiento: &Desbordamiento) -> String {
+    let encabezados = ["[*Expresión*]", "[*Resultado*]"].map(String::from);
+    let codigo = |c: &str| format!("[#raw({}, lang: \"rust\")]", typst::cadena(c));
+    let mut filas = vec![
+        vec![
+            codigo("x + 1 // x: u8 = 255, constante"),
+            codigo(&desbordamiento.constante),
+        ],
+        vec![codigo("x + 1 // debug"), codigo(&desbordamiento.debug)],
+        vec![
+            codigo("x + 1 // release (-O)"),
+            codigo(&desbordamiento.release),
+        ],
+    ];
+    filas.extend(
+        medicion
+            .expresiones
+            .iter()
+            .map(|(expresion, valor)| vec![codigo(expresion), codigo(valor)]),
+    );
+    let leyenda = format!(
+        "[Desbordamiento de enteros con `x: u8 = 255`, verificado con {}]",
+        medido_con(medicion)
+    );
+    typst::figura(
+        &typst::tabla(&encabezados, &filas),
+        &leyenda,
+        Some("tabla-enteros-desbordamiento"),
+    )
+}
+
+/// Genera las tablas de `build/generado/enteros/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "enteros")?;
+    let medicion = medir(&mut compilaciones)?;
+    let desbordamiento = desbordamiento(&mut compilaciones)?;
+    compilaciones.escribir(libro)?;
+
+    let archivos = [
+        (
+            "con-signo",
+            tabla_rangos(
+                &medicion,
+                &["i8", "i16", "i32", "i64", "i128"],
+                "Rangos numéricos de los tipos enteros con signo",
+                "tabla-enteros-signados",
+            ),
+        ),
+        (
+            "sin-signo",
+            tabla_rangos(
+                &medicion,
+                &["u8", "u16", "u32", "u64", "u128"],
+                "Rangos numéricos de los tipos enteros sin signo",
+                "tabla-enteros-no-signados",
+            ),
+        ),
+        (
+            "arquitectura",
+            tabla_rangos(
+                &medicion,
+                &["usize", "isize"],
+                &format!(
+                    "Rangos de los tipos dependientes de arquitectura en {} ({} bits)",
+                    medicion.arquitectura, medicion.bits_puntero
+                ),
+                "tabla-enteros-arquitectura",
+            ),
+        ),
+        ("constantes", tabla_constantes(&medicion)),
+        ("sufijos", tabla_sufijos(&medicion)),
+        (
+            "desbordamiento",
+            tabla_desbordamiento(&medicion, &desbordamiento),
+        ),
+    ];
+    for (nombre, contenido) in &archivos {
+        let contenido = format!("{}{contenido}", generado::CABECERA);
+        generado::escribir(libro, format!("enteros/{nombre}.typ"), &contenido)?;
+    }
+    Ok(archivos.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rustc::DirTemporal;
+
+    fn compilaciones() -> (DirTemporal, Compilaciones) {
+        let dir = DirTemporal::nuevo("enteros").unwrap();
+        let libro = Libro {
+            raiz: dir.ruta().to_path_buf(),
+            bless: false,
+        };
+        let compilaciones = Compilaciones::leer(&libro, "enteros").unwrap();
+        (dir, compilaciones)
+    }
+
+    #[test]
+    fn la_sonda_mide_los_doce_enteros() {
+        let (_dir, mut compilaciones) = compilaciones();
+        let medicion = medir(&mut compilaciones).unwrap();
+        assert_eq!(medicion.tipos.len(), 12);
+        let u8 = medicion.tipo("u8").unwrap();
+        assert_eq!((u8.bits, u8.tamano), (8, 1));
+        assert_eq!((u8.min.as_str(), u8.max.as_str()), ("0", "255"));
+        assert_eq!(medicion.tipo("i128").unwrap().max, i128::MAX.to_string());
+        assert!(
+            medicion
+                .sufijos
+                .contains(&(String::from("255u8"), String::from("u8"), 1))
+        );
+        assert!(
+            medicion
+                .expresiones
+                .contains(&(String::from("x.wrapping_add(1)"), String::from("0")))
+        );
+    }
+
+    #[test]
+    fn el_desbordamiento_es_el_que_dice_el_texto() {
+        let (_dir, mut compilaciones) = compilaciones();
+        let desbordamiento = desbordamiento(&mut compilaciones).unwrap();
+        assert_eq!(
+            desbordamiento.constante,
+            format!("error: {ERROR_CONSTANTE}")
+        );
+        assert_eq!(desbordamiento.debug, format!("pánico: {PANICO_DEBUG}"));
+        assert_eq!(desbordamiento.release, VALOR_RELEASE);
+    }
+
+    #[test]
+    fn tabla_de_rangos_solo_con_los_tipos_pedidos() {
+        let entero = |nombre: &str, min: &str, max: &str| Entero {
+            nombre: nombre.to_string(),
+            bits: 8,
+            tamano: 1,
+            min: min.to_string(),
+            max: max.to_string(),
+        };
+        let medicion = Medicion {
+            tipos: vec![entero("i8", "-128", "127"), entero("u8", "0", "255")],
+            rustc: String::from("rustc 1.95.0"),
+            ..Medicion::default()
+        };
+        let tabla = tabla_rangos(&medicion, &["u8"], "Rangos", "tabla-prueba");
+        assert!(tabla.contains("255"), "{tabla}");
+        assert!(!tabla.contains("-128"), "{tabla}");
+        assert!(tabla.contains("rustc 1.95.0"), "{tabla}");
+        assert!(tabla.contains("<tabla-prueba>"), "{tabla}");
+    }
+}
@@ -0,0 +1,339 @@
+//! Escenarios de los ejemplos de entrada y salida.
+//!
+//! Un fragmento como `File::open("hola.txt")?` tiene dos caminos: el archivo
+//! existe o no. Ejecutado a secas solo se ve el segundo. Un fragmento marcado
+//! con `escenario=<nombre>` (uno por escenario) se ejecuta en cada uno; cada
+//! escenario se declara en `herramientas/escenarios/<nombre>.json`:
+//!
+//! ```json
+//! {
+//!   "rama": "Ok",
+//!   "archivos": { "hola.txt": "¡Hola!\n" },
+//!   "entrada": "texto que teclea el lector\n",
+//!   "entorno": { "IDIOMA": "es" },
+//!   "args": ["--verbose"],
+//!   "salida": "Contenido: ¡Hola!\n",
+//!   "codigo": 0
+//! }
+//! ```
+//!
+//! El `main` que llama a la función del fragmento y los `use` que el libro
+//! omite van en líneas ocultas (`# `, [`crate::fragmentos`]). Cada escenario
+//! corre en un directorio vacío con sus `archivos`, su `entrada` estándar,
+//! sus variables de `entorno` y sus `args`; solo `rama` y `salida` son
+//! obligatorios. La salida estándar y el código de salida (`0` por defecto)
+//! deben coincidir, y cada fragmento tiene que tener al menos un escenario de
+//! la rama `Ok` y otro de la `Err`. Un escenario es de un solo fragmento, y
+//! su nombre solo lleva letras, cifras, `-` y `_`: un `escenario` sin nombre
+//! o con otro carácter es un error.
+//!
+//! Lo que imprime cada escenario (salida estándar y de error) queda en
+//! `build/generado/escenarios/<nombre>.typ`.
+
+use std::fs;
+
+use crate::aislado::{self, Limites, Terminacion};
+use crate::fragmentos::Fragmento;
+use crate::json::{self, Valor};
+use crate::rustc::{self, DirTemporal};
+use crate::{Error, Libro, Resultado, generado, typst};
+
+/// Directorio (relativo a la raíz) con las declaraciones de escenarios.
+pub const DIRECTORIO: &str = "herramientas/escenarios";
+
+/// Atributo de los fragmentos con escenarios.
+pub const ATRIBUTO: &str = "escenario";
+
+/// Las dos ramas de un `Result` que debe recorrer un ejemplo.
+pub const RAMAS: &[&str] = &["Ok", "Err"];
+
+/// Un escenario de ejecución.
+#[derive(Debug, Clone, Default)]
+pub struct Escenario {
+    pub nombre: String,
+    /// `Ok` o `Err`.
+    pub rama: String,
+    /// Archivos `(ruta, contenido)` en el directorio de trabajo.
+    pub archivos: Vec<(String, String)>,
+    pub entrada: Option<String>,
+    pub entorno: Vec<(String, String)>,
+    pub args: Vec<String>,
+    pub salida: String,
+    pub codigo: i32,
+}
+
+fn texto(valor: &Valor, campo: &str) -> Option<String> {
+    valor.campo(campo).and_then(Valor::cadena).map(String::from)
+}
+
+fn textos(valor: &Valor, campo: &str) -> Vec<(String, String)> {
+    valor
+        .campo(campo)
+        .map(Valor::pares)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|(k, v)| Some((k.clone(), v.cadena()?.to_string())))
+        .collect()
+}
+
+impl Escenario {
+    /// Lee `herramientas/escenarios/<nombre>.json`.
+    pub fn leer(libro: &Libro, nombre: &str) -> Resultado<Escenario> {
+        let ruta = format!("{DIRECTORIO}/{nombre}.json");
+        let falta = |campo: &str| Error::Libro(format!("{ruta}: falta `{campo}`"));
+        let contenido = fs::read_to_string(libro.raiz.join(&ruta))
+            .map_err(|e| Error::Libro(format!("{ruta}: {e}")))?;
+        let valor = json::parsear(&contenido)?;
+        let rama = texto(&valor, "rama").ok_or_else(|| falta("rama"))?;
+        if !RAMAS.contains(&rama.as_str()) {
+            return Err(Error::Libro(format!(
+                "{ruta}: rama desconocida `{rama}` (usa {})",
+                RAMAS.join(" o ")
+            )));
+        }
+        Ok(Escenario {
+            nombre: nombre.to_string(),
+            rama,
+            archivos: textos(&valor, "archivos"),
+            entrada: texto(&valor, "entrada"),
+            entorno: textos(&valor, "entorno"),
+            args: valor
+                .campo("args")
+                .map(Valor::lista)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|a| a.cadena().map(String::from))
+                .collect(),
+            salida: texto(&valor, "salida").ok_or_else(|| falta("salida"))?,
+            codigo: valor.campo("codigo").and_then(Valor::numero).unwrap_or(0.0) as i32,
+        })
+    }
+}
+
+/// Si `nombre` sirve como nombre de escenario (y de archivo).
+fn nombre_valido(nombre: &str) -> bool {
+    !nombre.is_empty()
+        && nombre
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Los escenarios de `fragmento`, en el orden de sus atributos `escenario=`.
+pub fn de(libro: &Libro, fragmento: &Fragmento) -> Resultado<Vec<Escenario>> {
+    let mut escenarios = Vec::new();
+    for nombre in fragmento.atributos.valores(ATRIBUTO) {
+        let Some(nombre) = nombre.filter(|n| nombre_valido(n)) else {
+            return Err(Error::Libro(format!(
+                "{}: `{ATRIBUTO}{}` no nombra un escenario; usa `{ATRIBUTO}=<nombre>` \
+                 (letras, cifras, `-` y `_`) con el escenario en {DIRECTORIO}/<nombre>.json",
+                fragmento.ubicacion(),
+                nombre.map(|n| format!("={n}")).unwrap_or_default()
+            )));
+        };
+        escenarios.push(Escenario::leer(libro, nombre)?);
+    }
+    for rama in RAMAS {
+        if !escenarios.iter().any(|e| e.rama == *rama) {
+            return Err(Error::Libro(format!(
+                "{}: falta un escenario de la rama `{rama}`",
+                fragmento.ubicacion()
+            )));
+        }
+    }
+    Ok(escenarios)
+}
+
+/// Ejecuta `programa` en `escenario`: devuelve stdout, stderr y el código
+/// de salida (`None` si lo terminó una señal o el tiempo máximo).
+pub fn ejecutar(
+    programa: &rustc::Programa,
+    escenario: &Escenario,
+) -> Resultado<(String, String, Option<i32>)> {
+    let dir = DirTemporal::nuevo("escenario")?;
+    for (ruta, contenido) in &escenario.archivos {
+        let destino = dir.ruta().join(ruta);
+        if let Some(padre) = destino.parent() {
+            fs::create_dir_all(padre)?;
+        }
+        fs::write(destino, contenido)?;
+    }
+    let mut orden = programa.orden();
+    orden
+        .current_dir(dir.ruta())
+        .args(&escenario.args)
+        .envs(escenario.entorno.iter().map(|(k, v)| (k, v)));
+    let informe = aislado::ejecutar_con_entrada(
+        &mut orden,
+        &Limites::default(),
+        escenario.entrada.as_deref(),
+    )?;
+    let codigo = match informe.terminacion {
+        Terminacion::Codigo(codigo) => Some(codigo),
+        _ => None,
+    };
+    Ok((informe.stdout, informe.stderr, codigo))
+}
+
+/// Ejecuta los escenarios de cada fragmento marcado con `escenario=` y
+/// escribe su salida en `build/generado/escenarios/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let fragmentos: Vec<Fragmento> = libro
+        .fragmentos()?
+        .into_iter()
+        .filter(|f| f.atributos.tiene(ATRIBUTO))
+        .collect();
+    let mut fallos = Vec::new();
+    let mut escritos = 0;
+    let mut usados: Vec<(String, String)> = Vec::new();
+    for fragmento in &fragmentos {
+        let escenarios = de(libro, fragmento)?;
+        let programa = rustc::compilar(&fragmento.programa())
+            .map_err(|e| Error::Libro(format!("{}: {e}", fragmento.ubicacion())))?;
+        for escenario in &escenarios {
+            if let Some((_, otro)) = usados.iter().find(|(n, _)| *n == escenario.nombre) {
+                return Err(Error::Libro(format!(
+                    "{}: el escenario `{}` ya es de {otro}",
+                    fragmento.ubicacion(),
+                    escenario.nombre
+                )));
+            }
+            usados.push((escenario.nombre.clone(), fragmento.ubicacion()));
+            let (stdout, stderr, codigo) = ejecutar(&programa, escenario)?;
+            if stdout != escenario.salida || codigo != Some(escenario.codigo) {
+                fallos.push(format!(
+                    "  {} (escenario `{}`, rama {}):\n    se esperaba {:?} (código {})\n    \
+                     salió {:?} (código {}){}",
+                    fragmento.ubicacion(),
+                    escenario.nombre,
+                    escenario.rama,
+                    escenario.salida,
+                    escenario.codigo,
+                    stdout,
+                    codigo.map_or(String::from("ninguno"), |c| c.to_string()),
+                    if stderr.is_empty() {
+                        String::new()
+                    } else {
+                        format!("\n    stderr: {}", stderr.trim().replace('\n', "\n      "))
+                    }
+                ));
+                continue;
+            }
+            let contenido = format!(
+                "{}#raw({}, block: true, lang: \"yaml\")\n",
+                generado::CABECERA,
+                typst::cadena(format!("{stdout}{stderr}").trim_end())
+            );
+            generado::escribir(
+                libro,
+                format!("escenarios/{}.typ", escenario.nombre),
+                &contenido,
+            )?;
+            escritos += 1;
+        }
+    }
+
+    // Un escenario sin fragmento es de un ejemplo que se borró o cambió.
+    let directorio = libro.raiz.join(DIRECTORIO);
+    if directorio.is_dir() {
+        for entrada in fs::read_dir(&directorio)? {
+            let ruta = entrada?.path();
+            let Some(nombre) = ruta.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if !usados.iter().any(|(n, _)| n == nombre) {
+                fallos.push(format!(
+                    "  {DIRECTORIO}/{nombre}.json: ningún fragmento tiene `{ATRIBUTO}={nombre}`"
+                ));
+            }
+        }
+    }
+    if !fallos.is_empty() {
+        return Err(Error::Libro(format!(
+            "escenarios que no se cumplen:\n{}",
+            fallos.join("\n")
+        )));
+    }
+    Ok(escritos)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::fragmentos::extraer;
+
+    const PROGRAMA: &str = "use std::fs;\n\
+        fn main() {\n    \
+            match fs::read_to_string(\"hola.txt\") {\n        \
+                Ok(texto) => print!(\"Contenido: {texto}\"),\n        \
+                Err(_) => { println!(\"no está\"); std::process::exit(1) }\n    \
+            }\n\
+        }\n";
+
+    fn fragmento(marca: &str) -> Fragmento {
+        let texto = format!("// libro: {marca}\n```rust\n{PROGRAMA}```\n");
+        extraer(Path::new("prueba.typ"), &texto).remove(0)
+    }
+
+    fn libro_con(escenarios: &[(&str, &str)]) -> (DirTemporal, Libro) {
+        let dir = DirTemporal::nuevo("escenarios").unwrap();
+        fs::create_dir_all(dir.ruta().join(DIRECTORIO)).unwrap();
+        for (nombre, json) in escenarios {
+            fs::write(
+                dir.ruta().join(DIRECTORIO).join(format!("{nombre}.json")),
+                json,
+            )
+            .unwrap();
+        }
+        let libro = Libro {
+            raiz: dir.ruta().to_path_buf(),
+            bless: false,
+        };
+        (dir, libro)
+    }
+
+    #[test]
+    fn ejecuta_cada_rama_con_sus_archivos() {
+        let (_dir, libro) = libro_con(&[
+            (
+                "existe",
+                r#"{"rama": "Ok", "archivos": {"hola.txt": "¡Hola!\n"}, "salida": "Contenido: ¡Hola!\n"}"#,
+            ),
+            (
+                "falta",
+                r#"{"rama": "Err", "salida": "no está\n", "codigo": 1}"#,
+            ),
+        ]);
+        let fragmento = fragmento("escenario=existe, escenario=falta");
+        let escenarios = de(&libro, &fragmento).unwrap();
+        assert_eq!(escenarios.len(), 2);
+        let programa = rustc::compilar(&fragmento.programa()).unwrap();
+        for escenario in &escenarios {
+            let (stdout, _, codigo) = ejecutar(&programa, escenario).unwrap();
+            assert_eq!(stdout, escenario.salida);
+            assert_eq!(codigo, Some(escenario.codigo));
+        }
+    }
+
+    #[test]
+    fn atributo_sin_nombre_valido_es_un_error() {
+        let (_dir, libro) = libro_con(&[]);
+        for marca in ["escenario", "escenario=", "escenario=a/b"] {
+            let Err(Error::Libro(mensaje)) = de(&libro, &fragmento(marca)) else {
+                panic!("`{marca}` debería ser un error");
+            };
+            assert!(mensaje.contains("prueba.typ"), "{mensaje}");
+            assert!(mensaje.contains("escenario=<nombre>"), "{mensaje}");
+        }
+    }
+
+    #[test]
+    fn falta_una_rama() {
+        let (_dir, libro) = libro_con(&[("solo", r#"{"rama": "Ok", "salida": ""}"#)]);
+        let Err(Error::Libro(mensaje)) = de(&libro, &fragmento("escenario=solo")) else {
+            panic!("sin escenario `Err` debería ser un error");
+        };
+        assert!(mensaje.contains("rama `Err`"), "{mensaje}");
+    }
+}
@@ -0,0 +1,463 @@
+//! Estilo de los ejemplos: `clippy` y `rustfmt`.
+//!
+//! Un libro para quien empieza enseña también con la forma del código, así
+//! que cada fragmento que compila pasa por `clippy` y cada uno que se puede
+//! analizar, por `rustfmt`. `libro generar estilo` falla con cada aviso en
+//! la línea del capítulo donde está:
+//!
+//! ```text
+//! src/chapters/14-Manejo_Errores/sections/error_recu.typ:45: clippy::ptr_arg: writing `&Vec` ...
+//! src/chapters/04-Programa/index.typ:639: rustfmt: se esperaba `    let texto: ...`
+//! ```
+//!
+//! Además de `clippy::all`, cada capítulo activa los lints de
+//! `clippy::pedantic` que tienen sentido para lo que enseña ([`PEDANTES`]);
+//! unos pocos se permiten en todo el libro (`PERMITIDOS`).
+//! De `rustfmt` se ignora el espacio antes de un comentario final: el libro
+//! alinea con dos (`v.push(1);  // [1]`) lo que `rustfmt` deja en uno.
+//!
+//! Un ejemplo que es poco idiomático a propósito (para mostrar qué evitar, o
+//! un paso intermedio) se marca con `permitir`, o con `permitir=<lints>` para
+//! permitir solo algunos, separados por espacios:
+//!
+//! ```text
+//! // libro: permitir=ptr_arg rustfmt
+//! ```
+//!
+//! Los resultados se guardan en `build/cache/estilo.json` ([`crate::cache`]).
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::cache::{self, Cache};
+use crate::fragmentos::{Fragmento, sin_comentario};
+use crate::json::{self, Valor};
+use crate::rustc::{DirTemporal, EDICION};
+use crate::{Error, Libro, Resultado, lotes};
+
+/// Lints de `clippy::pedantic` que se activan en cada capítulo (por el
+/// prefijo de su carpeta).
+pub const PEDANTES: &[(&str, &[&str])] = &[
+    (
+        "src/chapters/05-Variables",
+        &["clippy::explicit_iter_loop", "clippy::manual_string_new"],
+    ),
+    (
+        "src/chapters/07-iteradores",
+        &[
+            "clippy::needless_for_each",
+            "clippy::filter_map_next",
+            "clippy::flat_map_option",
+            "clippy::from_iter_instead_of_collect",
+        ],
+    ),
+    (
+        "src/chapters/08-funciones",
+        &[
+            "clippy::needless_pass_by_value",
+            "clippy::must_use_candidate",
+        ],
+    ),
+    (
+        "src/chapters/12-traits",
+        &[
+            "clippy::return_self_not_must_use",
+            "clippy::semicolon_if_nothing_returned",
+        ],
+    ),
+    (
+        "src/chapters/14-Manejo_Errores",
+        &[
+            "clippy::unnecessary_wraps",
+            "clippy::map_unwrap_or",
+            "clippy::redundant_closure_for_method_calls",
+        ],
+    ),
+];
+
+/// Lints que se permiten en todo el libro: los ejemplos usan `vec!` para
+/// enseñar vectores aunque bastara un array, y `3.14` como un número
+/// decimal cualquiera, no como π.
+const PERMITIDOS: &[&str] = &["clippy::useless_vec", "clippy::approx_constant"];
+
+/// Atributo que permite avisos en un fragmento.
+const PERMITIR: &str = "permitir";
+
+/// Columnas que caben en una línea de código del PDF; `rustfmt` usa 100.
+pub const ANCHO: usize = 80;
+
+/// Nombre de los avisos de `rustfmt`.
+const RUSTFMT: &str = "rustfmt";
+
+/// Un aviso sobre un fragmento.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aviso {
+    /// Línea del programa (desde 1).
+    pub linea: usize,
+    /// `clippy::ptr_arg` o `rustfmt`.
+    pub regla: String,
+    pub mensaje: String,
+}
+
+impl Aviso {
+    fn valor(&self) -> Valor {
+        Valor::Objeto(vec![
+            (String::from("linea"), Valor::Numero(self.linea as f64)),
+            (String::from("regla"), Valor::Cadena(self.regla.clone())),
+            (String::from("mensaje"), Valor::Cadena(self.mensaje.clone())),
+        ])
+    }
+
+    fn de(valor: &Valor) -> Option<Aviso> {
+        Some(Aviso {
+            linea: valor.campo("linea")?.numero()? as usize,
+            regla: valor.campo("regla")?.cadena()?.to_string(),
+            mensaje: valor.campo("mensaje")?.cadena()?.to_string(),
+        })
+    }
+}
+
+/// Lints que se activan para `fragmento`.
+fn lints(fragmento: &Fragmento) -> Vec<&'static str> {
+    let mut lints = vec!["clippy::all"];
+    for (carpeta, pedantes) in PEDANTES {
+        if fragmento.archivo.starts_with(carpeta) {
+            lints.extend(*pedantes);
+        }
+    }
+    lints
+}
+
+/// El programa de `clippy`: `$CLIPPY_DRIVER` o `clippy-driver` del `PATH`.
+fn clippy_driver() -> PathBuf {
+    std::env::var_os("CLIPPY_DRIVER")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("clippy-driver"))
+}
+
+fn version(programa: impl AsRef<std::ffi::OsStr>) -> Resultado<String> {
+    let salida = Command::new(programa).arg("-V").output()?;
+    if !salida.status.success() {
+        return Err(Error::Ejecucion(
+            String::from_utf8_lossy(&salida.stderr).into_owned(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&salida.stdout).trim().to_string())
+}
+
+/// Avisos de `clippy` sobre `programa`. Ninguno si no compila.
+fn clippy(programa: &str, edicion: &str, lints: &[&str]) -> Resultado<Vec<Aviso>> {
+    let dir = DirTemporal::nuevo("clippy")?;
+    std::fs::write(dir.ruta().join("main.rs"), programa)?;
+    let mut orden = Command::new(clippy_driver());
+    orden
+        .current_dir(dir.ruta())
+        .args([
+            "--edition",
+            edicion,
+            "--crate-type",
+            "bin",
+            "--emit=metadata",
+        ])
+        .args(["-o", "programa", "--error-format=json", "-A", "unused"]);
+    for lint in lints {
+        orden.args(["-W", lint]);
+    }
+    for lint in PERMITIDOS {
+        orden.args(["-A", lint]);
+    }
+    let salida = orden.arg("main.rs").output()?;
+    if !salida.status.success() {
+        return Ok(Vec::new());
+    }
+    let mut avisos = Vec::new();
+    for linea in String::from_utf8_lossy(&salida.stderr).lines() {
+        let diagnostico = json::parsear(linea)?;
+        let Some(regla) = diagnostico
+            .campo("code")
+            .and_then(|c| c.campo("code"))
+            .and_then(Valor::cadena)
+            .filter(|c| c.starts_with("clippy::"))
+        else {
+            continue;
+        };
+        let linea = diagnostico
+            .campo("spans")
+            .map(Valor::lista)
+            .unwrap_or_default()
+            .iter()
+            .find(|s| matches!(s.campo("is_primary"), Some(Valor::Bool(true))))
+            .and_then(|s| s.campo("line_start")?.numero())
+            .unwrap_or(1.0) as usize;
+        avisos.push(Aviso {
+            linea,
+            regla: regla.to_string(),
+            mensaje: diagnostico
+                .campo("message")
+                .and_then(Valor::cadena)
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+    Ok(avisos)
+}
+
+/// La línea sin los espacios antes de su comentario final, ni la sangría
+/// si es solo un comentario (`rustfmt` la alinea con el comentario de la
+/// línea anterior).
+fn normalizar(linea: &str) -> String {
+    let antes = sin_comentario(linea);
+    if antes.len() == linea.len() {
+        return linea.trim_end().to_string();
+    }
+    if antes.trim().is_empty() {
+        return linea.trim().to_string();
+    }
+    format!("{} {}", antes.trim_end(), linea[antes.len()..].trim_end())
+}
+
+/// Cambios para pasar de `original` a `formateado`, por tramos: la línea
+/// de `original` (desde 1) donde empieza cada uno y qué hay que hacer.
+fn diferencias(original: &[String], formateado: &[String]) -> Vec<(usize, String)> {
+    // Subsecuencia común más larga desde (i, j).
+    let (n, m) = (original.len(), formateado.len());
+    let mut comun = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            comun[i][j] = if original[i] == formateado[j] {
+                comun[i + 1][j + 1] + 1
+            } else {
+                comun[i + 1][j].max(comun[i][j + 1])
+            };
+        }
+    }
+    let mut tramos = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && original[i] == formateado[j] {
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let (inicio, mut sobran, mut faltan) = (i, 0, Vec::new());
+        while i < n || j < m {
+            if i < n && j < m && original[i] == formateado[j] {
+                break;
+            }
+            if j == m || (i < n && comun[i + 1][j] >= comun[i][j + 1]) {
+                sobran += 1;
+                i += 1;
+            } else {
+                faltan.push(formateado[j].trim());
+                j += 1;
+            }
+        }
+        let mensaje = match (sobran, faltan.as_slice()) {
+            (1, []) => String::from("sobra esta línea"),
+            (_, []) => format!("sobran {sobran} líneas"),
+            (0, [linea]) => format!("falta `{linea}` antes de esta línea"),
+            (_, [linea]) => format!("se esperaba `{linea}`"),
+            (_, [linea, resto @ ..]) => {
+                format!("se esperaba `{linea}` (y {} líneas más)", resto.len())
+            }
+        };
+        tramos.push((inicio.min(n.saturating_sub(1)) + 1, mensaje));
+    }
+    tramos
+}
+
+/// Avisos de `rustfmt` sobre `programa`, uno por tramo que cambia al
+/// formatearlo. Ninguno si no se puede analizar.
+fn rustfmt(programa: &str, edicion: &str) -> Resultado<Vec<Aviso>> {
+    let mut hijo = Command::new("rustfmt")
+        .args(["--edition", edicion, "--emit", "stdout"])
+        .args(["--config", &format!("max_width={ANCHO}")])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut entrada) = hijo.stdin.take() {
+        entrada.write_all(programa.as_bytes())?;
+    }
+    let salida = hijo.wait_with_output()?;
+    if !salida.status.success() {
+        return Ok(Vec::new());
+    }
+    let formateado = String::from_utf8_lossy(&salida.stdout);
+    let original: Vec<String> = programa.trim_end().lines().map(normalizar).collect();
+    let formateado: Vec<String> = formateado.trim_end().lines().map(normalizar).collect();
+    Ok(diferencias(&original, &formateado)
+        .into_iter()
+        .map(|(linea, mensaje)| Aviso {
+            linea,
+            regla: String::from(RUSTFMT),
+            mensaje,
+        })
+        .collect())
+}
+
+/// Si `aviso` está permitido en `fragmento`.
+fn permitido(fragmento: &Fragmento, aviso: &Aviso) -> bool {
+    if !fragmento.atributos.tiene(PERMITIR) {
+        return false;
+    }
+    let Some(permitidos) = fragmento.atributos.valor(PERMITIR) else {
+        return true;
+    };
+    let regla = aviso.regla.strip_prefix("clippy::").unwrap_or(&aviso.regla);
+    permitidos
+        .split_whitespace()
+        .any(|p| p.strip_prefix("clippy::").unwrap_or(p) == regla)
+}
+
+/// Línea del capítulo de la línea `linea` del programa de `fragmento`.
+fn linea_en_libro(fragmento: &Fragmento, linea: usize) -> usize {
+    let envuelto = fragmento.programa() != fragmento.codigo;
+    let linea = if envuelto {
+        linea.saturating_sub(1)
+    } else {
+        linea
+    };
+    let lineas = fragmento.codigo.lines().count();
+    fragmento.linea + linea.clamp(1, lineas.max(1))
+}
+
+/// Pasa `clippy` y `rustfmt` por todos los fragmentos. Devuelve cuántos se
+/// revisaron y falla con los avisos que no están permitidos.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let versiones = [version(clippy_driver())?, version("rustfmt")?];
+    let mut cache = Cache::leer(libro, "estilo");
+    let fragmentos: Vec<Fragmento> = libro
+        .fragmentos()?
+        .into_iter()
+        .filter(|f| f.compilable() && !f.atributos.tiene("no_compila"))
+        .collect();
+
+    let mut revisiones = Vec::new();
+    for fragmento in &fragmentos {
+        let programa = fragmento.programa();
+        let edicion = fragmento.atributos.valor("edicion").unwrap_or(EDICION);
+        let lints = lints(fragmento);
+        let mut partes = vec![versiones[0].as_str(), &versiones[1], edicion, &programa];
+        partes.extend(&lints);
+        partes.extend(PERMITIDOS);
+        let huella = cache::huella(&partes);
+        let guardados = cache
+            .obtener(&huella)
+            .map(|v| v.lista().iter().map(Aviso::de).collect::<Option<Vec<_>>>());
+        revisiones.push((
+            fragmento,
+            programa,
+            edicion,
+            lints,
+            huella,
+            guardados.flatten(),
+        ));
+    }
+
+    let avisos = lotes::en_paralelo(
+        &revisiones,
+        |(_, programa, edicion, lints, _, guardados)| {
+            if let Some(guardados) = guardados {
+                return Ok(guardados.clone());
+            }
+            let mut avisos = clippy(programa, edicion, lints)?;
+            avisos.extend(rustfmt(programa, edicion)?);
+            Ok(avisos)
+        },
+    )?;
+
+    let mut fallos = Vec::new();
+    for ((fragmento, _, _, _, huella, _), avisos) in revisiones.iter().zip(avisos) {
+        cache.guardar(
+            huella.clone(),
+            Valor::Lista(avisos.iter().map(Aviso::valor).collect()),
+        );
+        for aviso in avisos.iter().filter(|a| !permitido(fragmento, a)) {
+            fallos.push(format!(
+                "  {}:{}: {}: {}",
+                fragmento.archivo.display(),
+                linea_en_libro(fragmento, aviso.linea),
+                aviso.regla,
+                aviso.mensaje
+            ));
+        }
+    }
+    cache.escribir(libro)?;
+    if !fallos.is_empty() {
+        return Err(Error::Libro(format!(
+            "ejemplos que no siguen clippy o rustfmt (márcalos con `{PERMITIR}` si es a propósito):\n{}",
+            fallos.join("\n")
+        )));
+    }
+    Ok(revisiones.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lineas(texto: &str) -> Vec<String> {
+        texto.lines().map(String::from).collect()
+    }
+
+    fn tramos(original: &str, formateado: &str) -> Vec<(usize, String)> {
+        diferencias(&lineas(original), &lineas(formateado))
+    }
+
+    #[test]
+    fn sin_cambios() {
+        assert_eq!(tramos("a\nb\n", "a\nb\n"), []);
+    }
+
+    #[test]
+    fn una_linea_cambiada() {
+        assert_eq!(
+            tramos("fn f() {\nlet x=1;\n}", "fn f() {\n    let x = 1;\n}"),
+            [(2, String::from("se esperaba `let x = 1;`"))]
+        );
+    }
+
+    #[test]
+    fn lineas_que_sobran_y_que_faltan() {
+        assert_eq!(
+            tramos("a\n\n\nb", "a\nb"),
+            [(2, String::from("sobran 2 líneas"))]
+        );
+        assert_eq!(
+            tramos("a\n\nb", "a\nb"),
+            [(2, String::from("sobra esta línea"))]
+        );
+        assert_eq!(
+            tramos("a\nc", "a\nb\nc"),
+            [(2, String::from("falta `b` antes de esta línea"))]
+        );
+    }
+
+    #[test]
+    fn una_linea_partida_en_varias() {
+        assert_eq!(
+            tramos(
+                "f(uno, dos, tres);\nfin",
+                "f(\n    uno,\n    dos,\n    tres,\n);\nfin"
+            ),
+            [(1, String::from("se esperaba `f(` (y 4 líneas más)"))]
+        );
+    }
+
+    #[test]
+    fn al_final_se_marca_la_ultima_linea() {
+        assert_eq!(
+            tramos("a\nb", "a\nb\nc"),
+            [(2, String::from("falta `c` antes de esta línea"))]
+        );
+    }
+
+    #[test]
+    fn normalizar_ignora_la_alineacion_de_comentarios() {
+        assert_eq!(normalizar("let x = 1;      // uno"), "let x = 1; // uno");
+        assert_eq!(normalizar("                // sigue"), "// sigue");
+        assert_eq!(normalizar("    let y = 2;  "), "    let y = 2;");
+    }
+}
@@ -0,0 +1,297 @@
+//! Lo que produce cada etapa del compilador para un «hola mundo».
+//!
+//! El fragmento marcado con `etapas` se compila con
+//! `--emit=mir,llvm-ir,asm,link` y se recortan los extractos que muestra el
+//! capítulo junto al diagrama de fases (`src/utils/diagrama_llvm.typ`):
+//!
+//! - Expansión y resolución: `-Zunpretty=expanded` y `-Zunpretty=hir`, como
+//!   hace `cargo expand`. Son opciones inestables, así que se habilitan con
+//!   `RUSTC_BOOTSTRAP=1` solo para esas dos llamadas.
+//! - MIR, LLVM IR y ensamblador: la función `main` del programa.
+//! - Símbolos: los de `nm` relacionados con `main` y `println!`.
+//!
+//! El análisis léxico, el sintáctico y la verificación de tipos no dejan un
+//! artefacto que `rustc` estable pueda escribir; el capítulo lo explica.
+
+use std::fs;
+use std::process::Command;
+
+use crate::aislado::{self, Limites};
+use crate::fragmentos::Fragmento;
+use crate::rustc::{self, DirTemporal, EDICION};
+use crate::{Error, Libro, Resultado, generado, typst};
+
+/// Los artefactos recortados de una compilación.
+#[derive(Debug, Default)]
+pub struct Artefactos {
+    pub expandido: String,
+    pub hir: String,
+    pub mir: String,
+    pub llvm_ir: String,
+    pub asm: String,
+    /// (dirección, tipo, símbolo, símbolo sin decorar)
+    pub simbolos: Vec<[String; 4]>,
+    /// Lo que imprime el programa al ejecutarse.
+    pub salida: String,
+    pub rustc: String,
+}
+
+fn orden(dir: &DirTemporal, args: &[&str], bootstrap: bool) -> Resultado<String> {
+    let mut orden = Command::new(rustc::rustc());
+    orden
+        .args(["--edition", EDICION])
+        .args(args)
+        .current_dir(dir.ruta());
+    if bootstrap {
+        orden.env("RUSTC_BOOTSTRAP", "1");
+    }
+    let salida = orden.output()?;
+    if !salida.status.success() {
+        return Err(Error::Compilacion(
+            String::from_utf8_lossy(&salida.stderr).into_owned(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&salida.stdout).into_owned())
+}
+
+/// Líneas desde la primera que cumple `inicio` hasta la primera (incluida)
+/// que cumple `fin`.
+fn tramo(texto: &str, inicio: impl Fn(&str) -> bool, fin: impl Fn(&str) -> bool) -> String {
+    let mut lineas = Vec::new();
+    for linea in texto.lines().skip_while(|l| !inicio(l)) {
+        lineas.push(linea);
+        if lineas.len() > 1 && fin(linea) {
+            break;
+        }
+    }
+    lineas.join("\n")
+}
+
+/// Símbolos de `nm` que interesan en el capítulo: `main` (el que llama el
+/// sistema), la función `main` del programa y la función que usa `println!`.
+fn simbolos(dir: &DirTemporal) -> Resultado<Vec<[String; 4]>> {
+    let nm = |demangle: bool| -> Resultado<String> {
+        let mut orden = Command::new("nm");
+        if demangle {
+            orden.arg("-C");
+        }
+        let salida = orden.arg("programa").current_dir(dir.ruta()).output()?;
+        if !salida.status.success() {
+            return Err(Error::Ejecucion(
+                String::from_utf8_lossy(&salida.stderr).into_owned(),
+            ));
+        }
+        Ok(String::from_utf8_lossy(&salida.stdout).into_owned())
+    };
+    let (decorados, legibles) = (nm(false)?, nm(true)?);
+    let mut simbolos = Vec::new();
+    for (decorado, legible) in decorados.lines().zip(legibles.lines()) {
+        let partes: Vec<&str> = decorado.split_whitespace().collect();
+        let (direccion, tipo, nombre) = match partes[..] {
+            [direccion, tipo, nombre] => (direccion, tipo, nombre),
+            [tipo, nombre] => ("", tipo, nombre),
+            _ => continue,
+        };
+        let interesa = nombre == "main"
+            || nombre == "__libc_start_main"
+            || nombre.starts_with("_ZN4main4main")
+            || nombre.ends_with("2io5stdio6__print");
+        if interesa {
+            // Las dos salidas tienen las mismas columnas antes del nombre.
+            let legible = legible
+                .get(decorado.len() - nombre.len()..)
+                .unwrap_or(nombre);
+            simbolos.push([
+                direccion.trim_start_matches('0').to_string(),
+                tipo.to_string(),
+                nombre.to_string(),
+                legible.to_string(),
+            ]);
+        }
+    }
+    Ok(simbolos)
+}
+
+/// Compila el fragmento y extrae los artefactos de cada etapa.
+pub fn extraer(fragmento: &Fragmento) -> Resultado<Artefactos> {
+    let dir = DirTemporal::nuevo("etapas")?;
+    fs::write(dir.ruta().join("main.rs"), fragmento.programa())?;
+    let leer = |nombre: &str| fs::read_to_string(dir.ruta().join(nombre));
+
+    orden(
+        &dir,
+        &[
+            "--emit=mir,llvm-ir,asm,link",
+            "-C",
+            "codegen-units=1",
+            "-o",
+            "programa",
+            "main.rs",
+        ],
+        false,
+    )?;
+    let ejecucion = aislado::ejecutar(
+        Command::new(dir.ruta().join("programa")).current_dir(dir.ruta()),
+        &Limites::default(),
+    )?;
+    if !ejecucion.exito() {
+        return Err(Error::Ejecucion(ejecucion.stderr));
+    }
+
+    let mir = leer("programa.mir")?;
+    let llvm_ir = leer("programa.ll")?;
+    let asm = leer("programa.s")?;
+    Ok(Artefactos {
+        expandido: orden(&dir, &["-Zunpretty=expanded", "main.rs"], true)?,
+        hir: orden(&dir, &["-Zunpretty=hir", "main.rs"], true)?,
+        // Sin el aviso inicial de que el formato puede cambiar.
+        mir: mir
+            .lines()
+            .skip_while(|l| l.starts_with("//"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        llvm_ir: tramo(
+            &llvm_ir,
+            |l| l.starts_with("define") && l.contains("@_ZN4main4main"),
+            |l| l == "}",
+        ),
+        // Sin las directivas `.cfi_*`, que solo sirven al depurador.
+        asm: tramo(
+            &asm,
+            |l| l.starts_with("_ZN4main4main"),
+            |l| l.starts_with(".Lfunc_end"),
+        )
+        .lines()
+        .filter(|l| !l.trim_start().starts_with(".cfi_"))
+        .collect::<Vec<_>>()
+        .join("\n"),
+        simbolos: simbolos(&dir)?,
+        salida: ejecucion.stdout,
+        rustc: rustc::version()?,
+    })
+}
+
+fn listado(codigo: &str, lenguaje: &str, leyenda: &str, etiqueta: &str) -> String {
+    typst::figura(
+        &format!(
+            "raw({}, block: true, lang: {})",
+            typst::cadena(codigo.trim_end()),
+            typst::cadena(lenguaje)
+        ),
+        leyenda,
+        Some(etiqueta),
+    )
+}
+
+/// Genera los extractos de `build/generado/etapas/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let fragmentos: Vec<Fragmento> = libro
+        .fragmentos()?
+        .into_iter()
+        .filter(|f| f.atributos.tiene("etapas"))
+        .collect();
+    let [fragmento] = &fragmentos[..] else {
+        return Err(Error::Libro(format!(
+            "se esperaba un único fragmento con `etapas`, hay {}",
+            fragmentos.len()
+        )));
+    };
+    let a = extraer(fragmento)?;
+    let con = format!("#{}", typst::codigo(&a.rustc));
+
+    let encabezados = [
+        "[*Dirección*]",
+        "[*Tipo*]",
+        "[*Símbolo*]",
+        "[*Nombre en Rust*]",
+    ]
+    .map(String::from);
+    let filas: Vec<Vec<String>> = a
+        .simbolos
+        .iter()
+        .map(|s| {
+            let celda = |t: &str| {
+                if t.is_empty() {
+                    String::from("[]")
+                } else {
+                    format!("[#{}]", typst::codigo(t))
+                }
+            };
+            s.iter().map(|t| celda(t)).collect()
+        })
+        .collect();
+
+    let archivos = [
+        (
+            "expansion",
+            listado(
+                &a.expandido,
+                "rust",
+                &format!("[`println!` después de expandir las macros ({con})]"),
+                "etapa-expansion",
+            ),
+        ),
+        (
+            "hir",
+            listado(
+                &a.hir,
+                "rust",
+                &format!("[El programa en HIR, con las rutas ya resueltas ({con})]"),
+                "etapa-hir",
+            ),
+        ),
+        (
+            "mir",
+            listado(
+                &a.mir,
+                "rust",
+                &format!("[MIR de `main`: bloques básicos y sus saltos ({con})]"),
+                "etapa-mir",
+            ),
+        ),
+        (
+            "llvm-ir",
+            listado(
+                &a.llvm_ir,
+                "llvm",
+                &format!("[LLVM IR de `main` ({con})]"),
+                "etapa-llvm-ir",
+            ),
+        ),
+        (
+            "asm",
+            listado(
+                &a.asm,
+                "asm",
+                &format!(
+                    "[Ensamblador de `main` para {} ({con})]",
+                    std::env::consts::ARCH
+                ),
+                "etapa-asm",
+            ),
+        ),
+        (
+            "simbolos",
+            format!(
+                "#show table: set text(size: 8pt)\n\n{}",
+                typst::figura(
+                    &typst::tabla(&encabezados, &filas),
+                    &format!("[Símbolos del ejecutable según `nm` ({con})]"),
+                    Some("etapa-simbolos"),
+                )
+            ),
+        ),
+        (
+            "salida",
+            format!(
+                "#raw({}, block: true, lang: \"yaml\")\n",
+                typst::cadena(a.salida.trim_end())
+            ),
+        ),
+    ];
+    for (nombre, contenido) in &archivos {
+        let contenido = format!("{}{contenido}", generado::CABECERA);
+        generado::escribir(libro, format!("etapas/{nombre}.typ"), &contenido)?;
+    }
+    Ok(archivos.len())
+}
@@ -0,0 +1,192 @@
+//! Tablas de valores especiales, redondeo y formato de punto flotante.
+//!
+//! `sondas/flotantes.rs` evalúa cada método sobre un conjunto fijo de
+//! entradas que incluye NaN, ±∞, `-0.0` y subnormales, así que los valores
+//! impresos en el capítulo (incluido `0.1 + 0.2`) son los que produce el
+//! `rustc` local.
+
+use crate::cache::Compilaciones;
+use crate::matriz::{self, Matriz};
+use crate::{Libro, Resultado, generado, typst};
+
+const SONDA: &str = include_str!("../sondas/flotantes.rs");
+
+/// Valores más largos que esto se abrevian en las tablas.
+const MAX_VALOR: usize = 24;
+
+#[derive(Debug, Default)]
+pub struct Medicion {
+    pub matrices: Vec<Matriz>,
+    pub rustc: String,
+}
+
+impl Medicion {
+    fn matriz(&self, nombre: &str) -> Resultado<&Matriz> {
+        matriz::buscar(&self.matrices, nombre, "flotantes")
+    }
+}
+
+/// Compila y ejecuta la sonda de punto flotante.
+pub fn medir(compilaciones: &mut Compilaciones) -> Resultado<Medicion> {
+    Ok(Medicion {
+        matrices: matriz::parsear(&compilaciones.ejecutar(SONDA, &[])?, "flotantes")?,
+        rustc: compilaciones.version().to_string(),
+    })
+}
+
+/// Acorta los números que `{}` imprime sin exponente, dejando las cifras
+/// significativas y cuántos ceros se omiten: `0.0…022250738585072014 (307 ceros)`.
+pub fn abreviar(valor: &str) -> String {
+    if valor.chars().count() <= MAX_VALOR {
+        return valor.to_string();
+    }
+    let (signo, cifras) = match valor.strip_prefix('-') {
+        Some(resto) => ("-", resto),
+        None => ("", valor),
+    };
+    if let Some(decimales) = cifras.strip_prefix("0.") {
+        let significativas = decimales.trim_start_matches('0');
+        let ceros = decimales.len() - significativas.len();
+        return format!("{signo}0.0…0{significativas} ({ceros} ceros)");
+    }
+    if cifras.bytes().all(|b| b.is_ascii_digit()) {
+        let significativas = cifras.trim_end_matches('0');
+        let ceros = cifras.len() - significativas.len();
+        return format!("{signo}{significativas}0…0 ({ceros} ceros)");
+    }
+    let corte: String = valor.chars().take(MAX_VALOR).collect();
+    format!("{corte}…")
+}
+
+fn medido_con(medicion: &Medicion) -> String {
+    format!("#{}", typst::codigo(&medicion.rustc))
+}
+
+fn celda_codigo(texto: &str) -> String {
+    format!("[#raw({}, lang: \"rust\")]", typst::cadena(texto))
+}
+
+/// Tabla con una fila por entrada y una columna por método.
+fn tabla(matriz: &Matriz, esquina: &str, encabezado: fn(&str) -> String) -> String {
+    let mut encabezados = vec![format!("[*{esquina}*]")];
+    encabezados.extend(matriz.columnas.iter().map(|c| encabezado(c)));
+    let filas: Vec<Vec<String>> = matriz
+        .filas
+        .iter()
+        .map(|fila| {
+            let mut celdas = vec![celda_codigo(fila)];
+            celdas.extend(
+                matriz
+                    .columnas
+                    .iter()
+                    .map(|c| celda_codigo(&abreviar(matriz.valor(fila, c).unwrap_or("")))),
+            );
+            celdas
+        })
+        .collect();
+    typst::tabla(&encabezados, &filas)
+}
+
+fn metodo(columna: &str) -> String {
+    format!(
+        "[#raw({}, lang: \"rust\")]",
+        typst::cadena(&format!(".{columna}()"))
+    )
+}
+
+fn formato(columna: &str) -> String {
+    format!(
+        "[#raw({}, lang: \"rust\")]",
+        typst::cadena(&format!("\"{columna}\""))
+    )
+}
+
+fn constante(columna: &str) -> String {
+    format!("[#raw({}, lang: \"\")]", typst::cadena(columna))
+}
+
+/// Tabla de características: una fila por constante, una columna por tipo.
+fn tabla_tipos(medicion: &Medicion) -> Resultado<String> {
+    let matriz = medicion.matriz("tipos")?;
+    let mut encabezados = vec![String::from("[*Constante*]")];
+    encabezados.extend(matriz.columnas.iter().map(|c| constante(c)));
+    let filas: Vec<Vec<String>> = matriz
+        .filas
+        .iter()
+        .map(|fila| {
+            let nombre = match fila.as_str() {
+                "Bits" => String::from("[Bits]"),
+                fila => celda_codigo(&format!("T::{fila}")),
+            };
+            let mut celdas = vec![nombre];
+            celdas.extend(
+                matriz
+                    .columnas
+                    .iter()
+                    .map(|c| celda_codigo(matriz.valor(fila, c).unwrap_or(""))),
+            );
+            celdas
+        })
+        .collect();
+    let leyenda = format!(
+        "[Características de `f32` y `f64` según IEEE 754. `T::from_bits(1)` es el \
+         menor subnormal positivo. Valores obtenidos con {}]",
+        medido_con(medicion)
+    );
+    Ok(typst::figura(
+        &typst::tabla(&encabezados, &filas),
+        &leyenda,
+        Some("tabla-floating-point"),
+    ))
+}
+
+/// Las tablas anchas usan letra más pequeña; la regla solo afecta al
+/// archivo incluido.
+fn compacta(figura: String) -> String {
+    format!("#show table: set text(size: 8pt)\n\n{figura}")
+}
+
+/// Genera las tablas de `build/generado/flotantes/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "flotantes")?;
+    let medicion = medir(&mut compilaciones)?;
+    compilaciones.escribir(libro)?;
+    let con = medido_con(&medicion);
+
+    let especiales = typst::figura(
+        &tabla(medicion.matriz("especiales")?, "Valor", metodo),
+        &format!("[Métodos de verificación sobre valores especiales de `f64` ({con})]"),
+        Some("tabla-flotantes-especiales"),
+    );
+    let redondeo = typst::figura(
+        &tabla(medicion.matriz("redondeo")?, "Valor", metodo),
+        &format!("[Redondeo y signo en `f64`, impresos con `{{:?}}` ({con})]"),
+        Some("tabla-flotantes-redondeo"),
+    );
+    let formatos = typst::figura(
+        &tabla(medicion.matriz("formato")?, "Valor", formato),
+        &format!(
+            "[El mismo `f64` con distintos especificadores de formato. Los valores largos \
+             se abrevian con «…» ({con})]"
+        ),
+        Some("tabla-flotantes-formato"),
+    );
+    let operaciones = typst::figura(
+        &tabla(medicion.matriz("operaciones")?, "Expresión", formato),
+        &format!("[Resultados de operaciones y conversiones con punto flotante ({con})]"),
+        Some("tabla-flotantes-operaciones"),
+    );
+
+    let archivos = [
+        ("tipos", tabla_tipos(&medicion)?),
+        ("especiales", compacta(especiales)),
+        ("redondeo", compacta(redondeo)),
+        ("formato", compacta(formatos)),
+        ("operaciones", operaciones),
+    ];
+    for (nombre, contenido) in &archivos {
+        let contenido = format!("{}{contenido}", generado::CABECERA);
+        generado::escribir(libro, format!("flotantes/{nombre}.typ"), &contenido)?;
+    }
+    Ok(archivos.len())
+}
@@ -0,0 +1,715 @@
+//! Extracción de los fragmentos ```rust escritos en los capítulos.
+//!
+//! Un fragmento puede llevar atributos en un comentario de Typst colocado
+//! antes del bloque (puede haber una llamada `#codly(...)` en medio):
+//!
+//! ```text
+//! // libro: id=stack, memoria
+//! ```
+//!
+//! Los atributos se separan por comas y pueden ser banderas (`memoria`) o
+//! pares clave-valor (`id=stack`).
+//!
+//! También pueden ir en la línea de apertura, como en `rustdoc`:
+//! ```` ```rust,no_run ````. Typst deja lo que sigue a `rust` como primera
+//! línea del código; la regla `show raw` de `src/config/global.typ` la quita
+//! antes de mostrar el bloque. Además de los de `// libro:`, se entienden:
+//!
+//! - `ignore`: no se compila ni se analiza.
+//! - `fragment`: un trozo de un programa mayor (`trait A: B { }`); no se
+//!   compila suelto.
+//! - `no_run`: se compila pero no se ejecuta.
+//! - `should_panic`: el programa debe terminar con un pánico.
+//! - `compile_fail[=E0382]`, `edition2024` y `project=<nombre>`, que
+//!   equivalen a `no_compila`, `edicion=2024` y `proyecto=`.
+//!
+//! Como en `rustdoc`, las líneas que empiezan por `# ` (o que son solo `#`)
+//! se compilan pero no se muestran: así un ejemplo que sigue a otro puede
+//! declarar lo que necesita sin repetirlo en el PDF.
+//!
+//! ```text
+//! # struct Punto { x: i32, y: i32 }
+//! let p = Punto { x: 1, y: 2 };
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Resultado;
+
+/// Directorios (relativos a la raíz) cuyos archivos forman el libro.
+///
+/// Los capítulos de colecciones usan la extensión `.rs` aunque son Typst.
+pub const DIRECTORIOS: &[&str] = &["src/chapters", "src/contents"];
+
+const MARCA: &str = "// libro:";
+
+/// Apertura de un bloque de Rust.
+const APERTURA: &str = "```rust";
+
+/// Prefijo de las líneas ocultas.
+const OCULTA: &str = "# ";
+
+/// Atributos de `rustdoc` con otro nombre en `// libro:`.
+const EQUIVALENTES: &[(&str, &str)] = &[("compile_fail", "no_compila"), ("project", "proyecto")];
+
+/// Atributos declarados para un fragmento.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Atributos {
+    pares: Vec<(String, Option<String>)>,
+}
+
+impl Atributos {
+    /// Interpreta una lista separada por comas: `id=stack, memoria`.
+    pub fn parsear(texto: &str) -> Atributos {
+        let pares = texto
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| match p.split_once('=') {
+                Some((k, v)) => (k.trim().to_string(), Some(v.trim().to_string())),
+                None => (p.to_string(), None),
+            })
+            .collect();
+        Atributos { pares }
+    }
+
+    /// Interpreta los atributos de la línea de apertura (`,no_run,edition2024`)
+    /// con los nombres de `// libro:`.
+    pub fn parsear_apertura(texto: &str) -> Atributos {
+        let mut atributos = Atributos::parsear(texto);
+        for (clave, valor) in &mut atributos.pares {
+            if let Some(edicion) = clave.strip_prefix("edition")
+                && valor.is_none()
+                && !edicion.is_empty()
+            {
+                *valor = Some(edicion.to_string());
+                *clave = String::from("edicion");
+            } else if let Some((_, libro)) = EQUIVALENTES.iter().find(|(r, _)| r == clave) {
+                *clave = libro.to_string();
+            }
+        }
+        atributos
+    }
+
+    /// Indica si el atributo está presente, con o sin valor.
+    pub fn tiene(&self, nombre: &str) -> bool {
+        self.pares.iter().any(|(k, _)| k == nombre)
+    }
+
+    /// Valor de un atributo `clave=valor`.
+    pub fn valor(&self, nombre: &str) -> Option<&str> {
+        self.pares
+            .iter()
+            .find(|(k, _)| k == nombre)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// Valores de un atributo que se repite: `escenario=a, escenario=b`.
+    /// Las apariciones sin valor dan `None`.
+    pub fn valores<'a>(&'a self, nombre: &'a str) -> impl Iterator<Item = Option<&'a str>> {
+        self.pares
+            .iter()
+            .filter(move |(k, _)| k == nombre)
+            .map(|(_, v)| v.as_deref())
+    }
+
+    fn extender(&mut self, otros: Atributos) {
+        self.pares.extend(otros.pares);
+    }
+}
+
+/// Un bloque ```rust de un capítulo.
+#[derive(Debug, Clone)]
+pub struct Fragmento {
+    /// Archivo relativo a la raíz del libro.
+    pub archivo: PathBuf,
+    /// Línea (desde 1) donde abre el bloque.
+    pub linea: usize,
+    /// Código sin la sangría del bloque, con las líneas ocultas (sin su
+    /// `# `).
+    pub codigo: String,
+    /// Líneas de `codigo` (desde 0) que no se muestran en el PDF.
+    pub ocultas: Vec<usize>,
+    pub atributos: Atributos,
+    /// Si el autor resalta o anota líneas con un `#codly(highlights: ...)`
+    /// o `#codly(annotations: ...)` antes del bloque.
+    pub resaltado: bool,
+    /// Salida escrita a mano en un bloque ```yaml que sigue al fragmento
+    /// tras una línea `Salida:` o `Resultado:`, con la línea (desde 1) donde
+    /// abre.
+    pub salida_escrita: Option<(usize, String)>,
+}
+
+impl Fragmento {
+    /// Identificador del fragmento, si el autor le dio uno.
+    pub fn id(&self) -> Option<&str> {
+        self.atributos.valor("id")
+    }
+
+    /// El código tal como lo muestra Typst en `raw.text`: sin las líneas
+    /// ocultas ni el salto de línea final. Es la clave de los datos que las
+    /// plantillas buscan por bloque.
+    pub fn clave(&self) -> String {
+        let visibles: Vec<&str> = self
+            .codigo
+            .lines()
+            .enumerate()
+            .filter(|(n, _)| !self.ocultas.contains(n))
+            .map(|(_, l)| l)
+            .collect();
+        visibles.join("\n").trim_end_matches('\n').to_string()
+    }
+
+    /// Línea del bloque mostrado (desde 1) de la línea `linea` de `codigo`
+    /// (desde 1), o `None` si está oculta.
+    pub fn linea_visible(&self, linea: usize) -> Option<usize> {
+        let indice = linea.checked_sub(1)?;
+        if self.ocultas.contains(&indice) {
+            return None;
+        }
+        Some(linea - self.ocultas.iter().filter(|&&n| n < indice).count())
+    }
+
+    /// Ubicación legible: `src/chapters/...:123`.
+    pub fn ubicacion(&self) -> String {
+        format!("{}:{}", self.archivo.display(), self.linea)
+    }
+
+    /// Si el fragmento es un programa que se puede compilar solo: no lleva
+    /// `ignore` ni `fragment` y no es un archivo de un `proyecto=`.
+    pub fn compilable(&self) -> bool {
+        !["ignore", "fragment", "proyecto"]
+            .iter()
+            .any(|a| self.atributos.tiene(a))
+    }
+
+    /// Opciones de `rustc` para el fragmento: con `release`, `-O` (sin
+    /// comprobaciones de desbordamiento).
+    pub fn opciones(&self) -> &'static [&'static str] {
+        if self.atributos.tiene("release") {
+            &["-O", "-A", "warnings"]
+        } else {
+            &["-A", "warnings"]
+        }
+    }
+
+    /// Programa completo: el fragmento tal cual si ya define `main`, o
+    /// envuelto en un `fn main` si son sentencias sueltas.
+    pub fn programa(&self) -> String {
+        if define_main(&self.codigo) {
+            return self.codigo.clone();
+        }
+        let mut programa = String::from("fn main() {\n");
+        for linea in self.codigo.lines() {
+            programa.push_str("    ");
+            programa.push_str(linea);
+            programa.push('\n');
+        }
+        programa.push_str("}\n");
+        programa
+    }
+}
+
+/// Archivos del libro (rutas absolutas), en orden estable.
+pub fn archivos(raiz: &Path) -> Resultado<Vec<PathBuf>> {
+    let mut archivos = Vec::new();
+    for dir in DIRECTORIOS {
+        recolectar(&raiz.join(dir), &mut archivos)?;
+    }
+    archivos.sort();
+    Ok(archivos)
+}
+
+/// Extrae los fragmentos de todos los archivos del libro, en orden estable.
+pub fn extraer_libro(raiz: &Path) -> Resultado<Vec<Fragmento>> {
+    let mut fragmentos = Vec::new();
+    for archivo in archivos(raiz)? {
+        let texto = fs::read_to_string(&archivo)?;
+        let relativo = archivo.strip_prefix(raiz).unwrap_or(&archivo);
+        fragmentos.extend(extraer(relativo, &texto));
+    }
+    Ok(fragmentos)
+}
+
+fn recolectar(dir: &Path, archivos: &mut Vec<PathBuf>) -> Resultado<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entrada in fs::read_dir(dir)? {
+        let ruta = entrada?.path();
+        if ruta.is_dir() {
+            recolectar(&ruta, archivos)?;
+        } else if matches!(
+            ruta.extension().and_then(|e| e.to_str()),
+            Some("typ" | "rs")
+        ) {
+            archivos.push(ruta);
+        }
+    }
+    Ok(())
+}
+
+/// Extrae los fragmentos de un archivo Typst.
+pub fn extraer(archivo: &Path, texto: &str) -> Vec<Fragmento> {
+    let lineas: Vec<&str> = texto.lines().collect();
+    let mut fragmentos = Vec::new();
+    let mut pendientes = Atributos::default();
+    // Línea del último `#codly(` desde el bloque anterior.
+    let mut codly = None;
+    let mut i = 0;
+
+    while i < lineas.len() {
+        let linea = lineas[i];
+        let recortada = linea.trim_start();
+
+        if recortada.starts_with("#codly(") {
+            codly = Some(i);
+        }
+
+        if let Some(resto) = recortada.strip_prefix(MARCA) {
+            pendientes.extender(Atributos::parsear(resto));
+            i += 1;
+            continue;
+        }
+
+        let Some(apertura) = linea.find(APERTURA) else {
+            if recortada.starts_with("```")
+                && recortada.len() > 3
+                && !recortada[3..].contains("```")
+            {
+                // Bloque de otro lenguaje: sus atributos (p. ej. `arbol=`)
+                // no pasan al siguiente fragmento de Rust.
+                pendientes = Atributos::default();
+                codly = None;
+                i += 1;
+                while i < lineas.len() && !lineas[i].trim_start().starts_with("```") {
+                    i += 1;
+                }
+            }
+            i += 1;
+            continue;
+        };
+        let resto = linea[apertura + APERTURA.len()..].trim();
+        if let Some(info) = resto.strip_prefix(',')
+            && !info.contains("```")
+        {
+            pendientes.extender(Atributos::parsear_apertura(info));
+        } else if !resto.is_empty() {
+            // Bloque en línea (```rust ... ```) u otro lenguaje.
+            i += 1;
+            continue;
+        }
+
+        let resaltado = codly.take().is_some_and(|desde| {
+            lineas[desde..i]
+                .iter()
+                .any(|l| l.contains("highlights") || l.contains("annotations"))
+        });
+        let inicio = i;
+        i += 1;
+        while i < lineas.len() && !lineas[i].trim_start().starts_with("```") {
+            i += 1;
+        }
+        // Como Typst, se quita la sangría común al cuerpo y a la línea que
+        // cierra, no la de la línea que abre (`+ Alineación```rust`).
+        let sangria = lineas[inicio + 1..(i + 1).min(lineas.len())]
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let mut ocultas = Vec::new();
+        let cuerpo: Vec<String> = lineas[inicio + 1..i]
+            .iter()
+            .enumerate()
+            .map(|(n, l)| {
+                let linea = quitar_sangria(l, sangria);
+                let recortada = linea.trim_start();
+                let sangria = &linea[..linea.len() - recortada.len()];
+                match recortada.strip_prefix(OCULTA) {
+                    Some(resto) => {
+                        ocultas.push(n);
+                        format!("{sangria}{resto}")
+                    }
+                    None if recortada == OCULTA.trim_end() => {
+                        ocultas.push(n);
+                        String::new()
+                    }
+                    None => linea.to_string(),
+                }
+            })
+            .collect();
+
+        let mut codigo = cuerpo.join("\n");
+        codigo.push('\n');
+        let salida_escrita = salida_escrita(&lineas[i + 1..]).map(|(n, s)| (i + 1 + n + 1, s));
+        fragmentos.push(Fragmento {
+            archivo: archivo.to_path_buf(),
+            linea: inicio + 1,
+            codigo,
+            ocultas,
+            atributos: std::mem::take(&mut pendientes),
+            resaltado,
+            salida_escrita,
+        });
+        i += 1;
+    }
+    fragmentos
+}
+
+/// Si el siguiente bloque de `lineas` es un ```yaml precedido de una línea
+/// `Salida:` o `Resultado:`, su posición en `lineas` y su contenido.
+fn salida_escrita(lineas: &[&str]) -> Option<(usize, String)> {
+    let apertura = lineas
+        .iter()
+        .position(|l| l.trim_start().starts_with("```"))?;
+    if lineas[apertura].trim() != "```yaml" {
+        return None;
+    }
+    let anterior = lineas[..apertura]
+        .iter()
+        .rev()
+        .find(|l| !l.trim().is_empty())?
+        .trim()
+        .to_lowercase();
+    if anterior != "salida:" && anterior != "resultado:" {
+        return None;
+    }
+    let cuerpo: Vec<&str> = lineas[apertura + 1..]
+        .iter()
+        .take_while(|l| !l.trim_start().starts_with("```"))
+        .map(|l| l.trim())
+        .collect();
+    Some((apertura, cuerpo.join("\n")))
+}
+
+/// Qué es cada tramo del código de un fragmento.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Zona {
+    Codigo,
+    /// Cadena (también `r#"..."#`) o carácter literal, con sus comillas.
+    Literal,
+    /// Comentario `//` o `/* */`, con sus barras.
+    Comentario,
+}
+
+fn es_identificador(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Parte `texto` en tramos de código, literales y comentarios: `(inicio,
+/// fin, zona)`, en bytes. Distingue `'"'` (un carácter) de `'a` (un
+/// lifetime).
+fn zonas(texto: &str) -> Vec<(usize, usize, Zona)> {
+    let c: Vec<char> = texto.chars().collect();
+    let en = |j: usize| c.get(j).copied();
+    // Posición en bytes de cada carácter, y del final.
+    let bytes: Vec<usize> = texto
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([texto.len()])
+        .collect();
+    let mut tramos: Vec<(usize, usize, Zona)> = Vec::new();
+    let mut i = 0;
+    while i < c.len() {
+        let anterior = i.checked_sub(1).and_then(en);
+        let (fin, zona) = match c[i] {
+            '/' if en(i + 1) == Some('/') => {
+                let fin = (i..c.len()).find(|&j| c[j] == '\n').unwrap_or(c.len());
+                (fin, Zona::Comentario)
+            }
+            '/' if en(i + 1) == Some('*') => {
+                // Los comentarios de bloque se anidan.
+                let (mut j, mut profundidad) = (i + 2, 1);
+                while j < c.len() && profundidad > 0 {
+                    match (c[j], en(j + 1)) {
+                        ('/', Some('*')) => {
+                            profundidad += 1;
+                            j += 2;
+                        }
+                        ('*', Some('/')) => {
+                            profundidad -= 1;
+                            j += 2;
+                        }
+                        _ => j += 1,
+                    }
+                }
+                (j.min(c.len()), Zona::Comentario)
+            }
+            '"' => {
+                let mut j = i + 1;
+                while j < c.len() && c[j] != '"' {
+                    j += if c[j] == '\\' { 2 } else { 1 };
+                }
+                ((j + 1).min(c.len()), Zona::Literal)
+            }
+            'r' if !anterior.is_some_and(|a| es_identificador(a) && a != 'b')
+                && matches!(en(i + 1), Some('"' | '#')) =>
+            {
+                let almohadillas = (i + 1..c.len()).take_while(|&j| c[j] == '#').count();
+                let apertura = i + 1 + almohadillas;
+                if en(apertura) == Some('"') {
+                    let cierre = (apertura + 1..c.len()).find(|&j| {
+                        c[j] == '"' && (1..=almohadillas).all(|k| en(j + k) == Some('#'))
+                    });
+                    let fin = cierre.map_or(c.len(), |j| j + 1 + almohadillas);
+                    (fin, Zona::Literal)
+                } else {
+                    (i + 1, Zona::Codigo)
+                }
+            }
+            '\'' if en(i + 1) == Some('\\') => {
+                let cierre = (i + 3..c.len()).find(|&j| c[j] == '\'');
+                (cierre.map_or(c.len(), |j| j + 1), Zona::Literal)
+            }
+            '\'' if en(i + 2) == Some('\'') => (i + 3, Zona::Literal),
+            _ => (i + 1, Zona::Codigo),
+        };
+        match tramos.last_mut() {
+            Some((_, ultimo, Zona::Codigo)) if zona == Zona::Codigo => *ultimo = bytes[fin],
+            _ => tramos.push((bytes[i], bytes[fin], zona)),
+        }
+        i = fin;
+    }
+    tramos
+}
+
+/// Si `codigo` define el elemento `fn main(` (fuera de comentarios,
+/// cadenas y bloques: un `fn main` dentro de un `impl` no cuenta).
+fn define_main(codigo: &str) -> bool {
+    let mut profundidad = 0;
+    for (inicio, fin, zona) in zonas(codigo) {
+        if zona != Zona::Codigo {
+            continue;
+        }
+        for (i, c) in codigo[inicio..fin].char_indices() {
+            let i = inicio + i;
+            match c {
+                '{' => profundidad += 1,
+                '}' => profundidad -= 1,
+                'f' if profundidad == 0
+                    && !codigo[..i].ends_with(es_identificador)
+                    && es_fn_main(&codigo[i..fin]) =>
+                {
+                    return true;
+                }
+                _ => {}
+            }
+        }
+    }
+    false
+}
+
+/// Si `texto` empieza por `fn main(`, con cualquier blanco en medio.
+fn es_fn_main(texto: &str) -> bool {
+    let Some(resto) = texto.strip_prefix("fn") else {
+        return false;
+    };
+    let nombre = resto.trim_start();
+    nombre.len() < resto.len()
+        && nombre
+            .strip_prefix("main")
+            .is_some_and(|r| r.trim_start().starts_with('('))
+}
+
+/// La línea sin su comentario `// ...` final (ignora `//` dentro de cadenas
+/// y de caracteres literales como `'"'`).
+pub fn sin_comentario(linea: &str) -> &str {
+    zonas(linea)
+        .into_iter()
+        .find(|&(inicio, _, zona)| zona == Zona::Comentario && linea[inicio..].starts_with("//"))
+        .map_or(linea, |(inicio, _, _)| &linea[..inicio])
+}
+
+/// El comentario `// ...` final de la línea, sin las barras ni los blancos
+/// de alrededor.
+pub fn comentario(linea: &str) -> Option<&str> {
+    let codigo = sin_comentario(linea);
+    (codigo.len() < linea.len()).then(|| linea[codigo.len()..].trim_start_matches('/').trim())
+}
+
+/// El valor de un comentario sin la aclaración final entre paréntesis que
+/// lo sigue: `true (comparación lexicográfica)` → `true`. Un valor que es
+/// todo un paréntesis, como `(0, true)`, se queda como está.
+pub fn sin_aclaracion(texto: &str) -> &str {
+    let Some(cuerpo) = texto.strip_suffix(')') else {
+        return texto;
+    };
+    let mut profundidad = 0;
+    for (i, c) in cuerpo.char_indices().rev() {
+        match c {
+            ')' => profundidad += 1,
+            '(' if profundidad > 0 => profundidad -= 1,
+            '(' => {
+                let valor = &texto[..i];
+                let separado = valor.ends_with(char::is_whitespace);
+                return if separado && !valor.trim().is_empty() {
+                    valor.trim_end()
+                } else {
+                    texto
+                };
+            }
+            _ => {}
+        }
+    }
+    texto
+}
+
+fn quitar_sangria(linea: &str, sangria: usize) -> &str {
+    let blancos = linea.len() - linea.trim_start().len();
+    &linea[blancos.min(sangria)..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extraer_uno(texto: &str) -> Fragmento {
+        let mut fragmentos = extraer(Path::new("prueba.typ"), texto);
+        assert_eq!(fragmentos.len(), 1);
+        fragmentos.remove(0)
+    }
+
+    #[test]
+    fn atributos_de_la_marca() {
+        let fragmento = extraer_uno(
+            "Texto\n// libro: id=stack, memoria\n// libro: edicion=2021\n\
+             ```rust\nfn main() {}\n```\n",
+        );
+        assert_eq!(fragmento.linea, 4);
+        assert_eq!(fragmento.id(), Some("stack"));
+        assert!(fragmento.atributos.tiene("memoria"));
+        assert_eq!(fragmento.atributos.valor("edicion"), Some("2021"));
+    }
+
+    #[test]
+    fn atributos_de_la_apertura() {
+        let fragmento =
+            extraer_uno("// libro: id=x\n```rust,no_run,compile_fail\nfn main() {}\n```\n");
+        assert_eq!(fragmento.id(), Some("x"));
+        assert!(fragmento.atributos.tiene("no_run"));
+        assert!(fragmento.atributos.tiene("no_compila"));
+    }
+
+    #[test]
+    fn parsear_apertura_traduce_los_nombres_de_rustdoc() {
+        let atributos = Atributos::parsear_apertura("edition2018, project=hola, should_panic");
+        assert_eq!(atributos.valor("edicion"), Some("2018"));
+        assert_eq!(atributos.valor("proyecto"), Some("hola"));
+        assert!(atributos.tiene("should_panic"));
+        assert!(!atributos.tiene("edition2018"));
+    }
+
+    #[test]
+    fn valores_repetidos() {
+        let atributos = Atributos::parsear("escenario=a, id=x, escenario, escenario=b");
+        assert_eq!(
+            atributos.valores("escenario").collect::<Vec<_>>(),
+            [Some("a"), None, Some("b")]
+        );
+        assert_eq!(atributos.valor("escenario"), Some("a"));
+    }
+
+    #[test]
+    fn los_atributos_de_otro_lenguaje_no_pasan_al_siguiente() {
+        let fragmentos = extraer(
+            Path::new("prueba.typ"),
+            "// libro: arbol=x\n```toml\n[package]\n```\n```rust\nlet x = 1;\n```\n",
+        );
+        assert_eq!(fragmentos.len(), 1);
+        assert!(!fragmentos[0].atributos.tiene("arbol"));
+    }
+
+    #[test]
+    fn quita_la_sangria_comun() {
+        let fragmento = extraer_uno("+ Lista\n  ```rust\n  if x {\n      y();\n  }\n  ```\n");
+        assert_eq!(fragmento.codigo, "if x {\n    y();\n}\n");
+    }
+
+    #[test]
+    fn lineas_ocultas() {
+        let fragmento = extraer_uno(
+            "```rust\n# use std::fs::File;\n#\nfn main() {\n    # let x = 1;\n    dbg!(x);\n}\n```\n",
+        );
+        assert_eq!(
+            fragmento.codigo,
+            "use std::fs::File;\n\nfn main() {\n    let x = 1;\n    dbg!(x);\n}\n"
+        );
+        assert_eq!(fragmento.ocultas, [0, 1, 3]);
+        assert_eq!(fragmento.clave(), "fn main() {\n    dbg!(x);\n}");
+        assert_eq!(fragmento.linea_visible(1), None);
+        assert_eq!(fragmento.linea_visible(3), Some(1));
+        assert_eq!(fragmento.linea_visible(5), Some(2));
+    }
+
+    #[test]
+    fn salida_escrita_tras_el_bloque() {
+        let fragmento =
+            extraer_uno("```rust\nprintln!(\"hola\");\n```\nSalida:\n```yaml\n  hola\n```\n");
+        assert_eq!(fragmento.salida_escrita, Some((5, String::from("hola"))));
+    }
+
+    #[test]
+    fn sin_comentario_respeta_las_cadenas() {
+        assert_eq!(sin_comentario("let x = 5; // cinco"), "let x = 5; ");
+        assert_eq!(
+            sin_comentario(r#"let url = "http://a.b"; // web"#),
+            r#"let url = "http://a.b"; "#
+        );
+        assert_eq!(
+            sin_comentario(r#"let s = "\"//\"";"#),
+            r#"let s = "\"//\"";"#
+        );
+        assert_eq!(sin_comentario("let x = 5;"), "let x = 5;");
+        assert_eq!(
+            sin_comentario(r#"let c = '"'; // comillas"#),
+            r#"let c = '"'; "#
+        );
+        assert_eq!(
+            sin_comentario(r#"let c = '\''; let s = "//"; // "#),
+            r#"let c = '\''; let s = "//"; "#
+        );
+        assert_eq!(
+            sin_comentario(r##"let s = r#"a "//" b"#; // raw"##),
+            r##"let s = r#"a "//" b"#; "##
+        );
+        assert_eq!(
+            sin_comentario("fn f<'a>(x: &'a str) {} // lifetime"),
+            "fn f<'a>(x: &'a str) {} "
+        );
+        assert_eq!(
+            sin_comentario("let x = /* a */ 5; // b"),
+            "let x = /* a */ 5; "
+        );
+    }
+
+    #[test]
+    fn programa_con_main_propio() {
+        for codigo in [
+            "fn main() {}\n",
+            "use std::fs;\n\npub fn main () {\n}\n",
+            "fn main\n() {}\n",
+        ] {
+            let fragmento = extraer_uno(&format!("```rust\n{codigo}```\n"));
+            assert_eq!(fragmento.programa(), fragmento.codigo, "{codigo}");
+        }
+    }
+
+    #[test]
+    fn programa_envuelve_las_sentencias_sueltas() {
+        for codigo in [
+            "// fn main() se escribe solo una vez\nlet x = 1;\n",
+            "println!(\"fn main() {{}}\");\n",
+            "/* fn main() {} */\nlet x = 1;\n",
+            "impl A {\n    fn main(&self) {}\n}\nA.main();\n",
+            "fn main_auxiliar() {}\nmain_auxiliar();\n",
+        ] {
+            let fragmento = extraer_uno(&format!("```rust\n{codigo}```\n"));
+            assert!(
+                fragmento.programa().starts_with("fn main() {\n"),
+                "{codigo}"
+            );
+        }
+    }
+}
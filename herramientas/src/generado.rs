@@ -0,0 +1,34 @@
+//! Escritura de los archivos generados en `build/generado/`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Libro, Resultado};
+
+/// Directorio (relativo a la raíz) donde se escribe todo lo generado.
+pub const DIRECTORIO: &str = "build/generado";
+
+/// Primera línea de cada archivo Typst generado.
+pub const CABECERA: &str = "// Generado por `libro generar`. No editar a mano.\n";
+
+/// Escribe `contenido` en `build/generado/<ruta>` si cambió.
+pub fn escribir(libro: &Libro, ruta: impl AsRef<Path>, contenido: &str) -> Resultado<PathBuf> {
+    escribir_en(libro, DIRECTORIO, ruta, contenido)
+}
+
+/// Como [`escribir`], en otro `directorio` relativo a la raíz.
+pub fn escribir_en(
+    libro: &Libro,
+    directorio: &str,
+    ruta: impl AsRef<Path>,
+    contenido: &str,
+) -> Resultado<PathBuf> {
+    let destino = libro.raiz.join(directorio).join(ruta);
+    if let Some(padre) = destino.parent() {
+        fs::create_dir_all(padre)?;
+    }
+    if fs::read_to_string(&destino).ok().as_deref() != Some(contenido) {
+        fs::write(&destino, contenido)?;
+    }
+    Ok(destino)
+}
@@ -0,0 +1,338 @@
+//! Lector y escritor mínimos de JSON, suficientes para los diagnósticos de
+//! `rustc`.
+//!
+//! El crate no tiene dependencias; este módulo cubre el JSON que producen
+//! nuestras propias herramientas y `rustc --error-format=json`, y el que
+//! leen las plantillas de Typst con `json()`.
+
+use crate::{Error, Resultado};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Valor {
+    Nulo,
+    Bool(bool),
+    Numero(f64),
+    Cadena(String),
+    Lista(Vec<Valor>),
+    /// Pares en el orden del documento.
+    Objeto(Vec<(String, Valor)>),
+}
+
+impl Valor {
+    /// Campo `clave` de un objeto.
+    pub fn campo(&self, clave: &str) -> Option<&Valor> {
+        match self {
+            Valor::Objeto(pares) => pares.iter().find(|(k, _)| k == clave).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn cadena(&self) -> Option<&str> {
+        match self {
+            Valor::Cadena(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn lista(&self) -> &[Valor] {
+        match self {
+            Valor::Lista(v) => v,
+            _ => &[],
+        }
+    }
+
+    /// Pares de un objeto (vacío si no lo es).
+    pub fn pares(&self) -> &[(String, Valor)] {
+        match self {
+            Valor::Objeto(pares) => pares,
+            _ => &[],
+        }
+    }
+
+    pub fn numero(&self) -> Option<f64> {
+        match self {
+            Valor::Numero(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// El valor como JSON compacto.
+    pub fn serializar(&self) -> String {
+        match self {
+            Valor::Nulo => String::from("null"),
+            Valor::Bool(b) => b.to_string(),
+            Valor::Numero(n) => n.to_string(),
+            Valor::Cadena(s) => cadena(s),
+            Valor::Lista(valores) => {
+                let valores: Vec<String> = valores.iter().map(Valor::serializar).collect();
+                format!("[{}]", valores.join(","))
+            }
+            Valor::Objeto(pares) => {
+                let pares: Vec<String> = pares
+                    .iter()
+                    .map(|(k, v)| format!("{}:{}", cadena(k), v.serializar()))
+                    .collect();
+                format!("{{{}}}", pares.join(","))
+            }
+        }
+    }
+}
+
+/// Literal de cadena JSON: `"..."` con los escapes necesarios.
+pub fn cadena(texto: &str) -> String {
+    let mut salida = String::with_capacity(texto.len() + 2);
+    salida.push('"');
+    for c in texto.chars() {
+        match c {
+            '"' => salida.push_str("\\\""),
+            '\\' => salida.push_str("\\\\"),
+            '\n' => salida.push_str("\\n"),
+            '\t' => salida.push_str("\\t"),
+            '\r' => salida.push_str("\\r"),
+            c if c.is_control() => salida.push_str(&format!("\\u{:04x}", c as u32)),
+            c => salida.push(c),
+        }
+    }
+    salida.push('"');
+    salida
+}
+
+/// Interpreta un documento JSON completo.
+pub fn parsear(texto: &str) -> Resultado<Valor> {
+    let mut lector = Lector {
+        texto: texto.as_bytes(),
+        pos: 0,
+    };
+    let valor = lector.valor()?;
+    lector.espacios();
+    if lector.pos != lector.texto.len() {
+        return Err(lector.error("texto sobrante"));
+    }
+    Ok(valor)
+}
+
+struct Lector<'a> {
+    texto: &'a [u8],
+    pos: usize,
+}
+
+impl Lector<'_> {
+    fn error(&self, que: &str) -> Error {
+        Error::Libro(format!("JSON inválido en el byte {}: {que}", self.pos))
+    }
+
+    fn espacios(&mut self) {
+        while self
+            .texto
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn esperar(&mut self, literal: &str) -> Resultado<()> {
+        if self.texto[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("se esperaba `{literal}`")))
+        }
+    }
+
+    fn valor(&mut self) -> Resultado<Valor> {
+        self.espacios();
+        match self.texto.get(self.pos) {
+            Some(b'{') => self.objeto(),
+            Some(b'[') => self.lista(),
+            Some(b'"') => Ok(Valor::Cadena(self.cadena()?)),
+            Some(b't') => self.esperar("true").map(|_| Valor::Bool(true)),
+            Some(b'f') => self.esperar("false").map(|_| Valor::Bool(false)),
+            Some(b'n') => self.esperar("null").map(|_| Valor::Nulo),
+            Some(b'-' | b'0'..=b'9') => self.numero(),
+            _ => Err(self.error("se esperaba un valor")),
+        }
+    }
+
+    fn objeto(&mut self) -> Resultado<Valor> {
+        self.esperar("{")?;
+        let mut pares = Vec::new();
+        self.espacios();
+        if self.texto.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Valor::Objeto(pares));
+        }
+        loop {
+            self.espacios();
+            let clave = self.cadena()?;
+            self.espacios();
+            self.esperar(":")?;
+            pares.push((clave, self.valor()?));
+            self.espacios();
+            match self.texto.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Valor::Objeto(pares));
+                }
+                _ => return Err(self.error("se esperaba `,` o `}`")),
+            }
+        }
+    }
+
+    fn lista(&mut self) -> Resultado<Valor> {
+        self.esperar("[")?;
+        let mut valores = Vec::new();
+        self.espacios();
+        if self.texto.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Valor::Lista(valores));
+        }
+        loop {
+            valores.push(self.valor()?);
+            self.espacios();
+            match self.texto.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Valor::Lista(valores));
+                }
+                _ => return Err(self.error("se esperaba `,` o `]`")),
+            }
+        }
+    }
+
+    fn numero(&mut self) -> Resultado<Valor> {
+        let inicio = self.pos;
+        while self
+            .texto
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_digit() || b"+-.eE".contains(b))
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.texto[inicio..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(Valor::Numero)
+            .ok_or_else(|| self.error("número inválido"))
+    }
+
+    fn cadena(&mut self) -> Resultado<String> {
+        self.esperar("\"")?;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&b) = self.texto.get(self.pos) else {
+                return Err(self.error("cadena sin cerrar"));
+            };
+            self.pos += 1;
+            match b {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&e) = self.texto.get(self.pos) else {
+                        return Err(self.error("escape sin cerrar"));
+                    };
+                    self.pos += 1;
+                    let c = match e {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode()?,
+                        _ => return Err(self.error("escape desconocido")),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+                b => bytes.push(b),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("UTF-8 inválido"))
+    }
+
+    /// `\uXXXX`, incluidos los pares sustitutos (`🦀`).
+    fn unicode(&mut self) -> Resultado<char> {
+        let alto = self.hex4()?;
+        let codigo = if (0xD800..0xDC00).contains(&alto) {
+            self.esperar("\\u")?;
+            let bajo = self.hex4()?;
+            0x10000 + ((alto - 0xD800) << 10) + (bajo - 0xDC00)
+        } else {
+            alto
+        };
+        char::from_u32(codigo).ok_or_else(|| self.error("código Unicode inválido"))
+    }
+
+    fn hex4(&mut self) -> Resultado<u32> {
+        let digitos = self
+            .texto
+            .get(self.pos..self.pos + 4)
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .and_then(|d| u32::from_str_radix(d, 16).ok())
+            .ok_or_else(|| self.error("se esperaban 4 dígitos hexadecimales"))?;
+        self.pos += 4;
+        Ok(digitos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsear_un_documento() {
+        let valor = parsear(
+            r#" {"nombre": "vec", "n": -1.5e2, "ok": true, "nada": null,
+                "lista": [1, [], {}], "vacio": ""} "#,
+        )
+        .unwrap();
+        assert_eq!(valor.campo("nombre").and_then(Valor::cadena), Some("vec"));
+        assert_eq!(valor.campo("n").and_then(Valor::numero), Some(-150.0));
+        assert_eq!(valor.campo("ok"), Some(&Valor::Bool(true)));
+        assert_eq!(valor.campo("nada"), Some(&Valor::Nulo));
+        assert_eq!(
+            valor.campo("lista").map(Valor::lista),
+            Some(
+                &[
+                    Valor::Numero(1.0),
+                    Valor::Lista(Vec::new()),
+                    Valor::Objeto(Vec::new())
+                ][..]
+            )
+        );
+        assert_eq!(valor.campo("vacio").and_then(Valor::cadena), Some(""));
+        assert_eq!(valor.campo("falta"), None);
+    }
+
+    #[test]
+    fn parsear_escapes() {
+        let valor = parsear(r#""a\"b\\c\/d\n\té🦀""#).unwrap();
+        assert_eq!(valor.cadena(), Some("a\"b\\c/d\n\té🦀"));
+    }
+
+    #[test]
+    fn ida_y_vuelta() {
+        let texto = r#"{"codigo":"let x = \"5\";\n","lineas":[1,2],"error":"E0382"}"#;
+        assert_eq!(parsear(texto).unwrap().serializar(), texto);
+    }
+
+    #[test]
+    fn parsear_rechaza_lo_invalido() {
+        for texto in [
+            "",
+            "{",
+            r#"{"a" 1}"#,
+            "[1, 2",
+            r#""sin cerrar"#,
+            r#""\x""#,
+            "1 2",
+            "tru",
+        ] {
+            assert!(parsear(texto).is_err(), "{texto:?}");
+        }
+    }
+}
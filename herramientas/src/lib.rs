@@ -0,0 +1,110 @@
+//! Herramientas del libro «Diviértete con Rust».
+//!
+//! El libro está escrito en Typst, pero casi todo lo que muestra sale de
+//! Rust: valores, direcciones de memoria, tablas de rangos. Este crate
+//! ejecuta esos ejemplos con el toolchain local y escribe el resultado en
+//! `build/generado/`, de donde lo leen los capítulos.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub mod afirmaciones;
+pub mod aislado;
+pub mod arbol;
+pub mod arboles;
+pub mod booleanos;
+pub mod cache;
+pub mod capacidad;
+pub mod caracteres;
+pub mod cargo;
+pub mod comentarios;
+pub mod diagnosticos;
+pub mod ediciones;
+pub mod ejemplos;
+pub mod enteros;
+pub mod escenarios;
+pub mod estilo;
+pub mod etapas;
+pub mod flotantes;
+pub mod fragmentos;
+pub mod generado;
+pub mod json;
+pub mod lotes;
+pub mod matriz;
+pub mod memoria;
+pub mod msrv;
+pub mod panicos;
+pub mod resultados;
+pub mod rustc;
+pub mod salidas;
+pub mod transcripciones;
+pub mod typst;
+pub mod verificacion;
+
+/// Errores de las herramientas del libro.
+#[derive(Debug)]
+pub enum Error {
+    /// Fallo de lectura o escritura de archivos.
+    Io(io::Error),
+    /// Un programa no compiló; contiene la salida de `rustc`.
+    Compilacion(String),
+    /// Un programa compiló pero terminó con error.
+    Ejecucion(String),
+    /// El contenido del libro no tiene la forma esperada.
+    Libro(String),
+}
+
+pub type Resultado<T> = Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "error de E/S: {e}"),
+            Error::Compilacion(msg) => write!(f, "no compila:\n{msg}"),
+            Error::Ejecucion(msg) => write!(f, "falló al ejecutarse:\n{msg}"),
+            Error::Libro(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Raíz del libro: el directorio que contiene `main.typ`.
+#[derive(Debug, Clone)]
+pub struct Libro {
+    pub raiz: PathBuf,
+    /// `--bless`: actualizar las instantáneas en lugar de compararlas.
+    pub bless: bool,
+}
+
+impl Libro {
+    /// Busca la raíz subiendo desde `inicio`.
+    pub fn encontrar(inicio: &Path) -> Resultado<Libro> {
+        let mut dir = Some(inicio);
+        while let Some(actual) = dir {
+            if actual.join("main.typ").is_file() {
+                return Ok(Libro {
+                    raiz: actual.to_path_buf(),
+                    bless: false,
+                });
+            }
+            dir = actual.parent();
+        }
+        Err(Error::Libro(format!(
+            "no se encontró main.typ subiendo desde {}",
+            inicio.display()
+        )))
+    }
+
+    /// Todos los fragmentos ```rust de los capítulos.
+    pub fn fragmentos(&self) -> Resultado<Vec<fragmentos::Fragmento>> {
+        fragmentos::extraer_libro(&self.raiz)
+    }
+}
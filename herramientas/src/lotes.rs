@@ -0,0 +1,297 @@
+//! Compilación de muchos fragmentos a la vez.
+//!
+//! Llamar a `rustc` una vez por fragmento es lo más lento de comprobar el
+//! libro: cada llamada vuelve a cargar el compilador y a enlazar un binario.
+//! Aquí los programas de una misma edición y con las mismas opciones de
+//! `rustc` (`-O` para los `release`) se reúnen en lotes de hasta
+//! [`TAMANO`]: cada uno va en su módulo `f<n>`, y el `main` del lote ejecuta
+//! el del módulo que recibe como argumento. Los lotes se compilan en
+//! paralelo ([`en_paralelo`]).
+//!
+//! Si un lote no compila, se parte en dos mitades y se compila cada una,
+//! hasta dar con los fragmentos que fallan. Esos se compilan solos, como
+//! siempre, para que el error sea el mismo que vería el lector. También se
+//! compilan solos los que no pueden ir dentro de un módulo (rutas `crate::`,
+//! atributos `#![...]`) y aquellos cuyo comportamiento cambiaría en un lote:
+//! los que imprimen rutas o posiciones del código (`type_name`,
+//! `module_path!`, `line!`), leen sus argumentos o usan archivos del
+//! directorio de trabajo, que comparten todos los del lote.
+
+use std::num::NonZeroUsize;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::aislado::{self, Informe, Limites};
+use crate::rustc::{self, Programa};
+use crate::{Error, Resultado};
+
+/// Programas como máximo en cada lote.
+pub const TAMANO: usize = 32;
+
+/// Código que obliga a compilar un programa solo.
+const SOLOS: &[&str] = &[
+    "crate::",
+    "#![",
+    "type_name",
+    "module_path!",
+    "file!",
+    "line!",
+    "column!",
+    "env::args",
+    "fs::",
+    "File::",
+];
+
+/// Función que se añade a cada módulo del lote para llamar a su `main`,
+/// que es privado.
+const ENTRADA: &str = "__lote";
+
+/// Si `programa` puede compilarse dentro de un módulo sin cambiar lo que
+/// hace.
+pub fn empaquetable(programa: &str) -> bool {
+    !SOLOS.iter().any(|s| programa.contains(s))
+}
+
+/// Un programa compilado, solo o dentro de un lote.
+#[derive(Debug, Clone)]
+pub struct Ejecutable {
+    programa: Arc<Programa>,
+    /// Módulo del lote, o `None` si se compiló solo.
+    modulo: Option<String>,
+}
+
+impl Ejecutable {
+    /// Orden que ejecuta el programa (ver [`Programa::orden`]).
+    pub fn orden(&self) -> Command {
+        let mut orden = self.programa.orden();
+        orden.args(&self.modulo);
+        orden
+    }
+
+    /// Ejecuta el programa aislado, con `limites`.
+    pub fn correr_con(&self, limites: &Limites) -> Resultado<Informe> {
+        aislado::ejecutar(&mut self.orden(), limites)
+    }
+}
+
+/// Un programa, su edición y las opciones de `rustc` con que se compila.
+pub type Fuente<'a> = (String, &'a str, &'a [&'a str]);
+
+/// Compila cada [`Fuente`]. Devuelve, en el mismo orden, el ejecutable o la
+/// salida de `rustc` si no compila.
+pub fn compilar(programas: &[Fuente]) -> Resultado<Vec<Result<Ejecutable, String>>> {
+    let mut lotes: Vec<(&str, &[&str], Vec<usize>)> = Vec::new();
+    for (i, (programa, edicion, opciones)) in programas.iter().enumerate() {
+        let abierto = lotes.iter_mut().find(|(e, o, lote)| {
+            e == edicion
+                && o == opciones
+                && lote.len() < TAMANO
+                && empaquetable(&programas[lote[0]].0)
+        });
+        match abierto {
+            Some((_, _, lote)) if empaquetable(programa) => lote.push(i),
+            _ => lotes.push((edicion, opciones, vec![i])),
+        }
+    }
+    let compilados = en_paralelo(&lotes, |(edicion, opciones, lote)| {
+        compilar_lote(programas, edicion, opciones, lote)
+    })?;
+    let mut resultados: Vec<Option<Result<Ejecutable, String>>> = vec![None; programas.len()];
+    for (i, compilado) in compilados.into_iter().flatten() {
+        resultados[i] = Some(compilado);
+    }
+    Ok(resultados.into_iter().flatten().collect())
+}
+
+/// Compila los programas `lote`; si no compilan juntos, parte el lote en
+/// dos.
+fn compilar_lote(
+    programas: &[Fuente],
+    edicion: &str,
+    opciones: &[&str],
+    lote: &[usize],
+) -> Resultado<Vec<(usize, Result<Ejecutable, String>)>> {
+    if let [i] = lote {
+        let compilado = match rustc::compilar_en(&programas[*i].0, edicion, opciones) {
+            Ok(programa) => Ok(Ejecutable {
+                programa: Arc::new(programa),
+                modulo: None,
+            }),
+            Err(Error::Compilacion(salida)) => Err(salida),
+            Err(e) => return Err(e),
+        };
+        return Ok(vec![(*i, compilado)]);
+    }
+    match rustc::compilar_en(&fuente(programas, lote), edicion, opciones) {
+        Ok(programa) => {
+            let programa = Arc::new(programa);
+            Ok(lote
+                .iter()
+                .map(|&i| {
+                    let ejecutable = Ejecutable {
+                        programa: Arc::clone(&programa),
+                        modulo: Some(format!("f{i}")),
+                    };
+                    (i, Ok(ejecutable))
+                })
+                .collect())
+        }
+        Err(Error::Compilacion(_)) => {
+            let (primera, segunda) = lote.split_at(lote.len() / 2);
+            let mut compilados = compilar_lote(programas, edicion, opciones, primera)?;
+            compilados.extend(compilar_lote(programas, edicion, opciones, segunda)?);
+            Ok(compilados)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Fuente de un lote: un módulo por programa y un `main` que elige cuál
+/// ejecutar.
+fn fuente(programas: &[Fuente], lote: &[usize]) -> String {
+    let mut fuente = String::new();
+    for &i in lote {
+        fuente.push_str(&format!(
+            "pub mod f{i} {{\n{}\npub fn {ENTRADA}() -> impl std::process::Termination {{\n    main()\n}}\n}}\n\n",
+            programas[i].0
+        ));
+    }
+    fuente.push_str(
+        "fn main() -> std::process::ExitCode {\n    match std::env::args().nth(1).as_deref() {\n",
+    );
+    for &i in lote {
+        fuente.push_str(&format!(
+            "        Some(\"f{i}\") => std::process::Termination::report(f{i}::{ENTRADA}()),\n"
+        ));
+    }
+    fuente.push_str("        _ => std::process::ExitCode::FAILURE,\n    }\n}\n");
+    fuente
+}
+
+/// Aplica `f` a cada elemento con un hilo por procesador. Devuelve los
+/// resultados en el orden de `elementos`, o el primer error.
+pub fn en_paralelo<T: Sync, R: Send>(
+    elementos: &[T],
+    f: impl Fn(&T) -> Resultado<R> + Sync,
+) -> Resultado<Vec<R>> {
+    let hilos = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(elementos.len());
+    let siguiente = AtomicUsize::new(0);
+    let resultados: Mutex<Vec<Option<Resultado<R>>>> =
+        Mutex::new(elementos.iter().map(|_| None).collect());
+    thread::scope(|s| {
+        for _ in 0..hilos {
+            s.spawn(|| {
+                loop {
+                    let i = siguiente.fetch_add(1, Ordering::Relaxed);
+                    let Some(elemento) = elementos.get(i) else {
+                        break;
+                    };
+                    let resultado = f(elemento);
+                    resultados.lock().unwrap()[i] = Some(resultado);
+                }
+            });
+        }
+    });
+    resultados
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rustc::EDICION;
+
+    const OPCIONES: &[&str] = &["-A", "warnings"];
+
+    fn programa(texto: &str) -> String {
+        format!("fn main() {{\n    println!(\"{texto}\");\n}}\n")
+    }
+
+    fn stdout(ejecutable: &Ejecutable) -> String {
+        ejecutable.correr_con(&Limites::default()).unwrap().stdout
+    }
+
+    #[test]
+    fn empaquetable_sin_rutas_ni_archivos() {
+        assert!(empaquetable("fn main() { println!(\"hola\"); }"));
+        assert!(!empaquetable("use crate::modelo::Punto;"));
+        assert!(!empaquetable("#![allow(unused)]\nfn main() {}"));
+        assert!(!empaquetable("let f = File::open(\"hola.txt\");"));
+        assert!(!empaquetable("println!(\"{}\", line!());"));
+    }
+
+    #[test]
+    fn un_lote_con_un_error_se_parte_hasta_dar_con_el() {
+        let fuentes: Vec<String> = (0..5)
+            .map(|i| match i {
+                3 => String::from("fn main() {\n    let x: i32 = \"tres\";\n}\n"),
+                i => programa(&i.to_string()),
+            })
+            .collect();
+        let programas: Vec<Fuente> = fuentes
+            .iter()
+            .map(|f| (f.clone(), EDICION, OPCIONES))
+            .collect();
+        let resultados = compilar(&programas).unwrap();
+        assert_eq!(resultados.len(), 5);
+        for (i, resultado) in resultados.iter().enumerate() {
+            match resultado {
+                Err(salida) => {
+                    assert_eq!(i, 3);
+                    assert!(salida.contains("E0308"), "{salida}");
+                }
+                Ok(ejecutable) => assert_eq!(stdout(ejecutable), format!("{i}\n")),
+            }
+        }
+        // `0..5` se parte en `0..2`, que compila junto, y `2..5`, que se
+        // vuelve a partir hasta quedar `2`, `3` y `4` solos.
+        let en_lote: Vec<bool> = resultados
+            .iter()
+            .map(|r| r.as_ref().is_ok_and(|e| e.modulo.is_some()))
+            .collect();
+        assert_eq!(en_lote, [true, true, false, false, false]);
+    }
+
+    #[test]
+    fn lotes_por_edicion_y_opciones() {
+        let (a, b, c, d) = (programa("a"), programa("b"), programa("c"), programa("d"));
+        let fs =
+            String::from("fn main() {\n    println!(\"{}\", std::fs::exists(\"x\").is_ok());\n}\n");
+        let programas: Vec<Fuente> = vec![
+            (a, EDICION, OPCIONES),
+            (b, "2024", OPCIONES),
+            (c, EDICION, &["-O", "-A", "warnings"]),
+            (d, EDICION, OPCIONES),
+            (fs, EDICION, OPCIONES),
+        ];
+        let resultados: Vec<Ejecutable> = compilar(&programas)
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        let salidas: Vec<String> = resultados.iter().map(stdout).collect();
+        assert_eq!(salidas, ["a\n", "b\n", "c\n", "d\n", "true\n"]);
+        // `a` y `d` comparten lote; `b` y `c` van en otros y `fs` va solo.
+        assert!(Arc::ptr_eq(
+            &resultados[0].programa,
+            &resultados[3].programa
+        ));
+        assert!(!Arc::ptr_eq(
+            &resultados[0].programa,
+            &resultados[1].programa
+        ));
+        assert!(!Arc::ptr_eq(
+            &resultados[0].programa,
+            &resultados[2].programa
+        ));
+        assert_eq!(resultados[4].modulo, None);
+    }
+}
@@ -0,0 +1,124 @@
+//! `libro`: línea de comandos de las herramientas del libro.
+//!
+//! ```text
+//! libro generar [--bless] [generador...]   regenera build/generado/
+//! libro comprobar                         verifica con el toolchain fijado
+//! ```
+
+use std::process::ExitCode;
+
+use libro::{
+    Libro, Resultado, afirmaciones, arboles, booleanos, capacidad, caracteres, diagnosticos,
+    ediciones, ejemplos, enteros, escenarios, estilo, etapas, flotantes, memoria, msrv, panicos,
+    salidas, transcripciones, verificacion,
+};
+
+type Generador = fn(&Libro) -> Resultado<usize>;
+
+/// Generadores de `libro generar`, en el orden en que se ejecutan.
+const GENERADORES: &[(&str, Generador)] = &[
+    ("memoria", memoria::generar),
+    ("capacidad", capacidad::generar),
+    ("enteros", enteros::generar),
+    ("flotantes", flotantes::generar),
+    ("caracteres", caracteres::generar),
+    ("booleanos", booleanos::generar),
+    ("afirmaciones", afirmaciones::comprobar),
+    ("diagnosticos", diagnosticos::generar),
+    ("panicos", panicos::generar),
+    ("etapas", etapas::generar),
+    ("cargo", transcripciones::generar),
+    ("arboles", arboles::generar),
+    ("ediciones", ediciones::generar),
+    ("msrv", msrv::generar),
+    ("escenarios", escenarios::generar),
+    ("salidas", salidas::generar),
+    ("ejemplos", ejemplos::generar),
+    ("estilo", estilo::generar),
+];
+
+const AYUDA: &str = "\
+uso: libro <comando>
+
+comandos:
+  generar [generador...]   regenera build/generado/ (todos si no se indica)
+  comprobar                compara las versiones con rust-toolchain.toml (avisa
+                           si no coinciden), ejecuta todos los generadores y
+                           registra la verificación para el colofón (alias:
+                           check)
+
+opciones de generar:
+  --bless                  acepta los diagnósticos actuales de rustc como
+                           instantáneas (herramientas/diagnosticos/)
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match ejecutar(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn ejecutar(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut libro = Libro::encontrar(&std::env::current_dir()?)?;
+    match args.split_first() {
+        Some((comando, resto)) if comando == "generar" => {
+            libro.bless = resto.iter().any(|a| a == "--bless");
+            let nombres: Vec<String> = resto.iter().filter(|a| *a != "--bless").cloned().collect();
+            generar(&libro, &nombres)
+        }
+        Some((comando, [])) if comando == "comprobar" || comando == "check" => comprobar(&libro),
+        Some((comando, _)) if comando != "-h" && comando != "--help" => {
+            Err(format!("comando desconocido `{comando}`\n\n{AYUDA}").into())
+        }
+        _ => {
+            print!("{AYUDA}");
+            Ok(())
+        }
+    }
+}
+
+fn comprobar(libro: &Libro) -> Result<(), Box<dyn std::error::Error>> {
+    let fijadas = verificacion::Fijadas::leer(libro)?;
+    let instaladas = verificacion::Instaladas::detectar()?;
+    let distintas = verificacion::comparar(&fijadas, &instaladas);
+    for d in &distintas {
+        eprintln!(
+            "aviso: {} {} no es la versión fijada ({}); se verifica igual y \
+             el colofón lo indica",
+            d.nombre, d.instalada, d.fijada
+        );
+    }
+    if instaladas.typst.is_none() {
+        eprintln!("aviso: typst no está instalado; no se comprueba su versión");
+    }
+    generar(libro, &[])?;
+    let registro = verificacion::registrar(libro, &fijadas, &instaladas, &distintas)?;
+    let registro = registro.strip_prefix(&libro.raiz).unwrap_or(&registro);
+    println!(
+        "verificado con {}: {}",
+        instaladas.rustc,
+        registro.display()
+    );
+    Ok(())
+}
+
+fn generar(libro: &Libro, nombres: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(n) = nombres
+        .iter()
+        .find(|n| !GENERADORES.iter().any(|(g, _)| g == n))
+    {
+        return Err(format!("generador desconocido `{n}`").into());
+    }
+    for (nombre, generador) in GENERADORES {
+        if nombres.is_empty() || nombres.iter().any(|n| n == nombre) {
+            let n = generador(libro)?;
+            println!("{nombre:>12}: {n}");
+        }
+    }
+    Ok(())
+}
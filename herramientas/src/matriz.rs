@@ -0,0 +1,72 @@
+//! Tablas «filas × columnas» que imprimen las sondas.
+//!
+//! Una sonda escribe una línea por celda, separada por tabuladores:
+//!
+//! ```text
+//! celda <tabla> <fila> <columna> <valor>
+//! ```
+//!
+//! Filas y columnas se muestran en el orden en que aparecen por primera vez.
+//! Las líneas que no empiezan por `celda` se ignoran, así una sonda puede
+//! mezclar matrices con otros registros.
+
+use crate::{Error, Resultado};
+
+#[derive(Debug, Default)]
+pub struct Matriz {
+    pub nombre: String,
+    pub filas: Vec<String>,
+    pub columnas: Vec<String>,
+    /// (fila, columna, valor)
+    pub celdas: Vec<(String, String, String)>,
+}
+
+impl Matriz {
+    pub fn valor(&self, fila: &str, columna: &str) -> Option<&str> {
+        self.celdas
+            .iter()
+            .find(|(f, c, _)| f == fila && c == columna)
+            .map(|(_, _, v)| v.as_str())
+    }
+}
+
+/// Lee las matrices de la salida de `sonda` (el nombre solo se usa en los
+/// errores).
+pub fn parsear(salida: &str, sonda: &str) -> Resultado<Vec<Matriz>> {
+    let mut matrices: Vec<Matriz> = Vec::new();
+    for linea in salida.lines().filter(|l| l.starts_with("celda\t")) {
+        let ["celda", tabla, fila, columna, valor] = linea.split('\t').collect::<Vec<_>>()[..]
+        else {
+            return Err(Error::Libro(format!("sonda de {sonda}: `{linea}`")));
+        };
+        let indice = match matrices.iter().position(|m| m.nombre == tabla) {
+            Some(i) => i,
+            None => {
+                matrices.push(Matriz {
+                    nombre: tabla.to_string(),
+                    ..Matriz::default()
+                });
+                matrices.len() - 1
+            }
+        };
+        let matriz = &mut matrices[indice];
+        if !matriz.filas.iter().any(|f| f == fila) {
+            matriz.filas.push(fila.to_string());
+        }
+        if !matriz.columnas.iter().any(|c| c == columna) {
+            matriz.columnas.push(columna.to_string());
+        }
+        matriz
+            .celdas
+            .push((fila.to_string(), columna.to_string(), valor.to_string()));
+    }
+    Ok(matrices)
+}
+
+/// La matriz `nombre`, o un error si la sonda no la produjo.
+pub fn buscar<'a>(matrices: &'a [Matriz], nombre: &str, sonda: &str) -> Resultado<&'a Matriz> {
+    matrices
+        .iter()
+        .find(|m| m.nombre == nombre)
+        .ok_or_else(|| Error::Libro(format!("la sonda de {sonda} no produjo `{nombre}`")))
+}
@@ -0,0 +1,545 @@
+//! Diagramas de memoria generados a partir de ejecuciones reales.
+//!
+//! Los fragmentos marcados con `memoria` se instrumentan: después de cada
+//! `let` se inserta una sonda que imprime el nombre, tipo, tamaño,
+//! dirección y valor de la variable. Para `String` y `Vec` también imprime
+//! el triple `ptr`/`len`/`cap` y el contenido del buffer en el heap. Con
+//! esos datos se dibujan los nodos de `src/utils/stack.typ` y
+//! `src/utils/heap.typ`, así que el dibujo nunca puede contradecir al código
+//! que acompaña.
+
+use crate::cache::Compilaciones;
+use crate::fragmentos::{Fragmento, sin_comentario};
+use crate::{Error, Libro, Resultado, generado, typst};
+
+/// Separa `ptr`, `len`, `cap` y celdas en el campo de heap de la sonda.
+const SEP_CAMPO: char = '\u{1d}';
+/// Separa las celdas del buffer.
+const SEP_CELDA: char = '\u{1e}';
+/// Separa contenido y bytes dentro de una celda.
+const SEP_BYTES: char = '\u{1f}';
+
+/// Código que se antepone al fragmento instrumentado.
+///
+/// `Sonda` usa especialización por autorreferencia: si el tipo cumple el
+/// trait "positivo" (`Copy`, `Debug`, ser `String` o `Vec`) su método gana
+/// la resolución; si no, se usa el de la referencia.
+const PRELUDIO: &str = r#"#[allow(dead_code)]
+mod __libro {
+    pub struct Sonda<'a, T>(pub &'a T);
+
+    pub trait EsCopy { fn es_copy(&self) -> bool { true } }
+    impl<T: Copy> EsCopy for Sonda<'_, T> {}
+    pub trait NoCopy { fn es_copy(&self) -> bool { false } }
+    impl<T> NoCopy for &Sonda<'_, T> {}
+
+    pub trait ConDebug { fn valor(&self) -> String; }
+    impl<T: ::std::fmt::Debug> ConDebug for Sonda<'_, T> {
+        fn valor(&self) -> String { format!("{:?}", self.0) }
+    }
+    pub trait SinDebug { fn valor(&self) -> String { String::from("…") } }
+    impl<T> SinDebug for &Sonda<'_, T> {}
+
+    fn heap(ptr: *const u8, len: usize, cap: usize, celdas: Vec<String>) -> String {
+        format!("{ptr:p}\u{1d}{len}\u{1d}{cap}\u{1d}{}", celdas.join("\u{1e}"))
+    }
+
+    pub trait EnHeap { fn heap(&self) -> String; }
+    impl EnHeap for Sonda<'_, String> {
+        fn heap(&self) -> String {
+            let celdas = self.0.chars().map(|c| {
+                let mut buf = [0; 4];
+                let bytes: Vec<String> =
+                    c.encode_utf8(&mut buf).bytes().map(|b| format!("{b:02X}")).collect();
+                format!("{}\u{1f}{}", c.escape_debug(), bytes.join(" "))
+            });
+            heap(self.0.as_ptr(), self.0.len(), self.0.capacity(), celdas.collect())
+        }
+    }
+    impl<T: ::std::fmt::Debug> EnHeap for Sonda<'_, Vec<T>> {
+        fn heap(&self) -> String {
+            let tamano = ::std::mem::size_of::<T>();
+            let celdas = self.0.iter().map(|e| format!("{e:?}\u{1f}{tamano} B"));
+            heap(self.0.as_ptr().cast(), self.0.len(), self.0.capacity(), celdas.collect())
+        }
+    }
+    pub trait SinHeap { fn heap(&self) -> String { String::from("-") } }
+    impl<T> SinHeap for &Sonda<'_, T> {}
+}
+
+macro_rules! __libro_sonda {
+    ($nombre:ident, $origen:expr) => {{
+        #[allow(unused_imports)]
+        use __libro::{ConDebug as _, EnHeap as _, EsCopy as _, NoCopy as _, SinDebug as _, SinHeap as _};
+        println!(
+            "__libro\t{}\t{}\t{}\t{:p}\t{}\t{}\t{}\t{}",
+            stringify!($nombre),
+            ::std::any::type_name_of_val(&$nombre),
+            ::std::mem::size_of_val(&$nombre),
+            &$nombre,
+            (&__libro::Sonda(&$nombre)).es_copy(),
+            $origen,
+            (&__libro::Sonda(&$nombre)).heap(),
+            (&__libro::Sonda(&$nombre)).valor(),
+        );
+    }};
+}
+
+"#;
+
+/// Una variable local observada durante la ejecución.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    pub nombre: String,
+    /// Tipo sin rutas de módulo: `String`, no `alloc::string::String`.
+    pub tipo: String,
+    /// Tamaño en el stack, en bytes.
+    pub tamano: usize,
+    pub direccion: String,
+    /// Valor con formato `{:?}`.
+    pub valor: String,
+    pub es_copy: bool,
+    pub origen: Option<Origen>,
+    /// Buffer en el heap, para `String` y `Vec`.
+    pub heap: Option<Heap>,
+}
+
+/// De qué otra variable se inicializó una variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origen {
+    /// `let b = a;`: copia o move según `a` sea `Copy`.
+    Asignacion(String),
+    /// `let b = a.clone();`
+    Clon(String),
+}
+
+impl Origen {
+    pub fn variable(&self) -> &str {
+        match self {
+            Origen::Asignacion(v) | Origen::Clon(v) => v,
+        }
+    }
+}
+
+/// El triple que un `String` o `Vec` guarda en el stack y su buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heap {
+    pub ptr: String,
+    pub len: usize,
+    pub cap: usize,
+    /// Una celda por carácter o elemento: (contenido, bytes).
+    pub celdas: Vec<(String, String)>,
+}
+
+impl Heap {
+    fn parsear(campo: &str) -> Option<Heap> {
+        let mut partes = campo.split(SEP_CAMPO);
+        let ptr = partes.next()?.to_string();
+        let len = partes.next()?.parse().ok()?;
+        let cap = partes.next()?.parse().ok()?;
+        let celdas = partes
+            .next()?
+            .split(SEP_CELDA)
+            .filter(|c| !c.is_empty())
+            .filter_map(|c| c.split_once(SEP_BYTES))
+            .map(|(contenido, bytes)| (contenido.to_string(), bytes.to_string()))
+            .collect();
+        Some(Heap {
+            ptr,
+            len,
+            cap,
+            celdas,
+        })
+    }
+}
+
+/// Una declaración `let nombre = expr;` de una sola línea.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Let {
+    pub nombre: String,
+    pub expresion: String,
+}
+
+/// Interpreta una línea `let [mut] nombre[: Tipo] = expr;`, con o sin
+/// comentario al final. `None` si el patrón no es un nombre solo (`let (a,
+/// b) = ...`, `let Some(x) = ... else`, `let _ = ...`): la sonda no sabría
+/// qué medir.
+pub fn parsear_let(linea: &str) -> Option<Let> {
+    let resto = sin_comentario(linea)
+        .trim()
+        .strip_prefix("let ")?
+        .strip_suffix(';')?;
+    let resto = resto
+        .trim_start()
+        .strip_prefix("mut ")
+        .unwrap_or(resto)
+        .trim_start();
+    let fin = resto
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(resto.len());
+    let nombre = &resto[..fin];
+    if nombre.is_empty() || nombre == "_" || nombre.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let tras = resto[fin..].trim_start();
+    if !tras.starts_with([':', '=']) {
+        return None;
+    }
+    let (_, expresion) = tras.split_once('=')?;
+    Some(Let {
+        nombre: nombre.to_string(),
+        expresion: expresion.trim().to_string(),
+    })
+}
+
+/// Inserta una sonda después de cada `let` del programa.
+pub fn instrumentar(programa: &str) -> String {
+    let mut salida = String::from(PRELUDIO);
+    for linea in programa.lines() {
+        salida.push_str(linea);
+        salida.push('\n');
+        if let Some(decl) = parsear_let(linea) {
+            let sangria = &linea[..linea.len() - linea.trim_start().len()];
+            salida.push_str(&format!(
+                "{sangria}__libro_sonda!({}, {:?});\n",
+                decl.nombre, decl.expresion
+            ));
+        }
+    }
+    salida
+}
+
+/// Ejecuta el fragmento instrumentado y devuelve sus variables en orden.
+pub fn medir(fragmento: &Fragmento, compilaciones: &mut Compilaciones) -> Resultado<Vec<Variable>> {
+    let programa = fragmento.programa();
+    let stdout = compilaciones
+        .ejecutar(&instrumentar(&programa), &[])
+        .map_err(|e| Error::Libro(format!("{}: {e}", fragmento.ubicacion())))?;
+
+    let mut variables: Vec<Variable> = Vec::new();
+    for linea in stdout.lines() {
+        let Some(resto) = linea.strip_prefix("__libro\t") else {
+            continue;
+        };
+        let campos: Vec<&str> = resto.splitn(8, '\t').collect();
+        let [
+            nombre,
+            tipo,
+            tamano,
+            direccion,
+            es_copy,
+            expresion,
+            heap,
+            valor,
+        ] = campos[..]
+        else {
+            return Err(Error::Libro(format!("sonda mal formada: {linea}")));
+        };
+        let origen = origen(expresion, &variables);
+        variables.push(Variable {
+            nombre: nombre.to_string(),
+            tipo: tipo_corto(tipo),
+            tamano: tamano.parse().unwrap_or_default(),
+            direccion: direccion.to_string(),
+            valor: valor.to_string(),
+            es_copy: es_copy == "true",
+            origen,
+            heap: Heap::parsear(heap),
+        });
+    }
+    Ok(variables)
+}
+
+fn origen(expresion: &str, anteriores: &[Variable]) -> Option<Origen> {
+    let existe = |nombre: &str| anteriores.iter().any(|v| v.nombre == nombre);
+    if existe(expresion) {
+        return Some(Origen::Asignacion(expresion.to_string()));
+    }
+    expresion
+        .strip_suffix(".clone()")
+        .filter(|v| existe(v))
+        .map(|v| Origen::Clon(v.to_string()))
+}
+
+/// Quita las rutas de módulo: `alloc::vec::Vec<i32>` → `Vec<i32>`.
+pub fn tipo_corto(tipo: &str) -> String {
+    let mut salida = String::new();
+    let mut inicio_palabra = 0;
+    let mut caracteres = tipo.chars().peekable();
+    while let Some(c) = caracteres.next() {
+        if c == ':' && caracteres.peek() == Some(&':') {
+            caracteres.next();
+            salida.truncate(inicio_palabra);
+            continue;
+        }
+        salida.push(c);
+        if !(c.is_alphanumeric() || c == '_') {
+            inicio_palabra = salida.len();
+        }
+    }
+    salida
+}
+
+/// Texto de tamaño: `1 byte`, `24 bytes`.
+pub fn bytes(n: usize) -> String {
+    if n == 1 {
+        String::from("1 byte")
+    } else {
+        format!("{n} bytes")
+    }
+}
+
+const COLUMNAS: usize = 3;
+
+/// Posición de la variable `i`. Si hay datos en el heap, cada fila del
+/// stack deja debajo una fila para sus buffers.
+fn posicion(i: usize, con_heap: bool) -> (usize, usize) {
+    let fila = i / COLUMNAS;
+    (i % COLUMNAS, if con_heap { fila * 2 } else { fila })
+}
+
+/// Diagrama de stack (y heap, si lo hay) para las variables medidas.
+pub fn diagrama(id: &str, variables: &[Variable]) -> String {
+    let con_heap = variables.iter().any(|v| v.heap.is_some());
+    let columnas = variables.len().clamp(1, COLUMNAS);
+    let filas = variables.len().div_ceil(COLUMNAS).max(1);
+    let centro = (columnas - 1) as f32 / 2.0;
+
+    // Una variable movida a otra queda inválida.
+    let movida = |i: usize| {
+        let var = &variables[i];
+        !var.es_copy
+            && variables[i + 1..]
+                .iter()
+                .any(|v| matches!(&v.origen, Some(Origen::Asignacion(o)) if *o == var.nombre))
+    };
+
+    let mut nodos = Vec::new();
+    nodos.push(format!(
+        "node(({centro}, -0.5), text(size: 11pt, weight: \"bold\", fill: memory-blue)[STACK])"
+    ));
+
+    let mut notas = Vec::new();
+    let mut caption = String::from("Representación del stack de memoria en Rust");
+    for (i, var) in variables.iter().enumerate() {
+        let (x, y) = posicion(i, con_heap);
+        let valida = !movida(i);
+        match &var.heap {
+            Some(heap) => {
+                nodos.push(format!(
+                    "stack-box(({x}, {y}), {}, {}, {}, {}, valid: {valida})",
+                    typst::texto(&var.nombre),
+                    typst::texto(&heap.ptr),
+                    typst::texto(&heap.len.to_string()),
+                    typst::texto(&heap.cap.to_string()),
+                ));
+                if valida {
+                    let celdas: Vec<String> = heap
+                        .celdas
+                        .iter()
+                        .map(|(c, b)| format!("({}, {})", typst::texto(c), typst::texto(b)))
+                        .collect();
+                    nodos.push(format!(
+                        "heap-box(({x}, {}), ({}{}), {}, libres: {})",
+                        y + 1,
+                        celdas.join(", "),
+                        if celdas.len() == 1 { "," } else { "" },
+                        typst::texto(&heap.ptr),
+                        heap.cap - heap.len,
+                    ));
+                    nodos.push(format!(
+                        "edge(({x}, {y}), ({x}, {}), \"-|>\", label: text(size: 7pt)[ptr], \
+                         stroke: heap-green + 1.5pt)",
+                        y + 1
+                    ));
+                }
+                if let Some((c, b)) = heap.celdas.iter().find(|(_, b)| b.contains(' ')) {
+                    let nota = format!(
+                        "`{c}` ocupa {} bytes en UTF-8 (`{b}`): `len` cuenta bytes, no caracteres.",
+                        b.split(' ').count()
+                    );
+                    if var.tipo == "String" && !notas.contains(&nota) {
+                        notas.push(nota);
+                    }
+                }
+            }
+            None => nodos.push(format!(
+                "mem-cell(({x}, {y}), {}, {}, {}, tipo: {}, tamano: {})",
+                typst::texto(&var.nombre),
+                typst::texto(&var.valor),
+                typst::texto(&var.direccion),
+                typst::codigo(&var.tipo),
+                typst::texto(&bytes(var.tamano)),
+            )),
+        }
+
+        let Some(origen) = &var.origen else { continue };
+        let Some(j) = variables[..i]
+            .iter()
+            .rposition(|v| v.nombre == origen.variable())
+        else {
+            continue;
+        };
+        let (ox, oy) = posicion(j, con_heap);
+        let fuente = &variables[j];
+        let (accion, nota) = match origen {
+            Origen::Clon(_) => (
+                "Clone",
+                format!(
+                    "`{}.clone()` reserva un buffer nuevo en el heap para `{}`.",
+                    fuente.nombre, var.nombre
+                ),
+            ),
+            Origen::Asignacion(_) if fuente.es_copy => (
+                "Copy",
+                format!(
+                    "`{}` se copia en `{}` porque `{}` implementa `Copy`.",
+                    fuente.nombre, var.nombre, fuente.tipo
+                ),
+            ),
+            Origen::Asignacion(_) => {
+                caption = format!(
+                    "Move semántico: el ownership se transfiere de `{}` a `{}`",
+                    fuente.nombre, var.nombre
+                );
+                (
+                    "Move",
+                    format!(
+                        "`{}` se mueve a `{}`: `{}` no implementa `Copy`. Solo se copian \
+                         `ptr`, `len` y `cap`; `{}` queda inválido.",
+                        fuente.nombre, var.nombre, fuente.tipo, fuente.nombre
+                    ),
+                )
+            }
+        };
+        let color = if accion == "Move" {
+            "rust-orange"
+        } else {
+            "black"
+        };
+        nodos.push(format!(
+            "edge(({ox}, {oy}), ({x}, {y}), \"-|>\", \
+             label: text(size: 8pt, fill: {color}, weight: \"bold\")[{accion}], stroke: {color} + 1.5pt)"
+        ));
+        notas.push(nota);
+    }
+
+    if con_heap {
+        nodos.push(format!(
+            "node(({}, 1), text(size: 11pt, weight: \"bold\", fill: heap-green)[HEAP])",
+            columnas as f32 - 0.2
+        ));
+        if caption.starts_with("Representación") {
+            caption = String::from("Representación del stack y el heap en Rust");
+        }
+    }
+
+    if !notas.is_empty() {
+        let y = if con_heap { filas * 2 } else { filas };
+        nodos.push(format!(
+            "node(({centro}, {y}), rect(fill: yellow.lighten(80%), inset: 6pt, radius: 4pt, \
+             text(size: 8pt, style: \"italic\")[{}]))",
+            notas.join(" \\ ")
+        ));
+    }
+
+    let mut salida = String::from(generado::CABECERA);
+    salida.push_str(
+        "#import \"@preview/fletcher:0.5.8\" as fletcher: diagram, node, edge\n\
+         #import \"/src/utils/stack.typ\": mem-cell, memory-blue, rust-orange\n\
+         #import \"/src/utils/heap.typ\": stack-box, heap-box, heap-green\n\n",
+    );
+    salida.push_str(&format!(
+        "#figure(\n  kind: \"diagram\",\n  supplement: [Diagrama],\n  caption: [{caption}],\n\n  \
+         diagram(\n    spacing: (15mm, 10mm),\n    edge-stroke: 1.5pt,\n    \
+         edge-corner-radius: 5pt,\n    mark-scale: 80%,\n\n"
+    ));
+    for nodo in nodos {
+        salida.push_str("    ");
+        salida.push_str(&nodo);
+        salida.push_str(",\n");
+    }
+    salida.push_str(&format!("  )\n) <diag-{id}>\n"));
+    salida
+}
+
+/// Genera `build/generado/memoria/<id>.typ` para cada fragmento marcado.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut compilaciones = Compilaciones::leer(libro, "memoria")?;
+    let mut n = 0;
+    for fragmento in libro.fragmentos()? {
+        if !fragmento.atributos.tiene("memoria") {
+            continue;
+        }
+        let Some(id) = fragmento.id() else {
+            return Err(Error::Libro(format!(
+                "{}: un fragmento con `memoria` necesita `id=`",
+                fragmento.ubicacion()
+            )));
+        };
+        let variables = medir(&fragmento, &mut compilaciones)?;
+        generado::escribir(
+            libro,
+            format!("memoria/{id}.typ"),
+            &diagrama(id, &variables),
+        )?;
+        n += 1;
+    }
+    compilaciones.escribir(libro)?;
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn let_(nombre: &str, expresion: &str) -> Option<Let> {
+        Some(Let {
+            nombre: nombre.to_string(),
+            expresion: expresion.to_string(),
+        })
+    }
+
+    #[test]
+    fn parsear_let_con_nombre() {
+        assert_eq!(parsear_let("let x = 5;"), let_("x", "5"));
+        assert_eq!(
+            parsear_let("    let mut v: Vec<i32> = Vec::new(); // vacío"),
+            let_("v", "Vec::new()")
+        );
+        assert_eq!(
+            parsear_let("let iguales = a == b;"),
+            let_("iguales", "a == b")
+        );
+    }
+
+    #[test]
+    fn parsear_let_rechaza_patrones() {
+        for linea in [
+            "let (a, b) = (1, 2);",
+            "let Some(x) = opcion else { return };",
+            "let Punto { x, y } = p;",
+            "let [primero, ..] = lista;",
+            "let ref r = valor;",
+            "let _ = valor;",
+            "let x;",
+            "x = 5;",
+        ] {
+            assert_eq!(parsear_let(linea), None, "{linea}");
+        }
+    }
+
+    #[test]
+    fn tipo_corto_quita_las_rutas() {
+        assert_eq!(tipo_corto("i32"), "i32");
+        assert_eq!(tipo_corto("alloc::string::String"), "String");
+        assert_eq!(
+            tipo_corto("alloc::vec::Vec<alloc::string::String>"),
+            "Vec<String>"
+        );
+        assert_eq!(
+            tipo_corto("(&str, core::option::Option<u8>)"),
+            "(&str, Option<u8>)"
+        );
+    }
+}
@@ -0,0 +1,670 @@
+//! Versión mínima de Rust que necesita cada fragmento.
+//!
+//! Quien instala Rust desde su distribución puede tener un `rustc` de hace
+//! años. Los fragmentos que usan algo posterior a la edición 2021 (1.56),
+//! que el libro ya exige, llevan en el margen una insignia «desde Rust
+//! 1.xx».
+//!
+//! La versión sale de dos fuentes:
+//!
+//! - Sintaxis: una lista de construcciones del lenguaje con la versión en
+//!   que se estabilizaron ([`SINTAXIS`]).
+//! - Biblioteca estándar: el `#[stable(since = "...")]` de cada elemento,
+//!   tal como lo publica la documentación local de `std` (el componente
+//!   `rust-docs` de `rustup`). Se buscan las rutas `Tipo::elemento`, las
+//!   funciones `modulo::funcion`, las macros y las llamadas `.metodo(...)`.
+//!   Sin tipos no se sabe a qué tipo se llama un método, así que se toma la
+//!   versión más antigua entre los tipos candidatos: la insignia puede
+//!   quedarse corta, pero no exagera.
+//!
+//! Solo se analizan las líneas visibles: las ocultas (`# `) son andamiaje
+//! que el lector no copia.
+//!
+//! El resultado se escribe en `build/generado/msrv.typ`, un diccionario del
+//! código de cada fragmento a su versión, que usa la regla `show raw` de
+//! `src/config/global.typ`. Sin `rust-docs` se avisa y el diccionario queda
+//! vacío.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::fragmentos::sin_comentario;
+use crate::{Libro, Resultado, generado, rustc, typst};
+
+/// Hasta esta versión, incluida, no se marca: es la que exige la edición
+/// 2021.
+pub const UMBRAL: Version = Version(1, 56, 0);
+
+/// Una versión de Rust, `1.58.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(pub u32, pub u32, pub u32);
+
+impl Version {
+    /// Interpreta `1.58.0` (o `1.58`).
+    pub fn parsear(texto: &str) -> Option<Version> {
+        let mut partes = texto.trim().split('.').map(|p| p.parse::<u32>().ok());
+        Some(Version(
+            partes.next()??,
+            partes.next()??,
+            partes.next().flatten().unwrap_or(0),
+        ))
+    }
+
+    /// `1.58`, la forma en que se habla de una versión de Rust.
+    pub fn corta(&self) -> String {
+        format!("{}.{}", self.0, self.1)
+    }
+}
+
+/// Una construcción del lenguaje y la versión que la estabilizó.
+pub struct Construccion {
+    pub nombre: &'static str,
+    pub desde: Version,
+    pub detectar: fn(&str) -> bool,
+}
+
+pub const SINTAXIS: &[Construccion] = &[
+    Construccion {
+        nombre: "argumentos con nombre en `format!` (`{x}`)",
+        desde: Version(1, 58, 0),
+        detectar: argumentos_en_linea,
+    },
+    Construccion {
+        nombre: "`let ... else`",
+        desde: Version(1, 65, 0),
+        detectar: let_else,
+    },
+    Construccion {
+        nombre: "bloques con etiqueta (`'a: {`)",
+        desde: Version(1, 65, 0),
+        detectar: bloque_etiquetado,
+    },
+    Construccion {
+        nombre: "`if let ... && ...`",
+        desde: Version(1, 88, 0),
+        detectar: cadena_de_let,
+    },
+];
+
+const MACROS_DE_FORMATO: &[&str] = &[
+    "println!(",
+    "print!(",
+    "eprintln!(",
+    "eprint!(",
+    "format!(",
+    "write!(",
+    "writeln!(",
+    "panic!(",
+];
+
+/// `println!("{x}")`: un `{` seguido de un identificador en la cadena de
+/// formato (no `{0}`, `{}` ni `{{`).
+fn argumentos_en_linea(codigo: &str) -> bool {
+    codigo.lines().map(sin_comentario).any(|linea| {
+        MACROS_DE_FORMATO.iter().any(|macro_| {
+            let Some(inicio) = linea.find(macro_) else {
+                return false;
+            };
+            let resto = &linea[inicio + macro_.len()..];
+            let Some(cadena) = resto.split('"').nth(1) else {
+                return false;
+            };
+            let mut caracteres = cadena.chars().peekable();
+            while let Some(c) = caracteres.next() {
+                if c == '{' {
+                    match caracteres.peek() {
+                        Some('{') => {
+                            caracteres.next();
+                        }
+                        Some(&s) if s.is_alphabetic() || s == '_' => return true,
+                        _ => {}
+                    }
+                }
+            }
+            false
+        })
+    })
+}
+
+/// `let Some(x) = opcion else { ... };`, pero no `let x = if c { a } else { b };`.
+fn let_else(codigo: &str) -> bool {
+    codigo.lines().map(sin_comentario).any(|linea| {
+        let linea = linea.trim();
+        let Some(resto) = linea.strip_prefix("let ") else {
+            return false;
+        };
+        let Some((_, derecha)) = resto.split_once('=') else {
+            return false;
+        };
+        match derecha.split_once(" else") {
+            Some((valor, _)) => !valor.contains("if ") && !valor.contains('{'),
+            None => false,
+        }
+    })
+}
+
+/// `'etiqueta: {`, un bloque (no un bucle) con etiqueta.
+fn bloque_etiquetado(codigo: &str) -> bool {
+    codigo.lines().map(sin_comentario).any(|linea| {
+        let linea = linea.trim();
+        linea.starts_with('\'')
+            && linea.split_once(':').is_some_and(|(etiqueta, resto)| {
+                etiqueta[1..]
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_')
+                    && resto.trim_start().starts_with('{')
+            })
+    })
+}
+
+/// `if let Some(x) = a && x > 0`.
+fn cadena_de_let(codigo: &str) -> bool {
+    codigo.lines().map(sin_comentario).any(|linea| {
+        ["if let ", "while let "].iter().any(|inicio| {
+            linea
+                .find(inicio)
+                .is_some_and(|i| linea[i..].contains(" && "))
+        }) || linea.contains("&& let ")
+    })
+}
+
+/// Un elemento documentado de `std`.
+#[derive(Debug, Clone)]
+struct Elemento {
+    /// `is_some_and`, `MAX`, `from`.
+    nombre: String,
+    /// `id` del `impl` que lo contiene, si es de un trait.
+    bloque: String,
+    desde: Version,
+}
+
+/// Una página de la documentación: un tipo, trait o primitivo y sus
+/// elementos.
+#[derive(Debug, Clone)]
+struct Pagina {
+    desde: Option<Version>,
+    elementos: Vec<Elemento>,
+}
+
+/// La documentación local de `std`.
+pub struct Documentacion {
+    /// Nombre (`HashMap`, `i32`, `array::from_fn`, `matches!`) → archivos.
+    indice: HashMap<String, Vec<PathBuf>>,
+    paginas: HashMap<PathBuf, Pagina>,
+}
+
+/// Versión de un atributo `title="Stable since Rust version 1.70.0, ..."`.
+fn estable_desde(html: &str) -> Option<Version> {
+    const MARCA: &str = "Stable since Rust version ";
+    let inicio = html.find(MARCA)? + MARCA.len();
+    let fin = html[inicio..].find([',', '"'])? + inicio;
+    Version::parsear(&html[inicio..fin])
+}
+
+/// Lee las secciones `<section id="...">` de una página de rustdoc. Los
+/// métodos de un `impl` de trait no llevan versión: heredan la del `impl`.
+fn leer_pagina(html: &str) -> Pagina {
+    let mut secciones = html.split("<section id=\"");
+    let cabecera = secciones.next().unwrap_or_default();
+    let mut pagina = Pagina {
+        desde: cabecera
+            .find("<h1")
+            .and_then(|i| estable_desde(&cabecera[i..])),
+        elementos: Vec::new(),
+    };
+    let mut bloque = (String::new(), None);
+    for seccion in secciones {
+        let Some((id, resto)) = seccion.split_once('"') else {
+            continue;
+        };
+        // La versión está en la cabecera de la sección, antes del código.
+        let cabecera = &resto[..resto.find("</h").unwrap_or(resto.len())];
+        let desde = estable_desde(cabecera);
+        if let Some(nombre) = id.strip_prefix("impl-") {
+            bloque = (nombre.to_string(), desde);
+            continue;
+        }
+        let Some((_, nombre)) = id.split_once('.') else {
+            continue;
+        };
+        // `method.from-3`: el sufijo distingue los de varios `impl`.
+        let nombre = match nombre.rsplit_once('-') {
+            Some((base, n)) if n.bytes().all(|b| b.is_ascii_digit()) => base,
+            _ => nombre,
+        };
+        if let Some(desde) = desde.or(bloque.1) {
+            pagina.elementos.push(Elemento {
+                nombre: nombre.to_string(),
+                bloque: bloque.0.clone(),
+                desde,
+            });
+        }
+    }
+    pagina
+}
+
+impl Documentacion {
+    /// Indexa la documentación de `std` del toolchain activo, o `None` si
+    /// no está instalada.
+    pub fn abrir() -> Resultado<Option<Documentacion>> {
+        let salida = Command::new(rustc::rustc())
+            .args(["--print", "sysroot"])
+            .output()?;
+        let raiz = PathBuf::from(String::from_utf8_lossy(&salida.stdout).trim())
+            .join("share/doc/rust/html/std");
+        if !raiz.is_dir() {
+            return Ok(None);
+        }
+        let mut indice: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        indexar(&raiz, &mut indice)?;
+        Ok(Some(Documentacion {
+            indice,
+            paginas: HashMap::new(),
+        }))
+    }
+
+    fn paginas(&mut self, nombre: &str) -> Resultado<Vec<&Pagina>> {
+        let rutas = self.indice.get(nombre).cloned().unwrap_or_default();
+        for ruta in &rutas {
+            if !self.paginas.contains_key(ruta) {
+                let pagina = leer_pagina(&fs::read_to_string(ruta)?);
+                self.paginas.insert(ruta.clone(), pagina);
+            }
+        }
+        Ok(rutas.iter().map(|r| &self.paginas[r]).collect())
+    }
+
+    /// La versión más antigua de `elemento` (o del propio tipo, si es
+    /// `None`) entre las páginas llamadas `nombre`. `filtro` restringe el
+    /// `impl` que lo contiene.
+    fn desde(
+        &mut self,
+        nombre: &str,
+        elemento: Option<&str>,
+        filtro: &str,
+    ) -> Resultado<Option<Version>> {
+        Ok(self
+            .paginas(nombre)?
+            .iter()
+            .flat_map(|p| match elemento {
+                None => p.desde.into_iter().collect::<Vec<_>>(),
+                Some(e) => p
+                    .elementos
+                    .iter()
+                    .filter(|x| x.nombre == e && x.bloque.contains(filtro))
+                    .map(|x| x.desde)
+                    .collect(),
+            })
+            .min())
+    }
+
+    fn existe(&self, nombre: &str) -> bool {
+        self.indice.contains_key(nombre)
+    }
+}
+
+fn indexar(dir: &Path, indice: &mut HashMap<String, Vec<PathBuf>>) -> Resultado<()> {
+    let modulo = dir
+        .file_name()
+        .map(|m| m.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    for entrada in fs::read_dir(dir)? {
+        let ruta = entrada?.path();
+        if ruta.is_dir() {
+            indexar(&ruta, indice)?;
+            continue;
+        }
+        let archivo = ruta
+            .file_name()
+            .map(|a| a.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let Some((tipo, nombre)) = archivo
+            .strip_suffix(".html")
+            .and_then(|a| a.split_once('.'))
+        else {
+            continue;
+        };
+        let clave = match tipo {
+            "struct" | "enum" | "trait" | "primitive" => nombre.to_string(),
+            "fn" => format!("{modulo}::{nombre}"),
+            "macro" => format!("{nombre}!"),
+            _ => continue,
+        };
+        indice.entry(clave).or_default().push(ruta);
+    }
+    Ok(())
+}
+
+/// El código sin comentarios y sin el contenido de las cadenas.
+fn limpiar(codigo: &str) -> String {
+    let mut limpio = String::new();
+    for linea in codigo.lines().map(sin_comentario) {
+        let mut en_cadena = false;
+        let mut anterior = ' ';
+        for c in linea.chars() {
+            if c == '"' && anterior != '\\' {
+                en_cadena = !en_cadena;
+                limpio.push(c);
+            } else if !en_cadena {
+                limpio.push(c);
+            }
+            anterior = if anterior == '\\' { ' ' } else { c };
+        }
+        limpio.push('\n');
+    }
+    limpio
+}
+
+/// Un uso de `std` encontrado en el código.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Uso {
+    /// `Tipo::elemento`, con el carácter que sigue a `(` (`[` en
+    /// `HashMap::from([...])`).
+    Ruta(String, String, Option<char>),
+    /// `modulo::funcion`.
+    Funcion(String),
+    /// `.metodo(`.
+    Metodo(String),
+    /// `macro!`.
+    Macro(String),
+}
+
+fn es_identificador(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn usos(codigo: &str) -> Vec<Uso> {
+    let codigo = limpiar(codigo);
+    let caracteres: Vec<char> = codigo.chars().collect();
+    let mut usos = Vec::new();
+    let mut i = 0;
+    while i < caracteres.len() {
+        if !es_identificador(caracteres[i]) || (i > 0 && es_identificador(caracteres[i - 1])) {
+            i += 1;
+            continue;
+        }
+        // Una ruta `a::b::c`.
+        let inicio = i;
+        let mut segmentos = Vec::new();
+        loop {
+            let desde = i;
+            while i < caracteres.len() && es_identificador(caracteres[i]) {
+                i += 1;
+            }
+            segmentos.push(caracteres[desde..i].iter().collect::<String>());
+            let sigue = caracteres.get(i..i + 2) == Some(&[':', ':'])
+                && caracteres.get(i + 2).is_some_and(|c| es_identificador(*c));
+            if !sigue {
+                break;
+            }
+            i += 2;
+        }
+        let siguiente = caracteres.get(i).copied();
+        let tras_parentesis = (siguiente == Some('('))
+            .then(|| {
+                caracteres[i + 1..]
+                    .iter()
+                    .find(|c| !c.is_whitespace())
+                    .copied()
+            })
+            .flatten();
+        if segmentos.len() == 1 {
+            let punto = inicio > 0
+                && caracteres[inicio - 1] == '.'
+                && (inicio < 2 || caracteres[inicio - 2] != '.');
+            if siguiente == Some('!') {
+                usos.push(Uso::Macro(segmentos.remove(0)));
+            } else if punto && matches!(siguiente, Some('(') | Some(':')) {
+                usos.push(Uso::Metodo(segmentos.remove(0)));
+            }
+            continue;
+        }
+        for par in segmentos.windows(2) {
+            usos.push(Uso::Funcion(format!("{}::{}", par[0], par[1])));
+        }
+        for (j, par) in segmentos.windows(2).enumerate() {
+            let ultimo = j + 2 == segmentos.len();
+            usos.push(Uso::Ruta(
+                par[0].clone(),
+                par[1].clone(),
+                if ultimo { tras_parentesis } else { None },
+            ));
+        }
+    }
+    usos
+}
+
+/// Tipos en los que se buscan los métodos de un fragmento, además de los
+/// que nombra.
+const TIPOS_COMUNES: &[&str] = &[
+    "Option", "Result", "Vec", "String", "str", "slice", "char", "i32", "f64", "u8", "usize",
+    "Iterator",
+];
+
+/// La versión mínima de un fragmento y qué la impone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Minima {
+    pub version: Version,
+    pub motivo: String,
+}
+
+/// Calcula la versión mínima de `codigo`.
+pub fn analizar(doc: &mut Documentacion, codigo: &str) -> Resultado<Option<Minima>> {
+    let mut candidatas: Vec<Minima> = SINTAXIS
+        .iter()
+        .filter(|c| (c.detectar)(codigo))
+        .map(|c| Minima {
+            version: c.desde,
+            motivo: c.nombre.to_string(),
+        })
+        .collect();
+
+    // Lo que el propio fragmento define no es de `std`.
+    let limpio = limpiar(codigo);
+    let propios: Vec<&str> = limpio
+        .split(|c: char| !es_identificador(c))
+        .collect::<Vec<_>>()
+        .windows(2)
+        .filter(|p| matches!(p[0], "fn" | "struct" | "enum" | "trait" | "type"))
+        .map(|p| p[1])
+        .collect();
+    let usos = usos(codigo);
+    let mut tipos: Vec<String> = TIPOS_COMUNES.iter().map(|t| t.to_string()).collect();
+    for palabra in limpio.split(|c: char| !es_identificador(c)) {
+        if !palabra.is_empty()
+            && !propios.contains(&palabra)
+            && doc.existe(palabra)
+            && !tipos.iter().any(|t| t == palabra)
+        {
+            tipos.push(palabra.to_string());
+        }
+    }
+
+    for uso in usos {
+        let (version, motivo) = match &uso {
+            Uso::Ruta(tipo, elemento, tras) if !propios.contains(&tipo.as_str()) => {
+                // `HashMap::from([...])` es `impl From<[(K, V); N]>`.
+                let filtro = if elemento == "from" && *tras == Some('[') {
+                    "From%3C%5B"
+                } else {
+                    ""
+                };
+                let version = match doc.desde(tipo, Some(elemento), filtro)? {
+                    Some(v) => Some(v),
+                    None => doc.desde(tipo, None, "")?,
+                };
+                (version, format!("`{tipo}::{elemento}`"))
+            }
+            Uso::Funcion(ruta) => (doc.desde(ruta, None, "")?, format!("`{ruta}`")),
+            Uso::Macro(nombre) => (
+                doc.desde(&format!("{nombre}!"), None, "")?,
+                format!("`{nombre}!`"),
+            ),
+            Uso::Metodo(nombre) if !propios.contains(&nombre.as_str()) => {
+                let mut version = None;
+                for tipo in &tipos {
+                    if let Some(v) = doc.desde(tipo, Some(nombre), "")? {
+                        version = Some(version.map_or(v, |actual: Version| actual.min(v)));
+                    }
+                }
+                (version, format!("`.{nombre}()`"))
+            }
+            _ => (None, String::new()),
+        };
+        if let Some(version) = version {
+            candidatas.push(Minima { version, motivo });
+        }
+    }
+    Ok(candidatas.into_iter().max_by_key(|m| m.version))
+}
+
+/// Genera `build/generado/msrv.typ` con la versión de los fragmentos que
+/// pasan de [`UMBRAL`].
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let mut entradas: Vec<(String, Minima)> = Vec::new();
+    match Documentacion::abrir()? {
+        Some(mut doc) => {
+            for fragmento in libro.fragmentos()? {
+                if fragmento.atributos.tiene("ignore") {
+                    continue;
+                }
+                let clave = fragmento.clave();
+                let Some(minima) = analizar(&mut doc, &clave)? else {
+                    continue;
+                };
+                if minima.version > UMBRAL && !entradas.iter().any(|(c, _)| *c == clave) {
+                    entradas.push((clave, minima));
+                }
+            }
+        }
+        None => eprintln!(
+            "aviso: no está la documentación local de std; no se marca la \
+             versión mínima de los fragmentos (`rustup component add rust-docs`)"
+        ),
+    }
+    let mut contenido = format!(
+        "{}// Versión mínima de Rust de cada fragmento, por su código.\n#let msrv = (\n",
+        generado::CABECERA
+    );
+    for (clave, minima) in &entradas {
+        contenido.push_str(&format!(
+            "  {}: (version: {}, motivo: {}),\n",
+            typst::cadena(clave),
+            typst::cadena(&minima.version.corta()),
+            typst::cadena(&minima.motivo)
+        ));
+    }
+    contenido.push_str(")\n");
+    generado::escribir(libro, "msrv.typ", &contenido)?;
+    Ok(entradas.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rustc::DirTemporal;
+
+    const OPTION: &str = r#"<h1>Enum <span class="enum">Option</span><span class="since" title="Stable since Rust version 1.0.0">1.0.0</span></h1>
+<section id="method.is_some_and" class="method"><span class="since rightside" title="Stable since Rust version 1.70.0">1.70.0</span><h4 class="code-header">pub fn is_some_and</h4></section>
+<section id="method.is_some" class="method"><span class="since rightside" title="Stable since Rust version 1.0.0, const since 1.48.0">1.0.0</span><h4 class="code-header">pub const fn is_some</h4></section>"#;
+
+    const VEC: &str = r#"<h1>Struct <span class="struct">Vec</span><span class="since" title="Stable since Rust version 1.0.0">1.0.0</span></h1>
+<section id="impl-From%3C%26%5BT%5D%3E-for-Vec%3CT%3E" class="impl"><span class="since rightside" title="Stable since Rust version 1.0.0">1.0.0</span><h3 class="code-header">impl From&lt;&amp;[T]&gt; for Vec&lt;T&gt;</h3></section>
+<section id="method.from" class="method trait-impl"><h4 class="code-header">fn from</h4></section>
+<section id="impl-From%3C%5BT;+N%5D%3E-for-Vec%3CT%3E" class="impl"><span class="since rightside" title="Stable since Rust version 1.44.0">1.44.0</span><h3 class="code-header">impl From&lt;[T; N]&gt; for Vec&lt;T&gt;</h3></section>
+<section id="method.from-1" class="method trait-impl"><h4 class="code-header">fn from</h4></section>"#;
+
+    fn documentacion(dir: &DirTemporal) -> Documentacion {
+        let raiz = dir.ruta().join("std");
+        fs::create_dir_all(raiz.join("option")).unwrap();
+        fs::create_dir_all(raiz.join("vec")).unwrap();
+        fs::write(raiz.join("option/enum.Option.html"), OPTION).unwrap();
+        fs::write(raiz.join("vec/struct.Vec.html"), VEC).unwrap();
+        let mut indice = HashMap::new();
+        indexar(&raiz, &mut indice).unwrap();
+        Documentacion {
+            indice,
+            paginas: HashMap::new(),
+        }
+    }
+
+    fn version(doc: &mut Documentacion, codigo: &str) -> Option<String> {
+        analizar(doc, codigo)
+            .unwrap()
+            .map(|m| format!("{} {}", m.version.corta(), m.motivo))
+    }
+
+    #[test]
+    fn parsear_version() {
+        assert_eq!(Version::parsear("1.58.0"), Some(Version(1, 58, 0)));
+        assert_eq!(Version::parsear(" 1.70 "), Some(Version(1, 70, 0)));
+        assert_eq!(Version::parsear("1"), None);
+        assert_eq!(Version::parsear("1.x.0"), None);
+        assert_eq!(Version(1, 58, 1).corta(), "1.58");
+    }
+
+    #[test]
+    fn las_versiones_se_ordenan_por_numero() {
+        assert!(Version(1, 9, 0) < Version(1, 10, 0));
+        assert!(Version(1, 56, 0) < Version(1, 56, 1));
+        assert!(Version(2, 0, 0) > Version(1, 88, 0));
+        assert_eq!(
+            [Version(1, 65, 0), Version(1, 58, 0)].into_iter().max(),
+            Some(Version(1, 65, 0))
+        );
+    }
+
+    #[test]
+    fn version_de_la_sintaxis() {
+        let dir = DirTemporal::nuevo("msrv").unwrap();
+        let mut doc = documentacion(&dir);
+        assert_eq!(
+            version(&mut doc, "let x = 1;\nprintln!(\"{x}\");").as_deref(),
+            Some("1.58 argumentos con nombre en `format!` (`{x}`)")
+        );
+        assert_eq!(
+            version(&mut doc, "let Some(x) = a else { return };").as_deref(),
+            Some("1.65 `let ... else`")
+        );
+        assert_eq!(
+            version(
+                &mut doc,
+                "let x = if c { 1 } else { 2 };\nprintln!(\"{}\", x);"
+            ),
+            None
+        );
+        assert_eq!(version(&mut doc, "// println!(\"{x}\")"), None);
+    }
+
+    #[test]
+    fn version_de_la_biblioteca() {
+        let dir = DirTemporal::nuevo("msrv").unwrap();
+        let mut doc = documentacion(&dir);
+        assert_eq!(
+            version(
+                &mut doc,
+                "let a = Some(3);\nlet b = a.is_some_and(|x| x > 2);"
+            )
+            .as_deref(),
+            Some("1.70 `.is_some_and()`")
+        );
+        assert_eq!(
+            version(&mut doc, "let v = Vec::from([1, 2]);").as_deref(),
+            Some("1.44 `Vec::from`")
+        );
+        assert_eq!(
+            version(&mut doc, "let v = Vec::from(&[1, 2][..]);").as_deref(),
+            Some("1.0 `Vec::from`")
+        );
+    }
+
+    #[test]
+    fn lo_definido_en_el_fragmento_no_es_de_std() {
+        let dir = DirTemporal::nuevo("msrv").unwrap();
+        let mut doc = documentacion(&dir);
+        let codigo = "fn is_some_and(x: u8) -> bool { x > 0 }\nlet b = 3.is_some_and();";
+        assert_eq!(version(&mut doc, codigo), None);
+    }
+}
@@ -0,0 +1,193 @@
+//! Transcripciones reales de programas que entran en pánico.
+//!
+//! Un fragmento marcado con `transcripcion` se copia como `src/main.rs` de
+//! un proyecto `cargo` desechable y se ejecuta como con `cargo run` (el
+//! binario corre aislado, ver [`crate::cargo::Proyecto::cargo`]). Con
+//! `transcripcion=backtrace` también se ejecuta con `RUST_BACKTRACE=1` y
+//! `RUST_BACKTRACE=full`. De cada backtrace solo quedan los marcos del
+//! propio programa; los de la biblioteca estándar se resumen con `...`.
+//!
+//! Se escribe `build/generado/panicos/<id>.typ` (y `<id>-backtrace.typ`,
+//! `<id>-full.typ`), con la versión de `rustc` usada al pie.
+
+use crate::cargo::{self, Proyecto};
+use crate::fragmentos::Fragmento;
+use crate::{Error, Libro, Resultado, generado, rustc, typst};
+
+/// Código de salida de un proceso que terminó por un pánico.
+const CODIGO_PANICO: i32 = 101;
+
+/// Una ejecución a capturar: sufijo del archivo y valor de `RUST_BACKTRACE`.
+const VARIANTES: &[(&str, Option<&str>)] = &[
+    ("", None),
+    ("-backtrace", Some("1")),
+    ("-full", Some("full")),
+];
+
+/// Deja en el backtrace los marcos de `mi_proyecto` y cambia cada tramo de
+/// marcos ajenos por una línea `...`.
+pub fn recortar(salida: &str) -> String {
+    let mut lineas = Vec::new();
+    let mut en_backtrace = false;
+    let mut conservar = false;
+    let mut omitido = false;
+    for linea in salida.lines() {
+        if linea == "stack backtrace:" {
+            en_backtrace = true;
+            lineas.push(linea.to_string());
+            continue;
+        }
+        let recortada = linea.trim_start();
+        let es_marco = recortada
+            .split_once(':')
+            .is_some_and(|(n, _)| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+        if en_backtrace && es_marco {
+            conservar = recortada.contains(&format!("{}::", cargo::PAQUETE));
+        } else if en_backtrace && !recortada.starts_with("at ") {
+            // Fin del backtrace (la nota final o la salida de cargo).
+            en_backtrace = false;
+            conservar = true;
+        }
+        if !en_backtrace || conservar {
+            if omitido {
+                lineas.push(String::from("   ..."));
+                omitido = false;
+            }
+            lineas.push(linea.to_string());
+        } else {
+            omitido = true;
+        }
+    }
+    if omitido {
+        lineas.push(String::from("   ..."));
+    }
+    lineas.join("\n")
+}
+
+/// Orden tal como la escribiría el lector.
+fn orden(backtrace: Option<&str>) -> String {
+    match backtrace {
+        Some(valor) => format!("$ RUST_BACKTRACE={valor} cargo run"),
+        None => String::from("$ cargo run"),
+    }
+}
+
+fn bloque(orden: &str, salida: &str, version: &str) -> String {
+    format!(
+        "{}#raw({}, block: true, lang: \"bash\")\n\
+         #raw({}, block: true, lang: \"yaml\")\n\
+         #align(right, text(size: 8pt)[Salida capturada con #{}])\n",
+        generado::CABECERA,
+        typst::cadena(orden),
+        typst::cadena(salida),
+        typst::codigo(version)
+    )
+}
+
+/// Ejecuta el fragmento en cada variante pedida y devuelve
+/// `(sufijo, orden, salida recortada)`.
+pub fn capturar(fragmento: &Fragmento) -> Resultado<Vec<(&'static str, String, String)>> {
+    let con_backtrace = fragmento.atributos.valor("transcripcion") == Some("backtrace");
+    let proyecto = Proyecto::nuevo(&fragmento.programa())?;
+    proyecto
+        .compilar()
+        .map_err(|e| Error::Libro(format!("{}: {e}", fragmento.ubicacion())))?;
+
+    let mut capturas = Vec::new();
+    for &(sufijo, backtrace) in VARIANTES {
+        if backtrace.is_some() && !con_backtrace {
+            continue;
+        }
+        let entorno: Vec<(&str, &str)> = backtrace
+            .map(|valor| ("RUST_BACKTRACE", valor))
+            .into_iter()
+            .collect();
+        let t = proyecto.cargo(&["run"], &entorno)?;
+        if t.codigo != Some(CODIGO_PANICO) {
+            return Err(Error::Libro(format!(
+                "{}: se esperaba un pánico (código {CODIGO_PANICO}), pero terminó con {:?}:\n{}",
+                fragmento.ubicacion(),
+                t.codigo,
+                t.salida
+            )));
+        }
+        capturas.push((sufijo, orden(backtrace), recortar(&t.salida)));
+    }
+    Ok(capturas)
+}
+
+/// Genera las transcripciones de `build/generado/panicos/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let version = rustc::version()?;
+    let mut n = 0;
+    for fragmento in libro.fragmentos()? {
+        if !fragmento.atributos.tiene("transcripcion") {
+            continue;
+        }
+        let Some(id) = fragmento.id() else {
+            return Err(Error::Libro(format!(
+                "{}: un fragmento con `transcripcion` necesita `id=...`",
+                fragmento.ubicacion()
+            )));
+        };
+        for (sufijo, orden, salida) in capturar(&fragmento)? {
+            let contenido = bloque(&orden, &salida, &version);
+            generado::escribir(libro, format!("panicos/{id}{sufijo}.typ"), &contenido)?;
+            n += 1;
+        }
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recortar_deja_los_marcos_del_proyecto() {
+        let salida = "\
+thread 'main' panicked at src/main.rs:3:5:
+boom
+stack backtrace:
+   0: std::panicking::begin_panic
+             at /rustc/abc/library/std/src/panicking.rs:1:1
+   1: core::panicking::panic_fmt
+   2: mi_proyecto::dividir
+             at ./src/main.rs:3:5
+   3: mi_proyecto::main
+             at ./src/main.rs:7:5
+   4: core::ops::function::FnOnce::call_once
+   5: std::rt::lang_start
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.";
+        assert_eq!(
+            recortar(salida),
+            "\
+thread 'main' panicked at src/main.rs:3:5:
+boom
+stack backtrace:
+   ...
+   2: mi_proyecto::dividir
+             at ./src/main.rs:3:5
+   3: mi_proyecto::main
+             at ./src/main.rs:7:5
+   ...
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace."
+        );
+    }
+
+    #[test]
+    fn recortar_sin_backtrace_no_cambia_nada() {
+        let salida = "thread 'main' panicked at src/main.rs:2:5:\nboom\nnote: run with \
+                      `RUST_BACKTRACE=1` environment variable to display a backtrace";
+        assert_eq!(recortar(salida), salida);
+    }
+
+    #[test]
+    fn recortar_un_backtrace_que_acaba_en_marcos_ajenos() {
+        let salida = "stack backtrace:\n   0: mi_proyecto::main\n   1: std::rt::lang_start";
+        assert_eq!(
+            recortar(salida),
+            "stack backtrace:\n   0: mi_proyecto::main\n   ..."
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Resultado de la verificación de cada fragmento, para las insignias del
+//! PDF.
+//!
+//! `libro generar ediciones` ya compila y ejecuta todos los fragmentos; con
+//! lo que ve en la edición de cada uno escribe `build/generado/resultados.json`,
+//! un objeto del código del bloque (como `raw.text`) a su estado:
+//!
+//! ```json
+//! {"let v = vec![10, 20, 30];\nlet x = v[5];": {"estado": "panico"}}
+//! ```
+//!
+//! La regla `show raw` de `src/config/global.typ` lo lee con `json()` y pone
+//! en la esquina de cada bloque «✓ compila», «✗ no compila (E0382)»,
+//! «⚠ panic» o «fragmento». Los fragmentos `ignore` no llevan insignia.
+//!
+//! Cada entrada lleva también los comentarios de salida, error y pánico del
+//! bloque ([`crate::comentarios`]), con su línea y columna, para que la
+//! plantilla los resalte: `"comentarios": [{"linea": 4, "columna": 23,
+//! "tipo": "salida"}]`. No se resaltan los bloques en los que el autor ya
+//! resalta líneas a mano.
+//!
+//! La insignia dice lo que pasó de verdad: un bloque que no compila lleva
+//! «✗ no compila» aunque no esté marcado (y entonces `libro generar` ya
+//! falla, [`crate::ediciones`]). «fragmento» es solo para los marcados con
+//! `fragment` y los archivos de un `proyecto=`; a un trozo que necesita las
+//! definiciones de un ejemplo anterior se le añaden como líneas ocultas
+//! (`# `, [`crate::fragmentos`]).
+
+use std::path::PathBuf;
+
+use crate::comentarios::{self, Comentario, Tipo};
+use crate::diagnosticos;
+use crate::ediciones::{Comportamiento, Fila};
+use crate::fragmentos::Fragmento;
+use crate::json::{self, Valor};
+use crate::rustc::EDICION;
+use crate::{Libro, Resultado, generado};
+
+/// Archivo (relativo a `build/generado/`) con los resultados.
+pub const ARCHIVO: &str = "resultados.json";
+
+/// Estado de un fragmento, tal como lo muestra la insignia.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Estado {
+    Compila,
+    /// No compila; el código de error de `rustc`, si lo tiene.
+    NoCompila(Option<String>),
+    Panico,
+    NoTermina,
+    DesbordaPila,
+    /// Un trozo de un programa mayor (`fragment` o un archivo de un
+    /// `proyecto=`).
+    Fragmento,
+}
+
+impl Estado {
+    /// Estado de `fragmento` según su comportamiento en su edición, o
+    /// `None` si no lleva insignia.
+    pub fn de(fragmento: &Fragmento, comportamiento: Option<&Comportamiento>) -> Option<Estado> {
+        if fragmento.atributos.tiene("ignore") {
+            return None;
+        }
+        let Some(comportamiento) = comportamiento else {
+            return Some(Estado::Fragmento);
+        };
+        Some(match comportamiento {
+            Comportamiento::NoCompila(error) => Estado::NoCompila(codigo_error(error)),
+            Comportamiento::Panico(_) => Estado::Panico,
+            Comportamiento::NoTermina => Estado::NoTermina,
+            Comportamiento::DesbordaPila => Estado::DesbordaPila,
+            Comportamiento::NoEjecutado | Comportamiento::Termina { .. } => Estado::Compila,
+        })
+    }
+
+    /// Nombre en el JSON.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Estado::Compila => "compila",
+            Estado::NoCompila(_) => "no_compila",
+            Estado::Panico => "panico",
+            Estado::NoTermina => "no_termina",
+            Estado::DesbordaPila => "desborda_pila",
+            Estado::Fragmento => "fragmento",
+        }
+    }
+
+    fn valor(&self, comentarios: &[Comentario]) -> Valor {
+        let mut pares = vec![(
+            String::from("estado"),
+            Valor::Cadena(self.nombre().to_string()),
+        )];
+        if let Estado::NoCompila(Some(codigo)) = self {
+            pares.push((String::from("error"), Valor::Cadena(codigo.clone())));
+        }
+        if !comentarios.is_empty() {
+            let comentarios = comentarios
+                .iter()
+                .map(|c| {
+                    Valor::Objeto(vec![
+                        (String::from("linea"), Valor::Numero(c.linea as f64)),
+                        (String::from("columna"), Valor::Numero(c.columna as f64)),
+                        (
+                            String::from("tipo"),
+                            Valor::Cadena(c.tipo.nombre().to_string()),
+                        ),
+                    ])
+                })
+                .collect();
+            pares.push((String::from("comentarios"), Valor::Lista(comentarios)));
+        }
+        Valor::Objeto(pares)
+    }
+}
+
+/// `E0382` de `error[E0382]: borrow of moved value`, aunque lleve colores
+/// ANSI.
+fn codigo_error(linea: &str) -> Option<String> {
+    let texto: String = diagnosticos::tramos(linea)
+        .into_iter()
+        .flatten()
+        .map(|t| t.texto)
+        .collect();
+    let resto = texto.trim_start().strip_prefix("error[")?;
+    let (codigo, _) = resto.split_once(']')?;
+    Some(codigo.to_string())
+}
+
+/// Escribe `build/generado/resultados.json`. `filas` son los fragmentos que
+/// se probaron; el resto de `fragmentos` no se compila suelto.
+pub fn escribir(libro: &Libro, fragmentos: &[Fragmento], filas: &[Fila]) -> Resultado<PathBuf> {
+    let mut entradas: Vec<(String, Estado, Vec<Comentario>)> = Vec::new();
+    for fragmento in fragmentos {
+        let comportamiento = filas
+            .iter()
+            .find(|f| std::ptr::eq(f.fragmento, fragmento))
+            .map(|fila| {
+                let edicion = fragmento.atributos.valor("edicion").unwrap_or(EDICION);
+                fila.en(edicion)
+            })
+            .transpose()?;
+        let Some(estado) = Estado::de(fragmento, comportamiento) else {
+            continue;
+        };
+        let comentarios = if fragmento.resaltado {
+            Vec::new()
+        } else {
+            comentarios::clasificar(&fragmento.codigo, comportamiento.and_then(|c| c.salida()))
+                .into_iter()
+                .filter(|c| c.tipo != Tipo::Explicacion)
+                .filter_map(|c| {
+                    let linea = fragmento.linea_visible(c.linea)?;
+                    Some(Comentario { linea, ..c })
+                })
+                .collect()
+        };
+        let clave = fragmento.clave();
+        if !entradas.iter().any(|(c, _, _)| *c == clave) {
+            entradas.push((clave, estado, comentarios));
+        }
+    }
+    // Una entrada por línea, para que los cambios se lean en un diff.
+    let lineas: Vec<String> = entradas
+        .iter()
+        .map(|(clave, estado, comentarios)| {
+            format!(
+                "  {}: {}",
+                json::cadena(clave),
+                estado.valor(comentarios).serializar()
+            )
+        })
+        .collect();
+    generado::escribir(libro, ARCHIVO, &format!("{{\n{}\n}}\n", lineas.join(",\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codigo_de_un_error() {
+        assert_eq!(
+            codigo_error("error[E0382]: borrow of moved value: `s`").as_deref(),
+            Some("E0382")
+        );
+    }
+
+    #[test]
+    fn sin_codigo_no_hay_codigo() {
+        assert_eq!(codigo_error("warning: unused variable: `x`"), None);
+        assert_eq!(codigo_error("error: could not compile `libro`"), None);
+        assert_eq!(codigo_error("note: see error[E0382] above"), None);
+    }
+
+    #[test]
+    fn codigo_con_colores_ansi() {
+        let linea = "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;9merror[E0499]\u{1b}[0m\u{1b}[0m\u{1b}[1m: \
+                     cannot borrow `v` as mutable more than once at a time\u{1b}[0m";
+        assert_eq!(codigo_error(linea).as_deref(), Some("E0499"));
+    }
+}
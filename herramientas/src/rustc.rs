@@ -0,0 +1,181 @@
+//! Compilación y ejecución de programas con el `rustc` local.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::aislado::{self, Informe, Limites, Terminacion};
+use crate::{Error, Resultado};
+
+/// Edición con la que se compilan los ejemplos del libro.
+pub const EDICION: &str = "2021";
+
+/// Ediciones en las que se prueban los ejemplos (ver `ediciones`).
+pub const EDICIONES: &[&str] = &["2021", "2024"];
+
+/// Directorio temporal que se borra al soltarse.
+#[derive(Debug)]
+pub struct DirTemporal {
+    ruta: PathBuf,
+}
+
+impl DirTemporal {
+    pub fn nuevo(prefijo: &str) -> Resultado<DirTemporal> {
+        static CONTADOR: AtomicUsize = AtomicUsize::new(0);
+        let n = CONTADOR.fetch_add(1, Ordering::Relaxed);
+        let ruta = std::env::temp_dir().join(format!("libro-{prefijo}-{}-{n}", std::process::id()));
+        if ruta.exists() {
+            fs::remove_dir_all(&ruta)?;
+        }
+        fs::create_dir_all(&ruta)?;
+        Ok(DirTemporal { ruta })
+    }
+
+    pub fn ruta(&self) -> &Path {
+        &self.ruta
+    }
+}
+
+impl Drop for DirTemporal {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.ruta);
+    }
+}
+
+/// Un programa ya compilado, listo para ejecutarse.
+#[derive(Debug)]
+pub struct Programa {
+    dir: DirTemporal,
+}
+
+impl Programa {
+    fn binario(&self) -> PathBuf {
+        self.dir.ruta().join("programa")
+    }
+
+    /// Ejecuta el programa y devuelve su stdout; falla si termina con error.
+    pub fn ejecutar(&self) -> Resultado<String> {
+        let ejecucion = self.correr()?;
+        if !ejecucion.exito {
+            return Err(Error::Ejecucion(format!(
+                "{}{}",
+                ejecucion.stderr, ejecucion.stdout
+            )));
+        }
+        Ok(ejecucion.stdout)
+    }
+
+    /// Ejecuta el programa, aislado y con los límites por defecto, sin
+    /// exigir que termine bien; falla si no termina a tiempo.
+    pub fn correr(&self) -> Resultado<Ejecucion> {
+        let informe = self.correr_con(&Limites::default())?;
+        if informe.terminacion == Terminacion::Agotado {
+            return Err(Error::Ejecucion(format!(
+                "no terminó en {} s:\n{}",
+                Limites::default().tiempo.as_secs(),
+                informe.stdout
+            )));
+        }
+        Ok(Ejecucion {
+            exito: informe.exito(),
+            stdout: informe.stdout,
+            stderr: informe.stderr,
+        })
+    }
+
+    /// Ejecuta el programa aislado, con `limites` (ver [`aislado`]).
+    pub fn correr_con(&self, limites: &Limites) -> Resultado<Informe> {
+        aislado::ejecutar(&mut self.orden(), limites)
+    }
+
+    /// Orden que ejecuta el programa en su directorio, para añadirle
+    /// argumentos o variables de entorno antes de pasarla a [`aislado`].
+    pub fn orden(&self) -> Command {
+        let mut orden = Command::new(self.binario());
+        orden.current_dir(self.dir.ruta());
+        orden
+    }
+}
+
+/// Resultado de ejecutar un programa.
+#[derive(Debug, Clone)]
+pub struct Ejecucion {
+    pub stdout: String,
+    pub stderr: String,
+    pub exito: bool,
+}
+
+/// Compila `fuente` como binario con la edición del libro.
+pub fn compilar(fuente: &str) -> Resultado<Programa> {
+    compilar_con(fuente, &[])
+}
+
+/// Como [`compilar`], con opciones extra para `rustc` (p. ej. `-O`).
+pub fn compilar_con(fuente: &str, opciones: &[&str]) -> Resultado<Programa> {
+    compilar_en(fuente, EDICION, opciones)
+}
+
+/// Como [`compilar_con`], en otra edición.
+pub fn compilar_en(fuente: &str, edicion: &str, opciones: &[&str]) -> Resultado<Programa> {
+    let dir = DirTemporal::nuevo("rustc")?;
+    let archivo = dir.ruta().join("main.rs");
+    fs::write(&archivo, fuente)?;
+
+    let salida = Command::new(rustc())
+        .args(["--edition", edicion, "-o", "programa", "main.rs"])
+        .args(opciones)
+        .current_dir(dir.ruta())
+        .output()?;
+    if !salida.status.success() {
+        return Err(Error::Compilacion(texto(&salida)));
+    }
+    Ok(Programa { dir })
+}
+
+/// Compila `fuente` esperando que falle y devuelve los diagnósticos en
+/// JSON (`--error-format=json`), uno por línea, con el texto ANSI incluido.
+pub fn diagnosticos(fuente: &str, opciones: &[&str]) -> Resultado<String> {
+    let dir = DirTemporal::nuevo("diagnostico")?;
+    fs::write(dir.ruta().join("main.rs"), fuente)?;
+
+    let salida = Command::new(rustc())
+        .args(["--edition", EDICION, "-o", "programa", "main.rs"])
+        .args(["--error-format=json", "--json=diagnostic-rendered-ansi"])
+        .args(opciones)
+        .current_dir(dir.ruta())
+        .output()?;
+    if salida.status.success() {
+        return Err(Error::Libro(String::from(
+            "se esperaba un error de compilación, pero compiló",
+        )));
+    }
+    Ok(String::from_utf8_lossy(&salida.stderr).into_owned())
+}
+
+/// Compila y ejecuta `fuente`, devolviendo su stdout.
+pub fn ejecutar(fuente: &str) -> Resultado<String> {
+    compilar(fuente)?.ejecutar()
+}
+
+/// El compilador a usar: `$RUSTC` o `rustc` del `PATH`.
+pub fn rustc() -> PathBuf {
+    std::env::var_os("RUSTC")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("rustc"))
+}
+
+fn texto(salida: &Output) -> String {
+    let mut texto = String::from_utf8_lossy(&salida.stderr).into_owned();
+    texto.push_str(&String::from_utf8_lossy(&salida.stdout));
+    texto
+}
+
+/// Versión del compilador, p. ej. `rustc 1.95.0 (59807616e 2026-04-14)`.
+pub fn version() -> Resultado<String> {
+    let salida = Command::new(rustc()).arg("-V").output()?;
+    if !salida.status.success() {
+        return Err(Error::Ejecucion(texto(&salida)));
+    }
+    Ok(String::from_utf8_lossy(&salida.stdout).trim().to_string())
+}
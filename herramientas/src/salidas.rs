@@ -0,0 +1,132 @@
+//! Salida capturada de los fragmentos, para no escribirla a mano.
+//!
+//! Solo los fragmentos ejecutables con `id`: de cada uno se toma lo que
+//! imprimió por stdout en su edición al probarlo [`crate::ediciones`] (sin
+//! compilarlo otra vez) y queda en `build/outputs/<id>.txt`. Los que no
+//! tienen `id` no se pueden nombrar desde el capítulo y no se escriben. En el capítulo, `#salida("<id>")` (de
+//! `src/config/global.typ`) lo muestra con el estilo «Output» de los
+//! bloques `yaml`:
+//!
+//! ````text
+//! // libro: id=vec-sort-desc
+//! ```rust
+//! v.sort_by(|a, b| b.cmp(a));
+//! println!("{:?}", v);
+//! ```
+//!
+//! #salida("vec-sort-desc")
+//! ````
+//!
+//! Así, al editar el código cambia también lo que el libro dice que
+//! imprime. `libro generar` falla si un fragmento con `id` no compila o no
+//! termina, y si un `#salida(...)` nombra un fragmento que no existe o que
+//! no se ejecuta.
+
+use std::fs;
+
+use crate::ediciones::{self, Comportamiento, Fila, LIMITE};
+use crate::escenarios;
+use crate::fragmentos::{self, Fragmento};
+use crate::rustc::EDICION;
+use crate::{Error, Libro, Resultado, generado};
+
+/// Directorio (relativo a la raíz) con las salidas capturadas.
+pub const DIRECTORIO: &str = "build/outputs";
+
+/// Llamada de Typst que incluye una salida.
+const LLAMADA: &str = "#salida(\"";
+
+/// Si el fragmento debería ejecutarse hasta el final.
+fn ejecutable(fragmento: &Fragmento) -> bool {
+    fragmento.compilable()
+        && ![
+            "no_run",
+            "no_compila",
+            "no_termina",
+            "stack_overflow",
+            escenarios::ATRIBUTO,
+        ]
+        .iter()
+        .any(|a| fragmento.atributos.tiene(a))
+}
+
+/// Lo que imprimió el fragmento de `fila` en su edición. Falla si no
+/// compiló o no terminó.
+pub fn capturar(fila: &Fila) -> Resultado<String> {
+    let fragmento = fila.fragmento;
+    let edicion = fragmento.atributos.valor("edicion").unwrap_or(EDICION);
+    let comportamiento = fila.en(edicion)?;
+    match comportamiento {
+        Comportamiento::NoCompila(error) => Err(Error::Libro(format!(
+            "{}: no compila en la edición {edicion} ({error}); si le faltan \
+             definiciones de otro ejemplo, márcalo con `fragment`",
+            fragmento.ubicacion()
+        ))),
+        Comportamiento::NoTermina => Err(Error::Libro(format!(
+            "{}: no terminó en {} s; si es a propósito, márcalo con `no_termina`",
+            fragmento.ubicacion(),
+            LIMITE.as_secs()
+        ))),
+        _ => comportamiento.salida().map(String::from).ok_or_else(|| {
+            Error::Libro(format!(
+                "{}: {}, no hay salida que capturar",
+                fragmento.ubicacion(),
+                comportamiento.resumen()
+            ))
+        }),
+    }
+}
+
+/// Los ids de cada `#salida("<id>")` del libro, con su ubicación.
+fn referencias(libro: &Libro) -> Resultado<Vec<(String, String)>> {
+    let mut referencias = Vec::new();
+    for archivo in fragmentos::archivos(&libro.raiz)? {
+        let texto = fs::read_to_string(&archivo)?;
+        let relativo = archivo.strip_prefix(&libro.raiz).unwrap_or(&archivo);
+        for (n, linea) in texto.lines().enumerate() {
+            let mut resto = linea;
+            while let Some(inicio) = resto.find(LLAMADA) {
+                resto = &resto[inicio + LLAMADA.len()..];
+                let Some((id, _)) = resto.split_once('"') else {
+                    break;
+                };
+                referencias.push((id.to_string(), format!("{}:{}", relativo.display(), n + 1)));
+            }
+        }
+    }
+    Ok(referencias)
+}
+
+/// Escribe `build/outputs/<id>.txt` para cada fragmento ejecutable con `id`
+/// y comprueba que cada `#salida(...)` tenga la suya.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    // Se prueban todos, no solo los que tienen `id`: la caché de
+    // `ediciones` descarta lo que no se usa en cada ejecución.
+    let fragmentos = libro.fragmentos()?;
+    let mut capturadas = Vec::new();
+    for fila in ediciones::probar_todos(libro, &fragmentos)? {
+        let Some(id) = fila.fragmento.id() else {
+            continue;
+        };
+        if !ejecutable(fila.fragmento) {
+            continue;
+        }
+        generado::escribir_en(libro, DIRECTORIO, format!("{id}.txt"), &capturar(&fila)?)?;
+        capturadas.push(id.to_string());
+    }
+
+    let faltan: Vec<String> = referencias(libro)?
+        .into_iter()
+        .filter(|(id, _)| !capturadas.contains(id))
+        .map(|(id, ubicacion)| {
+            format!("  {ubicacion}: no hay un fragmento ejecutable con `id={id}`")
+        })
+        .collect();
+    if !faltan.is_empty() {
+        return Err(Error::Libro(format!(
+            "salidas sin fragmento:\n{}",
+            faltan.join("\n")
+        )));
+    }
+    Ok(capturadas.len())
+}
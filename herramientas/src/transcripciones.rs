@@ -0,0 +1,142 @@
+//! Transcripciones reales de `cargo` para los capítulos de Cargo.
+//!
+//! Se repite, en un directorio temporal y sin red, la sesión que sigue el
+//! lector: `cargo new hola_mundo`, `cargo run` dos veces, `cargo check`,
+//! `cargo clean`, `cargo build` y `cargo build --release`. El `src/main.rs`
+//! es el fragmento marcado con `sesion=hola_mundo`.
+//!
+//! Cada orden produce `build/generado/cargo/<id>.typ` (la orden en un
+//! bloque `bash` y su salida en un bloque `yaml`) y `<id>-salida.typ` (solo
+//! la salida, para anotarla con `#codly`). Los árboles de directorios se
+//! dibujan tal como quedan en disco. Rutas y tiempos se normalizan (ver
+//! [`Sesion::cargo`]).
+
+use std::fs;
+
+use crate::cargo::Sesion;
+use crate::{Error, Libro, Resultado, arbol, generado, typst};
+
+/// Nombre del proyecto de la sesión, el mismo que usa el libro.
+pub const PROYECTO: &str = "hola_mundo";
+
+enum Paso {
+    /// `cargo <args>`; `en_proyecto` indica si se ejecuta dentro de él.
+    Cargo {
+        id: &'static str,
+        args: &'static [&'static str],
+        en_proyecto: bool,
+    },
+    /// Árbol del proyecto hasta `profundidad` niveles.
+    Arbol {
+        id: &'static str,
+        profundidad: usize,
+    },
+    /// Copia el fragmento del libro como `src/main.rs`.
+    Main,
+}
+
+const PASOS: &[Paso] = &[
+    Paso::Cargo {
+        id: "new",
+        args: &["new", PROYECTO],
+        en_proyecto: false,
+    },
+    Paso::Arbol {
+        id: "arbol-new",
+        profundidad: 3,
+    },
+    Paso::Main,
+    Paso::Cargo {
+        id: "run",
+        args: &["run"],
+        en_proyecto: true,
+    },
+    Paso::Cargo {
+        id: "run-otra-vez",
+        args: &["run"],
+        en_proyecto: true,
+    },
+    Paso::Arbol {
+        id: "arbol-build",
+        profundidad: 3,
+    },
+    Paso::Cargo {
+        id: "check",
+        args: &["check"],
+        en_proyecto: true,
+    },
+    Paso::Cargo {
+        id: "clean",
+        args: &["clean"],
+        en_proyecto: true,
+    },
+    Paso::Cargo {
+        id: "build",
+        args: &["build"],
+        en_proyecto: true,
+    },
+    Paso::Cargo {
+        id: "build-release",
+        args: &["build", "--release"],
+        en_proyecto: true,
+    },
+];
+
+fn bloque(texto: &str, lenguaje: &str) -> String {
+    format!(
+        "#raw({}, block: true, lang: {})\n",
+        typst::cadena(texto.trim_end()),
+        typst::cadena(lenguaje)
+    )
+}
+
+/// Genera las transcripciones de `build/generado/cargo/`.
+pub fn generar(libro: &Libro) -> Resultado<usize> {
+    let fragmentos = libro.fragmentos()?;
+    let Some(main) = fragmentos
+        .iter()
+        .find(|f| f.atributos.valor("sesion") == Some(PROYECTO))
+    else {
+        return Err(Error::Libro(format!(
+            "no hay ningún fragmento con `sesion={PROYECTO}`"
+        )));
+    };
+
+    let sesion = Sesion::nueva()?;
+    let proyecto = sesion.ruta().join(PROYECTO);
+    let mut archivos: Vec<(String, String)> = Vec::new();
+    for paso in PASOS {
+        match paso {
+            Paso::Cargo {
+                id,
+                args,
+                en_proyecto,
+            } => {
+                let dir = if *en_proyecto { PROYECTO } else { "." };
+                let t = sesion.cargo(dir, args)?;
+                if !t.exito {
+                    return Err(Error::Ejecucion(format!(
+                        "cargo {}:\n{}",
+                        args.join(" "),
+                        t.salida
+                    )));
+                }
+                let orden = bloque(&format!("$ cargo {}", args.join(" ")), "bash");
+                let salida = bloque(&t.salida, "yaml");
+                archivos.push((format!("{id}-salida"), salida.clone()));
+                archivos.push((id.to_string(), format!("{orden}{salida}")));
+            }
+            Paso::Arbol { id, profundidad } => {
+                let arbol = arbol::dibujar(&proyecto, PROYECTO, *profundidad)?;
+                archivos.push((id.to_string(), bloque(&arbol, "yaml")));
+            }
+            Paso::Main => fs::write(proyecto.join("src/main.rs"), &main.codigo)?,
+        }
+    }
+
+    for (nombre, contenido) in &archivos {
+        let contenido = format!("{}{contenido}", generado::CABECERA);
+        generado::escribir(libro, format!("cargo/{nombre}.typ"), &contenido)?;
+    }
+    Ok(archivos.len())
+}
@@ -0,0 +1,119 @@
+//! Ayudas para escribir código Typst.
+
+/// Literal de cadena Typst: `"..."` con los escapes necesarios.
+pub fn cadena(texto: &str) -> String {
+    let mut salida = String::with_capacity(texto.len() + 2);
+    salida.push('"');
+    for c in texto.chars() {
+        match c {
+            '"' => salida.push_str("\\\""),
+            '\\' => salida.push_str("\\\\"),
+            '\n' => salida.push_str("\\n"),
+            '\t' => salida.push_str("\\t"),
+            '\r' => salida.push_str("\\r"),
+            c if c.is_control() => salida.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => salida.push(c),
+        }
+    }
+    salida.push('"');
+    salida
+}
+
+/// Texto monoespaciado en línea: `raw("...")`.
+pub fn codigo(texto: &str) -> String {
+    format!("raw({})", cadena(texto))
+}
+
+/// Bloque de contenido con texto literal: `[#"..."]`.
+pub fn texto(texto: &str) -> String {
+    format!("[#{}]", cadena(texto))
+}
+
+/// Tabla Typst con encabezado. Las celdas ya son expresiones Typst
+/// (`[...]`, `raw(...)`), así que se pueden mezclar estilos.
+pub fn tabla(encabezados: &[String], filas: &[Vec<String>]) -> String {
+    let mut salida = format!("table(\n  columns: {},\n", encabezados.len());
+    salida.push_str(&format!("  table.header({}),\n", encabezados.join(", ")));
+    for fila in filas {
+        salida.push_str(&format!("  {},\n", fila.join(", ")));
+    }
+    salida.push(')');
+    salida
+}
+
+/// Figura con `cuerpo` (una expresión Typst) y su leyenda. Las tablas
+/// llevan `supplement: [Tabla]`, como las escritas a mano.
+pub fn figura(cuerpo: &str, leyenda: &str, etiqueta: Option<&str>) -> String {
+    let suplemento = if cuerpo.starts_with("table(") {
+        "\n  supplement: [Tabla],"
+    } else {
+        ""
+    };
+    let mut salida = format!(
+        "#figure(\n{},\n  caption: {},{suplemento}\n)",
+        sangrar(cuerpo, "  "),
+        leyenda
+    );
+    if let Some(etiqueta) = etiqueta {
+        salida.push_str(&format!(" <{etiqueta}>"));
+    }
+    salida.push('\n');
+    salida
+}
+
+fn sangrar(texto: &str, sangria: &str) -> String {
+    texto
+        .lines()
+        .map(|l| format!("{sangria}{l}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Entero con formato español: signo menos tipográfico (−) y grupos de
+/// tres cifras separados por un espacio fino, salvo en números de cuatro
+/// cifras (`1000`, `32 768`).
+pub fn numero(valor: &str) -> String {
+    let (signo, cifras) = match valor.strip_prefix('-') {
+        Some(resto) => ("\u{2212}", resto),
+        None => ("", valor),
+    };
+    if cifras.len() <= 4 || !cifras.bytes().all(|b| b.is_ascii_digit()) {
+        return format!("{signo}{cifras}");
+    }
+    let mut grupos = Vec::new();
+    let mut fin = cifras.len();
+    while fin > 0 {
+        let inicio = fin.saturating_sub(3);
+        grupos.push(&cifras[inicio..fin]);
+        fin = inicio;
+    }
+    grupos.reverse();
+    format!("{signo}{}", grupos.join("\u{2009}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cadena_escapa() {
+        assert_eq!(cadena("hola"), r#""hola""#);
+        assert_eq!(cadena("di \"sí\"\n"), r#""di \"sí\"\n""#);
+        assert_eq!(cadena("C:\\ruta\t1"), r#""C:\\ruta\t1""#);
+        assert_eq!(cadena("\u{1b}[0m"), r#""\u{1b}[0m""#);
+    }
+
+    #[test]
+    fn numero_agrupa_de_tres_en_tres() {
+        assert_eq!(numero("0"), "0");
+        assert_eq!(numero("1000"), "1000");
+        assert_eq!(numero("32768"), "32\u{2009}768");
+        assert_eq!(
+            numero("-2147483648"),
+            "\u{2212}2\u{2009}147\u{2009}483\u{2009}648"
+        );
+        assert_eq!(numero("-128"), "\u{2212}128");
+        // Lo que no es un entero se deja como está.
+        assert_eq!(numero("3.14159"), "3.14159");
+    }
+}
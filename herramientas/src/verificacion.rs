@@ -0,0 +1,280 @@
+//! Versiones con las que se verifica el libro y registro de la última
+//! verificación.
+//!
+//! `rust-toolchain.toml` fija la versión de Rust con la que se capturaron
+//! las salidas del libro, y `rustup` la instala con los componentes que usan
+//! los generadores. La de Typst, que no es parte del toolchain, está en
+//! `[package.metadata.libro]` de `herramientas/Cargo.toml`.
+//!
+//! `libro comprobar` compara esas versiones con las instaladas, ejecuta
+//! todos los generadores y, solo si todo sale bien, escribe
+//! `build/generado/verificacion.typ`. De ese archivo salen el colofón y el
+//! pie de cada capítulo («Ejemplos verificados con rustc 1.95, edición
+//! 2021»). Una versión distinta de la fijada no impide verificar: se avisa
+//! y el pie y el colofón lo dicen.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::msrv::Version;
+use crate::rustc::{self, EDICION};
+use crate::{Error, Libro, Resultado, cargo, generado, typst};
+
+/// Archivo con la versión de Rust fijada, relativo a la raíz del libro.
+pub const TOOLCHAIN: &str = "rust-toolchain.toml";
+
+/// Archivo con la versión de Typst fijada, relativo a la raíz del libro.
+pub const METADATOS: &str = "herramientas/Cargo.toml";
+
+/// Versiones fijadas en [`TOOLCHAIN`] y [`METADATOS`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fijadas {
+    pub rustc: String,
+    /// La de `rustc`: `cargo` se publica con cada versión de Rust.
+    pub cargo: String,
+    pub typst: String,
+}
+
+impl Fijadas {
+    /// Lee `channel` de [`TOOLCHAIN`] y `typst` de [`METADATOS`].
+    pub fn leer(libro: &Libro) -> Resultado<Fijadas> {
+        let rustc = buscar(libro, TOOLCHAIN, "toolchain", "channel")?;
+        if Version::parsear(&rustc).is_none() {
+            return Err(Error::Libro(format!(
+                "{TOOLCHAIN}: `channel` debe ser una versión (`1.95.0`), no `{rustc}`"
+            )));
+        }
+        Ok(Fijadas {
+            cargo: rustc.clone(),
+            rustc,
+            typst: buscar(libro, METADATOS, "package.metadata.libro", "typst")?,
+        })
+    }
+}
+
+/// El valor de `clave = "valor"` en la sección `[seccion]` de un archivo
+/// TOML. No entiende más TOML que ese.
+fn buscar(libro: &Libro, archivo: &str, seccion: &str, clave: &str) -> Resultado<String> {
+    let texto = fs::read_to_string(libro.raiz.join(archivo))?;
+    valor_toml(&texto, seccion, clave)
+        .ok_or_else(|| Error::Libro(format!("{archivo}: falta `{clave}` en `[{seccion}]`")))
+}
+
+fn valor_toml(texto: &str, seccion: &str, clave: &str) -> Option<String> {
+    let mut actual = "";
+    for linea in texto.lines() {
+        let linea = linea.split('#').next().unwrap_or_default().trim();
+        if let Some(nombre) = linea.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            actual = nombre.trim();
+            continue;
+        }
+        let Some((c, valor)) = linea.split_once('=') else {
+            continue;
+        };
+        if actual == seccion && c.trim() == clave {
+            return Some(valor.trim().trim_matches('"').to_string());
+        }
+    }
+    None
+}
+
+/// Versiones instaladas, tal como las imprime cada herramienta
+/// (`rustc 1.95.0 (59807616e 2026-04-14)`).
+#[derive(Debug, Clone)]
+pub struct Instaladas {
+    pub rustc: String,
+    pub cargo: String,
+    /// `None` si Typst no está instalado.
+    pub typst: Option<String>,
+}
+
+impl Instaladas {
+    pub fn detectar() -> Resultado<Instaladas> {
+        let typst = match Command::new(typst_cli()).arg("--version").output() {
+            Ok(salida) if salida.status.success() => {
+                Some(String::from_utf8_lossy(&salida.stdout).trim().to_string())
+            }
+            Ok(_) => None,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let salida = Command::new(cargo::cargo()).arg("-V").output()?;
+        Ok(Instaladas {
+            rustc: rustc::version()?,
+            cargo: String::from_utf8_lossy(&salida.stdout).trim().to_string(),
+            typst,
+        })
+    }
+}
+
+/// El número de versión de `rustc 1.95.0 (...)`: `1.95.0`.
+fn numero(version: &str) -> &str {
+    version.split_whitespace().nth(1).unwrap_or(version)
+}
+
+/// Una herramienta cuya versión instalada no es la fijada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distinta {
+    pub nombre: &'static str,
+    pub fijada: String,
+    pub instalada: String,
+}
+
+/// Las herramientas que no tienen la versión fijada. Typst solo se compara
+/// si está instalado: `libro` no lo necesita para verificar los ejemplos.
+pub fn comparar(fijadas: &Fijadas, instaladas: &Instaladas) -> Vec<Distinta> {
+    let mut distintas = Vec::new();
+    let mut pares = vec![
+        ("rustc", &fijadas.rustc, &instaladas.rustc),
+        ("cargo", &fijadas.cargo, &instaladas.cargo),
+    ];
+    if let Some(typst) = &instaladas.typst {
+        pares.push(("typst", &fijadas.typst, typst));
+    }
+    for (nombre, fijada, instalada) in pares {
+        if numero(instalada) != fijada {
+            distintas.push(Distinta {
+                nombre,
+                fijada: fijada.clone(),
+                instalada: numero(instalada).to_string(),
+            });
+        }
+    }
+    distintas
+}
+
+/// Escribe `build/generado/verificacion.typ` con las versiones verificadas,
+/// las que no eran las fijadas y la fecha (UTC) de hoy.
+pub fn registrar(
+    libro: &Libro,
+    fijadas: &Fijadas,
+    instaladas: &Instaladas,
+    distintas: &[Distinta],
+) -> Resultado<PathBuf> {
+    let rustc = numero(&instaladas.rustc);
+    // `1.95.0` → `1.95`, la forma en que se habla de una versión de Rust.
+    let corta = rustc.rsplit_once('.').map_or(rustc, |(corta, _)| corta);
+    let campos = [
+        ("rustc", typst::cadena(corta)),
+        ("rustc-completo", typst::cadena(&instaladas.rustc)),
+        ("cargo", typst::cadena(&instaladas.cargo)),
+        ("typst", typst::cadena(&fijadas.typst)),
+        ("edicion", typst::cadena(EDICION)),
+        ("plataforma", typst::cadena(&plataforma())),
+        ("fecha", typst::cadena(&fecha(SystemTime::now()))),
+        ("distintas", distintas_typ(distintas)),
+    ];
+    let mut contenido = format!("{}#let verificacion = (\n", generado::CABECERA);
+    for (clave, valor) in campos {
+        contenido.push_str(&format!("  {clave}: {valor},\n"));
+    }
+    contenido.push_str(")\n");
+    generado::escribir(libro, "verificacion.typ", &contenido)
+}
+
+/// `((nombre: "rustc", fijada: "1.95.0", instalada: "1.96.0"),)`.
+fn distintas_typ(distintas: &[Distinta]) -> String {
+    let mut lista = String::from("(");
+    for d in distintas {
+        lista.push_str(&format!(
+            "(nombre: {}, fijada: {}, instalada: {}),",
+            typst::cadena(d.nombre),
+            typst::cadena(&d.fijada),
+            typst::cadena(&d.instalada)
+        ));
+    }
+    lista.push(')');
+    lista
+}
+
+/// `$TYPST` o `typst` del `PATH`.
+fn typst_cli() -> PathBuf {
+    std::env::var_os("TYPST")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("typst"))
+}
+
+fn plataforma() -> String {
+    format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+}
+
+/// Fecha `AAAA-MM-DD` (UTC) de un instante.
+fn fecha(instante: SystemTime) -> String {
+    let segundos = instante
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // Días desde 1970-01-01 a fecha civil (algoritmo de Howard Hinnant).
+    let dias = (segundos / 86_400) as i64 + 719_468;
+    let era = dias.div_euclid(146_097);
+    let dia_era = dias.rem_euclid(146_097);
+    let anio_era = (dia_era - dia_era / 1_460 + dia_era / 36_524 - dia_era / 146_096) / 365;
+    let dia_anio = dia_era - (365 * anio_era + anio_era / 4 - anio_era / 100);
+    let mp = (5 * dia_anio + 2) / 153;
+    let dia = dia_anio - (153 * mp + 2) / 5 + 1;
+    let mes = if mp < 10 { mp + 3 } else { mp - 9 };
+    let anio = anio_era + era * 400 + i64::from(mes <= 2);
+    format!("{anio:04}-{mes:02}-{dia:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valor_de_una_seccion() {
+        let texto = "\
+# comentario
+[package]
+name = \"libro\"
+
+[package.metadata.libro]
+typst = \"0.13.1\"  # fijada
+
+[toolchain]
+channel = \"1.95.0\"
+components = [\"rust-docs\", \"clippy\"]
+";
+        assert_eq!(
+            valor_toml(texto, "package.metadata.libro", "typst").as_deref(),
+            Some("0.13.1")
+        );
+        assert_eq!(
+            valor_toml(texto, "toolchain", "channel").as_deref(),
+            Some("1.95.0")
+        );
+        assert_eq!(valor_toml(texto, "package", "typst"), None);
+    }
+
+    #[test]
+    fn una_version_distinta_no_es_un_error() {
+        let fijadas = Fijadas {
+            rustc: "1.95.0".to_string(),
+            cargo: "1.95.0".to_string(),
+            typst: "0.13.1".to_string(),
+        };
+        let mut instaladas = Instaladas {
+            rustc: "rustc 1.95.0 (59807616e 2026-04-14)".to_string(),
+            cargo: "cargo 1.95.0 (f2d3ce0bd 2026-03-21)".to_string(),
+            typst: None,
+        };
+        assert_eq!(comparar(&fijadas, &instaladas), []);
+
+        instaladas.rustc = "rustc 1.96.0 (0a1b2c3d4 2026-05-28)".to_string();
+        instaladas.typst = Some("typst 0.13.1 (8ace67d9)".to_string());
+        assert_eq!(
+            comparar(&fijadas, &instaladas),
+            [Distinta {
+                nombre: "rustc",
+                fijada: "1.95.0".to_string(),
+                instalada: "1.96.0".to_string(),
+            }]
+        );
+        assert_eq!(
+            distintas_typ(&comparar(&fijadas, &instaladas)),
+            "((nombre: \"rustc\", fijada: \"1.95.0\", instalada: \"1.96.0\"),)"
+        );
+    }
+}
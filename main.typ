@@ -82,3 +82,5 @@
 // #include "src/chapters/14-Manejo_Errores/index.typ"
 
 // #include "src/chapters/15-Modularidad/index.typ"
+
+#include "/src/contents/colofon.typ"
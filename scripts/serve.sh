@@ -1,5 +1,13 @@
 #!/bin/bash
 mkdir -p output
+# `generar` reutiliza build/cache/: solo recompila los fragmentos que
+# cambiaron. `comprobar` (verifica el toolchain y lo ejecuta todo) solo hace
+# falta la primera vez, para el colofón; después, `just comprobar`.
+if [ -f build/generado/verificacion.typ ]; then
+    cargo run --quiet --release -p libro -- generar
+else
+    cargo run --quiet --release -p libro -- comprobar
+fi
 ~/.cargo/bin/typst compile main.typ output/libro.pdf
 rm -f *.pdf
 
@@ -1,8 +1,7 @@
 
 Solo compilar sin ejecutar
-```bash
-cargo build
-```
+
+#include "/build/generado/cargo/build.typ"
 
 El binario se genera en target/debug/:
 
@@ -18,11 +17,10 @@ El binario se genera en target/debug/:
 
 Verificación rápida
 
-El comando más rápido durante el desarrollo:
+El comando más rápido durante el desarrollo. Comprueba el código sin generar
+el ejecutable (por eso dice `Checking` y no `Compiling`):
 
-```bash
-cargo check
-```
+#include "/build/generado/cargo/check.typ"
 
 ¿Cuándo usar cargo check?
 
@@ -36,9 +34,7 @@ cargo check
 
 Limpiar archivos compilados
 
-```bash
-cargo clean
-```
+#include "/build/generado/cargo/clean.typ"
 
 ¿Cuándo usar?
 
@@ -57,9 +53,7 @@ Modo Release
 
 Cuando necesites un binario optimizado para distribuir:
 
-```bash
-cargo build --release
-```
+#include "/build/generado/cargo/build-release.typ"
 
 El ejecutable optimizado se genera en target/release/.
 
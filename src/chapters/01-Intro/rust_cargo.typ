@@ -35,6 +35,7 @@ Proceso de Compilación
 
 Todo comienza con el código fuente `main.rs`:
 
+// libro: id=hola-rustc, etapas
 #codly(
   highlights : (
     (line:1, start: 1,end: 2,fill: rgb("#D6FFCB"),),
@@ -72,9 +73,61 @@ main.exe         # Windows
 ```
 
 Resultado:
-```yaml
-Hola desde Rust!
-```
+
+#include "/build/generado/etapas/salida.typ"
+
+==== Qué produce cada etapa
+
+Entre `rustc main.rs` y el ejecutable, el compilador pasa por varias fases. El
+@diag-pipeline las resume; a continuación vemos lo que cada una produjo al
+compilar el programa anterior.
+
+#include "../../utils/diagrama_llvm.typ"
+
+*1-2. Lexing y parsing.* El texto se convierte en tokens y los tokens en un
+árbol sintáctico (AST). El `rustc` estable no escribe ninguno de los dos; los
+vemos indirectamente en los errores de sintaxis.
+
+*3. Expansion.* Las macros se sustituyen por el código que generan. `println!`
+no es una función: se convierte en una llamada a `_print` con los argumentos
+ya preparados por `format_args!`. Además aparece el _preludio_, los nombres
+que todo programa importa sin escribirlos (@etapa-expansion).
+
+#include "/build/generado/etapas/expansion.typ"
+
+*4. Resolution.* Cada nombre se resuelve a su ruta completa y el programa se
+reduce a HIR, una forma más simple del árbol sin macros
+(@etapa-hir).
+
+#include "/build/generado/etapas/hir.typ"
+
+*5. Type check.* Aquí se comprueban los tipos y el préstamo de valores. Tampoco
+deja un archivo: si algo falla, su resultado son los mensajes de error que
+veremos a lo largo del libro.
+
+*6. MIR.* El cuerpo de cada función se traduce a bloques básicos (`bb0`,
+`bb1`...) unidos por saltos. Sobre esta forma trabaja el _borrow checker_ y
+se hacen las primeras optimizaciones. Se obtiene con `rustc --emit=mir`
+(@etapa-mir).
+
+#include "/build/generado/etapas/mir.typ"
+
+*7. LLVM IR.* `rustc` entrega el programa a LLVM en su propio lenguaje
+intermedio, independiente del procesador. Se obtiene con
+`rustc --emit=llvm-ir` (@etapa-llvm-ir).
+
+#include "/build/generado/etapas/llvm-ir.typ"
+
+*8. Codegen.* LLVM produce instrucciones para el procesador concreto
+(`rustc --emit=asm`, @etapa-asm) y el enlazador las une con la biblioteca
+estándar en el ejecutable. En él, nuestra función `main` aparece con un
+nombre _decorado_ que incluye el crate y un hash; el `main` sin decorar es
+el punto de entrada que genera Rust para llamarla
+(@etapa-simbolos).
+
+#include "/build/generado/etapas/asm.typ"
+
+#include "/build/generado/etapas/simbolos.typ"
 
 Gestor de Proyectos Cargo
 
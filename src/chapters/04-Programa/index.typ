@@ -66,6 +66,8 @@ cargo new hola_mundo
 
 - b: Nombre del proyecto
 
+#include "/build/generado/cargo/new-salida.typ"
+
 ```bash
 # Entrar al directorio
 cd hola_mundo
@@ -74,27 +76,12 @@ cd hola_mundo
 Estructura creada:
 #codly(
   highlights : (
-    (line:1, start:1, end:14, fill: rgb("#CBD4FF"),tag:"Raíz del proyecto"),
-    (line:2, start:11, end:21, fill: rgb("#C7FFFA"),tag:"Registra las versiones específicas"),
-    (line:3, start:11, end:21, fill: rgb("#C7FFD4"),tag:"Define las dependencias"),
-    (line:5, start:17, end:23, fill: rgb("#FCCBFF"),tag:"Código fuente principal"),
-    (line:6, start:11, end:18, fill: rgb("#FFD9CB"),tag:"Destino de la Compilación"),
-    (line:10, start:20, fill: rgb("#FFF0CB"),tag:"El ejecutable de tu aplicación"),
+    (line:1, start:1, end:11, fill: rgb("#CBD4FF"),tag:"Raíz del proyecto"),
+    (line:2, start:5, fill: rgb("#C7FFD4"),tag:"Define las dependencias"),
+    (line:4, start:9, fill: rgb("#FCCBFF"),tag:"Código fuente principal"),
   ),
 )
-
-```yaml
-hello_world/
-╠── Cargo.lock
-╠── Cargo.toml
-╠── src/
-│   ╚── main.rs
-╚── target/
-    ╚── debug/
-        ╠── build/
-        ╚── deps/
-          ╚── hello_world
-```
+#include "/build/generado/cargo/arbol-new.typ"
 
 
 Anatomía del Proyecto: Cargo.toml
@@ -144,8 +131,33 @@ edition = "2021"
 # Dependencias para scripts de build
 ```
 
+El campo #raw("edition", lang: "toml") indica la edición de Rust del proyecto.
+Los ejemplos de este libro usan la 2021, pero #raw("cargo new", lang: "bash") crea
+los proyectos nuevos con la 2024. Cada edición puede reservar palabras nuevas o
+ajustar algunas reglas. El cambio más fácil de ver es el de
+#raw("gen", lang: "rust"), una palabra reservada desde la 2024. Un nombre de
+variable tan corriente como este basta para que un programa escrito para la
+2021 deje de compilar al copiarlo en un proyecto nuevo:
+
+// libro: edicion=2021
+```rust
+let gen = 5;
+println!("{gen}");
+```
+
+En la 2024, #raw("rustc", lang: "bash") se queja de que
+#raw("gen", lang: "rust") es una palabra reservada. La solución es cambiar el
+nombre o escribir #raw("r#gen", lang: "rust"), que pide usar la palabra como
+identificador. Si un ejemplo del libro no te compila en tu proyecto, mira
+primero la edición de tu #raw("Cargo.toml", lang: "bash").
+
+Todos los ejemplos del libro se prueban en ambas ediciones, y los que se
+comportan distinto, como este, se marcan con la edición que describe el texto.
+#include "/build/generado/ediciones.typ"
+
 Punto de entrada: main.rs
 
+// libro: sesion=hola_mundo
 #codly(
   highlights : (
     (line:1, start: 1 , end: 9 ,  fill: rgb("#CBD4FF"), tag: "A"),
@@ -180,24 +192,30 @@ cargo run
     (line:4, start: 1 , fill: rgb("#FCCBFF"), tag: "D"),
   ),
 )
-```bash
-Compiling hola_mundo v0.1.0 (/ruta/hola_mundo)
- Finished `dev` profile [unoptimized + debuginfo] target(s) in 3.42s
-  Running `target/debug/hola_mundo`
-Hola, Rust!
-```
+#include "/build/generado/cargo/run-salida.typ"
 
 + A: Cargo compila el proyecto (solo la primera vez o si hay cambios)
 + B: Perfil de compilación: dev (desarrollo, sin optimizaciones)
 + C: Ruta del ejecutable que se está ejecutando
 + D: Output de tu programa
 
-Si no modificaste el código, la segunda ejecución será instantánea:
-```bash
-Finished `dev` profile [unoptimized + debuginfo] target(s) in 0.01s
- Running `target/debug/hola_mundo`
-Hola, Rust!
-```
+El tiempo que tarda cada compilación depende de la máquina, por eso aparece
+como `X.XXs`. Si no modificaste el código, la segunda ejecución será
+instantánea, porque no hay nada que compilar:
+
+#include "/build/generado/cargo/run-otra-vez-salida.typ"
+
+Después de compilar, el proyecto tiene dos elementos nuevos: `Cargo.lock` y
+la carpeta `target/`:
+
+#codly(
+  highlights : (
+    (line:2, start:5, fill: rgb("#C7FFFA"),tag:"Registra las versiones específicas"),
+    (line:6, start:5, fill: rgb("#FFD9CB"),tag:"Destino de la Compilación"),
+    (line:12, start:13, fill: rgb("#FFF0CB"),tag:"El ejecutable de tu aplicación"),
+  ),
+)
+#include "/build/generado/cargo/arbol-build.typ"
 
 === Variables con Rust
 
@@ -205,15 +223,15 @@ Let
 
 #codly(
   highlights : (
-    (line:2, start: 3,end:5, fill: rgb("#CBD4FF"), tag: "A"),
-    (line:2, start: 7,end:10, fill: rgb("#FFD9CB"), tag: "B"),
-    (line:2, start: 14,end:15, fill: rgb("#FCCBFF"), tag: "C"),
+    (line:2, start: 5,end:7, fill: rgb("#CBD4FF"), tag: "A"),
+    (line:2, start: 9,end:12, fill: rgb("#FFD9CB"), tag: "B"),
+    (line:2, start: 16,end:17, fill: rgb("#FCCBFF"), tag: "C"),
   ),
 )
 ```rust
 fn main() {
-  let edad = 25;
-  println!("Mi edad es {}", edad);
+    let edad = 25;
+    println!("Mi edad es {}", edad);
 }
 ```
 
@@ -230,6 +248,7 @@ Mi edad es 25
 
 Que pasa si queremos modificar el valor de una variable inmutable?
 
+// libro: id=edad-inmutable, no_compila=E0384
 #codly(
   highlights : (
     (line:3, start: 3,end:13, fill: rgb("#FCCBFF"), tag: "ERROR!!"),
@@ -247,19 +266,9 @@ Gracias a herramientas inteligentes como rust-analyzer y rustc, nuestros
 editores de código pueden analizar información avanzada e interactiva a través del
 Language Server Protocol (LSP). De esta manera, es posible visualizar errores,
 comprender por qué el código es incorrecto e incluso recibir sugerencias automáticas
-para corregirlo.
+para corregirlo. Este es el mensaje que muestra `rustc` al compilar el programa:
 
-```bash
-error[E0384]: No se puede asignar dos veces a la variable inmutable `edad`
- --> src/main.rs:3:3
-3 |   edad = 26;
-  |
-help: consider making this binding mutable
-  |
-2 |   let mut edad = 25;
-  |       +++
-For more information about this error, try `rustc --explain E0384`.
-```
+#include "/build/generado/diagnosticos/edad-inmutable.typ"
 
 + El Código de Error: [E0384]
 
@@ -276,16 +285,16 @@ explícitamente como mutable usando let mut.
 
 #codly(
   highlights : (
-    (line:2, start: 3,end:9, fill: rgb("#FCCBFF"), tag: "A"),
-    (line:4, start: 3, fill: rgb("#C7FFFA")),
+    (line:2, start: 5,end:11, fill: rgb("#FCCBFF"), tag: "A"),
+    (line:4, start: 5, fill: rgb("#C7FFFA")),
   ),
 )
 ```rust
 fn main() {
-  let mut carro = "Toyota";
-  println!("Mi carro es {}", carro);
-  carro = "Honda";
-  println!("Mi nuevo carro es {}", carro);
+    let mut carro = "Toyota";
+    println!("Mi carro es {}", carro);
+    carro = "Honda";
+    println!("Mi nuevo carro es {}", carro);
 }
 ```
 
@@ -301,15 +310,15 @@ La nueva variable "sombrea" a la anterior.
 
 #codly(
   highlights : (
-    (line:4, start: 3, fill: rgb("#FCCBFF"), tag: "A"),
+    (line:4, start: 5, fill: rgb("#FCCBFF"), tag: "A"),
   ),
 )
 ```rust
 fn main() {
-  let mut edad = 25;
-  println!("Mi edad es {}", edad);
-  let edad = "Mi edad es 35";
-  println!("{}", edad);
+    let mut edad = 25;
+    println!("Mi edad es {}", edad);
+    let edad = "Mi edad es 35";
+    println!("{}", edad);
 }
 ```
 
@@ -328,6 +337,7 @@ Mi edad es 35
 
 Lo que no se puede hacer es cambiar el tipo de una variable sin "sombrear".
 
+// libro: no_compila=E0308
 #codly(
   highlights : (
     (line:2, start: 1, fill: rgb("#FCCBFF"), tag: "ERROR!"),
@@ -346,22 +356,22 @@ En Rust, el scope está definido por llaves { }.
 
 #codly(
   highlights : (
-    (line:9, start: 3, fill: rgb("#C7FFFA"), tag: "A"),
-    (line:10, start: 3, fill: rgb("#FCCBFF"), tag: "ERROR!"),
+    (line:9, start: 5, fill: rgb("#C7FFFA"), tag: "A"),
+    (line:10, start: 5, fill: rgb("#FCCBFF"), tag: "ERROR!"),
   ),
 )
 ```rust
 fn main() {
-  let x = 5;
-  println!("Valor de x: {x}");
-  {
-    let x = x * 2;
-    let y = x;
-    println!("Dentro del scope x: {x}");
-    println!("Dentro del scope y: {y}");
-  }
-  //println!("Valor de y: {y}");
-  println!("Valor de x: {x}");
+    let x = 5;
+    println!("Valor de x: {x}");
+    {
+        let x = x * 2;
+        let y = x;
+        println!("Dentro del scope x: {x}");
+        println!("Dentro del scope y: {y}");
+    }
+    //println!("Valor de y: {y}");
+    println!("Valor de x: {x}");
 }
 ```
 - A:
@@ -392,17 +402,17 @@ conocidos de antemano, como constantes matemáticas, límites, o configuraciones
   highlights : (
     (line:1, start: 1,end:8, fill: rgb("#C7FFFA"), tag: "A"),
     (line:1, start: 9,end:13, fill: rgb("#CBD4FF"), tag: "B"),
-    (line:4, start: 1, fill: rgb("#C7FFFA"), tag: "C"),
-    (line:6, start: 1, fill: rgb("#FCCBFF"), tag: "D"),
+    (line:4, start: 3, fill: rgb("#C7FFFA"), tag: "C"),
+    (line:6, start: 3, fill: rgb("#FCCBFF"), tag: "D"),
   ),
 )
 ```rust
 const PI: f64 = 3.14159265359;
 
 fn main() {
-  const PI: f64 = 5.14;
-  println!("Valor de PI: {}", PI);
-  //PI = 3.12;
+    const PI: f64 = 5.14;
+    println!("Valor de PI: {}", PI);
+    //PI = 3.12;
 }
 ```
 
@@ -435,17 +445,17 @@ antes de que se ejecute la función main).
 #codly(
   highlights : (
     (line:1, start: 1,end:24, fill: rgb("#C7FFFA"), tag: "A"),
-    (line:4, start: 3, fill: rgb("#FCCBFF"), tag: "B"),
-    (line:5, start: 3, fill: rgb("#FCCBFF"), tag: "C"),
+    (line:4, start: 5, fill: rgb("#FCCBFF"), tag: "B"),
+    (line:5, start: 5, fill: rgb("#FCCBFF"), tag: "C"),
   ),
 )
 ```rust
 static PROTOCOLO_VERSION: u8 = 2;
 
 fn main() {
-  // let PROTOCOLO_VERSION:u8 = 3;
-  // PROTOCOLO_VERSION: u8 = 8;
-  println!("Protocolo v{}", PROTOCOLO_VERSION);
+    // let PROTOCOLO_VERSION:u8 = 3;
+    // PROTOCOLO_VERSION: u8 = 8;
+    println!("Protocolo v{}", PROTOCOLO_VERSION);
 }
 ```
 
@@ -474,21 +484,21 @@ Una expresión es cualquier pieza de código que se evalúa y devuelve un valor.
 
 #codly(
   highlights : (
-    (line:2, start: 3, fill: rgb("#C7FFFA"), tag: "A"),
-    (line:5, start: 3, fill: rgb("#C7FFFA"), tag: "A"),
-    (line:3, start: 5, fill: rgb("#FCCBFF"), tag: "B"),
-    (line:4, start: 5, fill: rgb("#FFD9CB"), tag: "D"),
-    (line:7, start: 3, fill: rgb("#C7FFFA"), tag: "A"),
+    (line:2, start: 5, fill: rgb("#C7FFFA"), tag: "A"),
+    (line:5, start: 5, fill: rgb("#C7FFFA"), tag: "A"),
+    (line:3, start: 9, fill: rgb("#FCCBFF"), tag: "B"),
+    (line:4, start: 9, fill: rgb("#FFD9CB"), tag: "D"),
+    (line:7, start: 5, fill: rgb("#C7FFFA"), tag: "A"),
   ),
 )
 ```rust
 fn main() {
-  let y = {
-    let z = 3;
-    z + 1
-  };
+    let y = {
+        let z = 3;
+        z + 1
+    };
 
-  println!("y = {}", y);
+    println!("y = {}", y);
 }
 ```
 
@@ -558,18 +568,19 @@ y = 4
 ```
 En caso de poner ; al final se convierte en una sentencia
 (statement) y devuleve un unit type ().
+// libro: permitir=let_unit_value no_effect
 #codly(
   highlights : (
-    (line:4, start: 5, fill: rgb("#D6FFCB"), tag: "A"),
+    (line:4, start: 9, fill: rgb("#D6FFCB"), tag: "A"),
   ),
 )
 ```rust
 fn main() {
-  let y = {
-    let z = 3;
-    z + 1;
-  };
-  println!("y = {:?}", y);
+    let y = {
+        let z = 3;
+        z + 1;
+    };
+    println!("y = {:?}", y);
 }
 ```
 
@@ -607,6 +618,7 @@ El Stack es una región de la memoria RAM para almacenar datos cuya vida
 útil y tamaño son conocidos en tiempo de compilación.
 
 
+// libro: id=stack, memoria
 ```rust
 fn main() {
     let numero: u8 = 5;
@@ -614,7 +626,7 @@ fn main() {
 }
 ```
 
-#include "../../utils/stack.typ"
+#include "/build/generado/memoria/stack.typ"
 
 
 Heap
@@ -624,21 +636,22 @@ y tamaño son conocidos en tiempo de ejecución.
 
 
 
+// libro: id=string-move, memoria
 #codly(
   highlights : (
-    (line:2, start: 12, end: 19, fill: rgb("#C7FFFA"), tag: "A"),
-    (line:2, start: 23, fill: rgb("#CBD4FF"), tag: "B"),
-    (line:3, start: 3, fill: rgb("#FFD9CB"), tag: "C"),
-    (line:6, start: 3, fill: rgb("#FCCBFF"), tag: "D"),
+    (line:2, start: 14, end: 21, fill: rgb("#C7FFFA"), tag: "A"),
+    (line:2, start: 25, fill: rgb("#CBD4FF"), tag: "B"),
+    (line:3, start: 5, fill: rgb("#FFD9CB"), tag: "C"),
+    (line:6, start: 5, fill: rgb("#FCCBFF"), tag: "D"),
   ),
 )
 ```rust
 fn main() {
-  let texto: String = String::from("Rûst");
-  let texto2: String = texto;
+    let texto: String = String::from("Rûst");
+    let texto2: String = texto;
 
-  println!("{texto2}");
-  //println!("{texto1}");
+    println!("{texto2}");
+    //println!("{texto1}");
 }
 ```
 
@@ -678,101 +691,7 @@ fn main() {
   - El uso de la variable texto es invalido, ya que fue movida a texto2.
 
 
-  #import "@preview/fletcher:0.5.8" as fletcher: diagram, node, edge
-
-  #let rust-orange = rgb("#ff6b35")
-  #let stack-blue = rgb("#4a90e2")
-  #let heap-green = rgb("#27ae60")
-
-  #let stack-box(pos, var-name, ptr, len, cap, valid: true) = {
-    let fill-color = if valid { stack-blue.lighten(90%) } else { gray.lighten(80%) }
-    let text-color = if valid { black } else { gray }
-
-    node(
-      pos,
-      align(left)[
-        #text(size: 9pt, weight: "bold", fill: if valid { rust-orange } else { gray })[#var-name]
-        #if not valid { text(size: 7pt, fill: red)[ ✗] } \
-        #line(length: 100%, stroke: 0.5pt + gray) \
-        #text(size: 7pt, fill: text-color)[ptr: #text(font: "monospace", fill: stack-blue)[#ptr]] \
-        #text(size: 7pt, fill: text-color)[len: #text(font: "monospace")[#len]] \
-        #text(size: 7pt, fill: text-color)[cap: #text(font: "monospace")[#cap]]
-      ],
-      width: 35mm,
-      fill: fill-color,
-      stroke: 2pt + if valid { stack-blue } else { gray },
-      corner-radius: 5pt,
-      inset: 8pt,
-    )
-  }
-
-  #let heap-box(pos, content, bytes, addr) = node(
-    pos,
-    align(left)[
-      #text(size: 8pt, weight: "bold", fill: heap-green)[Datos en Heap] \
-      #line(length: 100%, stroke: 0.5pt + gray) \
-      #text(size: 7.5pt, font: "monospace")[#content] \
-      #text(size: 6.5pt, fill: gray)[bytes: #bytes] \
-      #text(size: 6.5pt, fill: gray)[addr: #text(font: "monospace")[#addr]]
-    ],
-    width: 38mm,
-    fill: heap-green.lighten(90%),
-    stroke: 2pt + heap-green,
-    corner-radius: 5pt,
-    inset: 8pt,
-  )
-
-  #figure(
-    kind: "diagram",
-    supplement: [Diagrama],
-    caption: [Move semántico: el ownership se transfiere de `texto` a `texto2`],
-
-    diagram(
-      spacing: (15mm, 10mm),
-      edge-stroke: 1.5pt,
-      edge-corner-radius: 5pt,
-      mark-scale: 80%,
-
-      // Stack label
-      node((0.5, -0.5), text(size: 10pt, weight: "bold", fill: stack-blue)[STACK]),
-
-      // texto (inválido después del move)
-      stack-box((0, 0), [texto], [0x582...3d00], [5], [5], valid: false),
-
-      // Flecha de move
-      edge((0, 0), (1, 0), "-|>",
-        label: text(size: 8pt, fill: rust-orange, weight: "bold")[Move 📦],
-        stroke: rust-orange + 2pt
-      ),
-
-      // texto2 (nuevo owner)
-      stack-box((1, 0), [texto2], [0x582...3d00], [5], [5], valid: true),
-
-      // Flecha al heap
-      edge((1, 0), (1, 1), "-|>",
-        label: text(size: 7pt)[ptr],
-        stroke: heap-green + 1.5pt
-      ),
-
-      // Heap label
-      node((1, 0.5),
-        place(dx: 45mm)[
-          #text(size: 10pt, weight: "bold", fill: heap-green)[HEAP]
-        ]
-      ),
-
-      // Datos en heap
-      heap-box((1, 1), [`[R,û,s,t]`], [[82,195,187,115,116]], [0x582...3d00]),
-
-      // Nota explicativa
-      node((0, 0.8),
-        text(size: 7pt, fill: red, style: "italic")[
-          ✗ texto inválido \
-          (ownership movido)
-        ]
-      ),
-    )
-  ) <diag-string-move>
+  #include "/build/generado/memoria/string-move.typ"
 
 
   // Falta agregar mas detalles
@@ -35,6 +35,7 @@ Siempre necesitas `mut` para modificar un HashMap.
 
 *2. Usando `from()` con array de tuplas*
 
+// libro: permitir=rustfmt
 ```rust
 use std::collections::HashMap;
 
@@ -67,7 +68,7 @@ let valores = vec![1, 2, 3];
 
 let mut mapa = HashMap::new();
 
-for (k, v) in claves.into_iter().zip(valores.into_iter()) {
+for (k, v) in claves.into_iter().zip(valores) {
     mapa.insert(k, v);
 }
 
@@ -378,7 +379,7 @@ println!("len: {}, cap: {}", mapa.len(), mapa.capacity());
 ```rust
 use std::collections::HashMap;
 
-let mut mapa = HashMap::new();
+let mut mapa: HashMap<&str, i32> = HashMap::new();
 
 mapa.reserve(100);  // reserva espacio para al menos 100 elementos
 
@@ -472,7 +473,8 @@ let mut mapa = HashMap::new();
 mapa.insert("a", 1);
 mapa.insert("b", 2);
 
-for (clave, valor) in mapa {  // sin &, consume el mapa
+for (clave, valor) in mapa {
+    // sin &, consume el mapa
     println!("{}: {}", clave, valor);
 }
 
@@ -514,6 +516,7 @@ println!("{:?}", mapa);  // {"contador": 2}
 
 *3. Usar `and_modify()` antes de insertar*
 
+// libro: permitir=rustfmt
 ```rust
 use std::collections::HashMap;
 
@@ -661,7 +664,7 @@ let datos = vec![("Ana", 25), ("Luis", 30), ("Ana", 28)];
 let mut grupos: HashMap<&str, Vec<i32>> = HashMap::new();
 
 for (nombre, edad) in datos {
-    grupos.entry(nombre).or_insert(Vec::new()).push(edad);
+    grupos.entry(nombre).or_default().push(edad);
 }
 
 println!("{:?}", grupos);
@@ -926,6 +929,7 @@ println!("{:?}", mapa2);  // {"a": 1, "b": 2}
 
 #text(14pt)[*Resumen rápido*]
 
+// libro: ignore
 ```rust
 use std::collections::HashMap;
 
@@ -957,9 +961,9 @@ mapa.is_empty();
 mapa.capacity();
 
 // Recorrer
-for (k, v) in &mapa { }
-for k in mapa.keys() { }
-for v in mapa.values() { }
+for (k, v) in &mapa {}
+for k in mapa.keys() {}
+for v in mapa.values() {}
 
 // Combinar
 mapa1.extend(mapa2);
@@ -167,7 +167,20 @@ println!("{}", s2);     // s2 sigue disponible
 println!("{}", s3);     // Hola Mundo
 ```
 
-El operador `+` mueve el primer operando y toma prestado el segundo.
+El operador `+` mueve el primer operando y toma prestado el segundo. Si
+descomentamos la línea que usa `s1`, el compilador lo explica:
+
+// libro: id=string-movido, no_compila=E0382
+```rust
+let s1 = String::from("Hola");
+let s2 = String::from(" Mundo");
+
+let s3 = s1 + &s2;
+
+println!("{}", s1);
+```
+
+#include "/build/generado/diagnosticos/string-movido.typ"
 
 *4. Concatenar múltiples strings con `format!`*
 
@@ -221,7 +234,7 @@ Usa `chars()` para acceder:
 let s = String::from("Hola");
 
 // Obtener el primer carácter
-if let Some(primero) = s.chars().nth(0) {
+if let Some(primero) = s.chars().next() {
     println!("Primer carácter: {}", primero);  // H
 }
 
@@ -721,6 +734,15 @@ s.reserve_exact(10);
 println!("cap: {}", s.capacity());  // exactamente 10
 ```
 
+Los valores reales, medidos al compilar el libro:
+
+#include "/build/generado/capacidad/string-metodos.typ"
+
+Al crecer con `push`, un `String` sigue la misma política que un `Vec<u8>`:
+
+#include "/build/generado/capacidad/string-crecimiento.typ"
+#include "/build/generado/capacidad/string-grafico.typ"
+
 #text(14pt)[*Validación UTF-8*]
 
 Rust garantiza que todo `String` es UTF-8 válido:
@@ -5,6 +5,8 @@ cantidad variable de valores del mismo tipo en una secuencia contigua de memoria
 cuyo tamaño es fijo y debe conocerse en tiempo de compilación, los vectores pueden crecer o reducirse durante la
 ejecución del programa.
 
+#import "/src/config/global.typ": salida
+
 #text(14pt)[*Creación de vectores*]
 
 *1. Usando la macro `vec![]`*
@@ -47,7 +49,7 @@ println!("{:?}", v);  // [0, 1, 2, 3, 4]
 *6. Vector con capacidad reservada*
 
 ```rust
-let mut v = Vec::with_capacity(10);
+let v: Vec<i32> = Vec::with_capacity(10);
 println!("len: {}, cap: {}", v.len(), v.capacity());
 // len: 0, cap: 10
 ```
@@ -81,9 +83,9 @@ println!("Segundo elemento: {}", v[1]);  // 20
 
 Si accedes a un índice fuera de rango, el programa entra en `panic` en tiempo de ejecución:
 
-```rust
+```rust,should_panic
 let v = vec![10, 20, 30];
-// let x = v[5];  // panic: index out of bounds
+let x = v[5];  // panic: index out of bounds
 ```
 
 *2. Usando `get()` (recomendado para acceso seguro)*
@@ -430,6 +432,12 @@ v.push(3);  // se queda sin espacio, realocar
 println!("cap: {}", v.capacity());  // 4 (duplicado)
 ```
 
+¿Qué tan rápido crece? Depende del tamaño del elemento y de la versión de Rust.
+Esta tabla se mide al compilar el libro, empujando 100 elementos:
+
+#include "/build/generado/capacidad/vec-crecimiento.typ"
+#include "/build/generado/capacidad/vec-grafico.typ"
+
 *Métodos relacionados con capacidad:*
 
 ```rust
@@ -448,12 +456,15 @@ v.reserve_exact(5);
 println!("cap: {}", v.capacity());  // 8
 ```
 
+#include "/build/generado/capacidad/vec-metodos.typ"
+
 #text(14pt)[*Copia y movimiento*]
 
 Los vectores NO implementan `Copy`, solo `Clone`.
 
 Al asignarlos, se mueven, no se copian automáticamente:
 
+// libro: id=vec-move, memoria
 ```rust
 let v1 = vec![1, 2, 3];
 let v2 = v1;  // v1 se mueve a v2
@@ -462,8 +473,11 @@ let v2 = v1;  // v1 se mueve a v2
 println!("{:?}", v2);     // ✓ [1, 2, 3]
 ```
 
+#include "/build/generado/memoria/vec-move.typ"
+
 Para mantener ambos, usa `clone()`:
 
+// libro: id=vec-clone, memoria
 ```rust
 let v1 = vec![1, 2, 3];
 let v2 = v1.clone();  // copia explícita
@@ -472,6 +486,8 @@ println!("{:?}", v1);  // [1, 2, 3]
 println!("{:?}", v2);  // [1, 2, 3]
 ```
 
+#include "/build/generado/memoria/vec-clone.typ"
+
 #text(14pt)[*Comparaciones*]
 
 Puedes comparar vectores si sus elementos implementan `PartialEq` o `Ord`:
@@ -492,26 +508,33 @@ La comparación es elemento por elemento, de izquierda a derecha.
 
 *1. Ordenar con `sort()`*
 
+// libro: id=vec-sort
 ```rust
 let mut v = vec![3, 1, 4, 1, 5];
 
 v.sort();
 
-println!("{:?}", v);  // [1, 1, 3, 4, 5]
+println!("{:?}", v);
 ```
 
+#salida("vec-sort")
+
 *2. Ordenar de mayor a menor*
 
+// libro: id=vec-sort-desc
 ```rust
 let mut v = vec![3, 1, 4, 1, 5];
 
 v.sort_by(|a, b| b.cmp(a));
 
-println!("{:?}", v);  // [5, 4, 3, 1, 1]
+println!("{:?}", v);
 ```
 
+#salida("vec-sort-desc")
+
 *3. Ordenar con función personalizada `sort_by_key()`*
 
+// libro: id=vec-sort-by-key, permitir=rustfmt
 ```rust
 let mut personas = vec![
     ("Ana", 25),
@@ -522,19 +545,23 @@ let mut personas = vec![
 personas.sort_by_key(|persona| persona.1);  // ordenar por edad
 
 println!("{:?}", personas);
-// [("Pedro", 20), ("Ana", 25), ("Luis", 30)]
 ```
 
+#salida("vec-sort-by-key")
+
 *4. Ordenamiento inestable (más rápido) `sort_unstable()`*
 
+// libro: id=vec-sort-unstable
 ```rust
 let mut v = vec![3, 1, 4, 1, 5];
 
 v.sort_unstable();  // más rápido pero no preserva orden de elementos iguales
 
-println!("{:?}", v);  // [1, 1, 3, 4, 5]
+println!("{:?}", v);
 ```
 
+#salida("vec-sort-unstable")
+
 #text(14pt)[*Búsqueda*]
 
 *1. Verificar si contiene un elemento con `contains()`*
@@ -571,6 +598,7 @@ match v.binary_search(&5) {
 
 Puedes crear vectores dentro de otros vectores (matrices dinámicas):
 
+// libro: permitir=rustfmt
 ```rust
 let matriz = vec![
     vec![1, 2, 3],
@@ -9,6 +9,7 @@ Esto los hace muy rápidos, pero limita su uso a tamaños pequeños o medianos.
 Una vez definido, su tamaño no puede cambiar durante la ejecución del programa.
 
 *Sintaxis general*
+// libro: ignore
 ```rust
 let nombre: [Tipo; tamaño] = [valor1, valor2, valor3, ...];
 ```
@@ -36,6 +37,7 @@ println!("{:#?}", datos);  // formato en líneas
 ```
 
 Resultado con `{:#?}`:
+// libro: ignore
 ```rust
 [
     10,
@@ -58,11 +60,14 @@ let flotantes = [1.5, 2.7, 3.9];
 
 Si mezclas tipos distintos, Rust no compila:
 
+// libro: id=array-mixto, no_compila=E0308
 ```rust
 // Error de compilación:
 let mixto = [1, "dos", 3.0];
 ```
 
+#include "/build/generado/diagnosticos/array-mixto.typ"
+
 #text(14pt)[*Acceso por índice*]
 
 ```rust
@@ -80,10 +85,11 @@ Los índices empiezan en 0, igual que en tuplas.
 
 Rust evita errores de índice fuera de rango en tiempo de compilación cuando es posible.
 
+// libro: no_compila
 ```rust
 let arr = [1, 2, 3];
 
-println!("{}", arr[5]); // panic!
+println!("{}", arr[5]); // error: el índice 5 está fuera de rango
 ```
 
 Acceder fuera del rango genera un panic!.
@@ -244,6 +250,7 @@ let b = a.clone();
 
 #text(14pt)[*Arrays multidimensionales*]
 
+// libro: permitir=rustfmt
 ```rust
 let matriz: [[i32; 3]; 2] = [
     [1, 2, 3],
@@ -258,6 +265,7 @@ Se lee de derecha a izquierda: `[[i32; 3]; 2]` = array de 2 filas, cada fila con
 
 Arrays 3D:
 
+// libro: permitir=rustfmt
 ```rust
 let cubo: [[[i32; 2]; 2]; 2] = [
     [[1, 2], [3, 4]],
@@ -269,6 +277,7 @@ println!("{}", cubo[1][0][1]);  // 6
 
 #text(14pt)[*Métodos útiles*]
 
+// libro: permitir=const_is_empty
 ```rust
 let arr = [5, 10, 15, 20];
 
@@ -295,7 +304,6 @@ let arr = [1, 2, 3, 4, 5];
 // Verificar si contiene un valor
 println!("{}", arr.contains(&3));  // true
 
-
 // Obtener slice
 let slice = &arr[1..4];
 println!("{:?}", slice);  // [2, 3, 4]
@@ -327,7 +335,7 @@ println!("{:?}", tupla);  // (1, 2, 3)
 
 Aunque técnicamente no hay límite de tamaño para arrays, hay consideraciones prácticas:
 
-- Arrays muy grandes (>10,000 elementos) pueden causar stack overflow
+- Arrays muy grandes pueden causar stack overflow: el stack del hilo principal suele ser de 8 MiB en Linux
 - Para colecciones grandes o dinámicas, usa `Vec<T>` que vive en el heap
 
 ```rust
@@ -340,3 +348,17 @@ let pequeno = [0; 100];
 // Mejor usar Vec para colecciones grandes
 let grande = vec![0; 1_000_000];
 ```
+
+Un array de 2 millones de `u64` ocupa 16 MB y no cabe en un stack de 8 MiB. El
+programa no llega a imprimir nada: aborta al entrar en `main`.
+
+// libro: stack_overflow
+```rust
+let enorme = [0u64; 2_000_000];
+println!("{}", enorme.len());
+```
+
+```yaml
+thread 'main' has overflowed its stack
+fatal runtime error: stack overflow, aborting
+```
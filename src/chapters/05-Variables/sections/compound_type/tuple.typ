@@ -48,6 +48,7 @@ Cada valor dentro de la tupla se accede usando un índice con punto '#raw(".",la
 Los índices empiezan en 0.
 
 ```rust
+# let persona = ("Luis", 24, true);
 println!("Nombre: {}", persona.0);  // elemento 1: Luis
 println!("Edad: {}", persona.1);    // elemento 2: 24
 println!("Activa: {}", persona.2);  // elemento 3: true
@@ -80,6 +81,7 @@ println!("{}", edad);  // 25
 Puedes imprimir una tupla con:
 
 ```rust
+# let persona = ("Luis", 24, true);
 println!("{:?}", persona);  // ("Luis", 24, true)
 ```
 
@@ -114,6 +116,7 @@ También se llama:
 - unit type
 - unit value
 
+// libro: permitir=let_unit_value
 ```rust
 fn saludo() {
     println!("Hola!");
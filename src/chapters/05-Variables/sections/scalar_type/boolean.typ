@@ -6,6 +6,7 @@ Un valor booleano solo puede adoptar dos estados posibles:
 - `true` : verdadero
 - `false` : falso
 
+// libro: afirmaciones
 ```rust
 let es_mayor_edad: bool = true;
 let esta_lloviendo: bool = false;
@@ -41,18 +42,7 @@ Rust proporciona un conjunto de operadores lógicos para combinar, invertir o co
 
 El operador `!` invierte el valor de una expresión booleana. Es un operador unario que se aplica a un único operando.
 
-#figure(
-  table(
-    columns: (auto, auto),
-    align: center,
-    inset: 10pt,
-    table.header([*Expresión*], [*Resultado*]),
-    [`!true`], [`false`],
-    [`!false`], [`true`],
-  ),
-  caption: [Tabla de verdad del operador NOT],
-  supplement: [Tabla]
-)
+#include "/build/generado/booleanos/not.typ"
 
 ===== AND
 
@@ -61,20 +51,7 @@ Este operador implementa *short-circuit*: si el primer operando es `false`, el
 segundo no se evalúa.
 
 
-#figure(
-  table(
-    columns: (auto, auto, auto),
-    align: center,
-    inset: 10pt,
-    table.header([*A*], [*B*], [*A && B*]),
-    [`true`], [`true`], [`true`],
-    [`true`], [`false`], [`false`],
-    [`false`], [`true`], [`false`],
-    [`false`], [`false`], [`false`],
-  ),
-  caption: [Tabla de verdad del operador AND],
-  supplement: [Tabla]
-)
+#include "/build/generado/booleanos/and.typ"
 
 Ejemplo práctico:
 
@@ -90,20 +67,8 @@ println!("¿Puede conducir? {}", puede_conducir);  // true
 El operador `||` devuelve `true` si al menos uno de los operandos es verdadero. También implementa
 evaluación perezosa: si el primer operando es `true`, el segundo no se evalúa.
 
-#figure(
-  table(
-    columns: (auto, auto, auto),
-    align: center,
-    inset: 10pt,
-    table.header([*A*], [*B*], [*A || B*]),
-    [`true`], [`true`], [`true`],
-    [`true`], [`false`], [`true`],
-    [`false`], [`true`], [`true`],
-    [`false`], [`false`], [`false`],
-  ),
-  caption: [Tabla de verdad del operador OR],
-  supplement: [Tabla]
-)
+#include "/build/generado/booleanos/or.typ"
+
 Ejemplo práctico:
 
 ```rust
@@ -113,25 +78,21 @@ let puede_descansar = es_fin_semana || es_feriado;
 println!("¿Puede descansar? {}", puede_descansar);  // true
 ```
 
+===== Evaluación en cortocircuito
+
+Que el segundo operando no se evalúe importa cuando tiene efectos: una llamada a
+una función que escribe, cuenta o falla. Los operadores `&` y `|` también
+aceptan `bool`, pero evalúan siempre los dos lados. La @tabla-bool-cortocircuito
+cuenta cuántas veces se llama a una función `derecha()` en cada caso:
+
+#include "/build/generado/booleanos/cortocircuito.typ"
+
 ===== XOR
 
 El operador `^` devuelve `true` únicamente cuando los operandos tienen valores diferentes.
 Es útil para detectar discrepancias o alternar estados.
 
-#figure(
-  table(
-    columns: (auto, auto, auto),
-    align: center,
-    inset: 10pt,
-    table.header([*A*], [*B*], [*A ^ B*]),
-    [`true`], [`true`], [`false`],
-    [`true`], [`false`], [`true`],
-    [`false`], [`true`], [`true`],
-    [`false`], [`false`], [`false`],
-  ),
-  caption: [Tabla de verdad del operador XOR],
-  supplement: [Tabla]
-)
+#include "/build/generado/booleanos/xor.typ"
 
 Ejemplo práctico:
 
@@ -174,6 +135,7 @@ Los operadores de comparación evalúan relaciones entre valores y devuelven un
 
 El tipo `bool` puede convertir valores booleanos a cadenas de texto:
 
+// libro: permitir=to_string_in_format_args
 ```rust
 let verdadero = true;
 let falso = false;
@@ -187,6 +149,7 @@ println!("{}", falso.to_string());      // "false"
 Los valores booleanos pueden convertirse a tipos numéricos mediante el operador `as`. Por convención, `false`
 se convierte en `0` y `true` en `1`:
 
+// libro: afirmaciones
 ```rust
 let verdadero = true;
 let falso = false;
@@ -199,14 +162,15 @@ let valor_f: u8 = falso as u8;        // 0
 
 ==== Precedencia de Operadores
 
-Cuando se combinan múltiples operadores, es importante comprender su orden de evaluación:
+Cuando se combinan múltiples operadores, es importante comprender su orden de evaluación.
+La @tabla-bool-precedencia lo muestra de mayor a menor. Conviene fijarse en dos
+detalles: `^` se aplica antes que `&&` y `||` (e incluso antes que las
+comparaciones), y `!` se aplica antes que `==`, así que `!a == b` significa
+`(!a) == b`.
 
-1. `!`: Mayor precedencia
-2. Operadores de comparación (`==`, `!=`, `<`, `>`, `<=`, `>=`)
-3. `&&`
-4. `||`
-5. `^`: Menor precedencia
+#include "/build/generado/booleanos/precedencia.typ"
 
+// libro: permitir=nonminimal_bool eq_op
 ```rust
 let resultado1 = !false && true || false;
 // Equivale a: ((!false) && true) || false
@@ -227,3 +191,8 @@ let resultado2 = (!false && true) || false;
     *Recomendación:* Aunque Rust tiene reglas claras de precedencia, el uso de paréntesis explícitos mejora la legibilidad del código y previene errores sutiles.
   ]
 )
+
+Cada fila de la @tabla-bool-precedencia está respaldada por una expresión cuyo
+resultado solo es posible con ese orden:
+
+#include "/build/generado/booleanos/pruebas.typ"
@@ -47,6 +47,22 @@ let tab = '\t';
 println!("¿Control?: {}", tab.is_control());          // true
 ```
 
+Estos métodos siguen las propiedades de Unicode, no solo las de ASCII. La
+@tabla-char-clasificacion los aplica a letras con tilde, dígitos de otras
+escrituras y caracteres invisibles: `'٣'` (tres árabe-índico) es numérico pero no
+es un dígito ASCII, y el espacio de ancho cero `'\u{200B}'` no cuenta como espacio
+en blanco.
+
+#include "/build/generado/caracteres/clasificacion.typ"
+
+Aunque un `char` siempre ocupa 4 bytes en memoria, dentro de un `String` se
+guarda en UTF-8 y ocupa entre 1 y 4 bytes. La @tabla-char-unicode muestra el
+código de cada carácter, su codificación y dos conversiones habituales:
+`to_uppercase()` puede devolver más de un carácter (`'ß'` pasa a `"SS"`) y
+`to_digit(10)` solo reconoce los dígitos ASCII.
+
+#include "/build/generado/caracteres/unicode.typ"
+
 
 
 ==== Secuencias de Escape
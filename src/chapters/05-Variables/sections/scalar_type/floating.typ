@@ -10,23 +10,7 @@ otros lenguajes y sistemas, y proporcionando un comportamiento predecible en dif
 
 Rust proporciona dos tipos de punto flotante que difieren en su precisión y rango de representación:
 
-#figure(
-  table(
-    columns: (auto, auto, auto),
-    stroke: 0.5pt + black,
-    align: (center, center, center),
-    inset: 10pt,
-    table.header(
-      [*Tipo*],
-      [*Precisión*],
-      [*Rango aproximado*]
-    ),
-    [#raw("f32", lang: "")], [6–9 dígitos significativos], [$±3.4 × 10^(38)$],
-    [#raw("f64", lang: "")], [15–17 dígitos significativos], [$±1.8 × 10^(308)$],
-  ),
-  caption: [Características de los tipos de punto flotante según IEEE 754],
-  supplement: [Tabla]
-) <tabla-floating-point>
+#include "/build/generado/flotantes/tipos.typ"
 
 *Precisión simple `f32`*: Utiliza 32 bits para almacenar el valor. Es más eficiente en términos de memoria y
 puede ofrecer ventajas de rendimiento.
@@ -76,6 +60,10 @@ let resultado = 0.1 + 0.2;
 println!("{}", resultado);  // Salida: 0.30000000000000004
 ```
 
+La @tabla-flotantes-operaciones recoge este y otros resultados que suelen sorprender:
+
+#include "/build/generado/flotantes/operaciones.typ"
+
 
 ==== Valores Especiales
 
@@ -100,7 +88,7 @@ let raiz_negativa = (-1.0_f64).sqrt();  // NaN
 Rust proporciona métodos para detectar estos casos:
 
 ```rust
-let valor = 1.0 / 0.0;
+let valor: f64 = 1.0 / 0.0;
 
 println!("¿Es infinito?: {}", valor.is_infinite());
 println!("¿Es finito?: {}", valor.is_finite());
@@ -109,15 +97,21 @@ println!("¿Es signo negativo?: {}", valor.is_sign_negative());
 println!("¿Es signo positivo?: {}", valor.is_sign_positive());
 ```
 
+La @tabla-flotantes-especiales aplica estos métodos a cada valor especial. Los
+subnormales son valores tan cercanos a cero que pierden precisión: `is_normal()`
+devuelve `false` para ellos, igual que para el cero.
+
+#include "/build/generado/flotantes/especiales.typ"
+
 ==== Constantes y Límites
 
 Todos los tipos de punto flotante proporcionan constantes útiles:
 
 ```rust
 // Límites numéricos
-println!("f32 min: {}, max: {}", f32::MIN, f32::MAX);
+println!("f32 min: {:e}, max: {:e}", f32::MIN, f32::MAX);
 // -3.4028235e38  3.4028235e38
-println!("f64 min: {}, max: {}", f64::MIN, f64::MAX);
+println!("f64 min: {:e}, max: {:e}", f64::MIN, f64::MAX);
 // -1.7976931348623157e308  1.7976931348623157e308
 ```
 
@@ -128,33 +122,39 @@ Rust proporciona una amplia colección de métodos matemáticos para tipos de pu
 ===== Redondeo
 
 ```rust
-let valor = 3.7;
+let valor: f64 = 3.7;
 
-println!("floor (hacia abajo): {}", valor.floor());     // 3.0
-println!("ceil (hacia arriba): {}", valor.ceil());      // 4.0
-println!("round (más cercano): {}", valor.round());     // 4.0
-println!("trunc (parte entera): {}", valor.trunc());    // 3.0
+println!("floor (hacia abajo): {}", valor.floor());     // 3
+println!("ceil (hacia arriba): {}", valor.ceil());      // 4
+println!("round (más cercano): {}", valor.round());     // 4
+println!("trunc (parte entera): {}", valor.trunc());    // 3
 ```
 
+`{}` no muestra el `.0` de un flotante entero; `{:?}` sí. La @tabla-flotantes-redondeo
+aplica cada método a valores negativos, a los casos intermedios (`2.5` se redondea
+alejándose de cero) y a los valores especiales:
+
+#include "/build/generado/flotantes/redondeo.typ"
+
 ===== Operaciones con Signo
 
 ```rust
-let negativo = -15.8;
+let negativo: f64 = -15.8;
 
 println!("Valor absoluto: {}", negativo.abs());         // 15.8
-println!("Signum (+1, 0 o -1): {}", negativo.signum()); // -1.0
-println!("Copiar signo: {}", 10.0_f64.copysign(negativo)); // -10.0
+println!("Signum (+1 o -1): {}", negativo.signum());    // -1
+println!("Copiar signo: {}", 10.0_f64.copysign(negativo)); // -10
 ```
 
 ===== Potencias y Raíces
 
 ```rust
-let base = 4.0;
+let base: f64 = 4.0;
 
-println!("Potencia entera: {}", base.powi(3));          // 64.0
-println!("Potencia decimal: {}", base.powf(1.5));       // 8.0
-println!("Raíz cuadrada: {}", base.sqrt());             // 2.0
-println!("Raíz cúbica: {}", 27.0_f64.cbrt());           // 3.0
+println!("Potencia entera: {}", base.powi(3));          // 64
+println!("Potencia decimal: {}", base.powf(1.5));       // 8
+println!("Raíz cuadrada: {}", base.sqrt());             // 2
+println!("Raíz cúbica: {}", 27.0_f64.cbrt());           // 3
 ```
 
 
@@ -162,9 +162,9 @@ println!("Raíz cúbica: {}", 27.0_f64.cbrt());           // 3.0
 ===== Descomposición de Valores
 
 ```rust
-let numero:f32 = 42.75;
+let numero: f32 = 42.75;
 
-println!("Parte entera: {}", numero.trunc());           // 42.0
+println!("Parte entera: {}", numero.trunc());           // 42
 println!("Parte fraccionaria: {}", numero.fract());     // 0.75
 ```
 
@@ -188,10 +188,16 @@ println!("Ancho 10, 2 dec: {:10.2}", valor);    // "    123.46"
 println!("Alineado izq: {:<10.2}", valor);      // "123.46    "
 ```
 
+La @tabla-flotantes-formato muestra cómo se imprimen los valores especiales con cada
+especificador:
+
+#include "/build/generado/flotantes/formato.typ"
+
 ==== Conversiones entre Tipos
 
 Al igual que con los enteros, las conversiones entre tipos de punto flotante deben ser explícitas:
 
+// libro: afirmaciones
 ```rust
 let x: f64 = 3.14159265359;
 
@@ -18,24 +18,7 @@ Los enteros con signo son aquellos tipos enteros que pueden representar tanto va
 positivos como negativos, además del cero. En Rust, se identifican por el prefijo #raw("i",lang:"rust")
 seguido del número de bits: i8, i16, i32, i64 y i128.
 
-#figure(
-  table(
-    columns: (auto, auto, auto),
-    align: (center, right, right),
-    table.header(
-      [*Tipo*],
-      [*Valor mínimo*],
-      [*Valor máximo*]
-    ),
-    [#raw("i8", lang: "")], [−128], [127],
-    [#raw("i16", lang: "")], [−32 768], [32 767],
-    [#raw("i32", lang: "")], [−2 147 483 648], [2 147 483 647],
-    [#raw("i64", lang: "")], [−9 223 372 036 854 775 808], [9 223 372 036 854 775 807],
-    [#raw("i128", lang: "")], [#raw("−2¹²⁷", lang: "")], [#raw("2¹²⁷ − 1", lang: "")],
-  ),
-  caption: [Rangos numéricos de los tipos enteros con signo],
-  supplement: [Tabla]
-) <tabla-enteros-signados>
+#include "/build/generado/enteros/con-signo.typ"
 
 El número en el nombre del tipo indica la cantidad de bits utilizados para almacenar el valor. Por ejemplo, `i8` utiliza 8 bits, mientras que `i128` utiliza 128 bits.
 
@@ -49,24 +32,7 @@ let deuda: i64 = -9_223_372_036_854_775_808;
 
 Los enteros sin signo representan exclusivamente valores no negativos (positivos y cero). Al no reservar un bit para el signo, pueden almacenar números positivos de mayor magnitud utilizando la misma cantidad de bits que sus equivalentes con signo.
 
-#figure(
-  table(
-    columns: (auto, auto, auto),
-    align: (center, right, right),
-    table.header(
-      [*Tipo*],
-      [*Valor mínimo*],
-      [*Valor máximo*]
-    ),
-    [#raw("u8", lang: "")], [0], [255],
-    [#raw("u16", lang: "")], [0], [65 535],
-    [#raw("u32", lang: "")], [0], [4 294 967 295],
-    [#raw("u64", lang: "")], [0], [18 446 744 073 709 551 615],
-    [#raw("u128", lang: "")], [0], [#raw("2¹²⁸ − 1", lang: "")],
-  ),
-  caption: [Rangos numéricos de los tipos enteros sin signo],
-  supplement: [Tabla]
-) <tabla-enteros-no-signados>
+#include "/build/generado/enteros/sin-signo.typ"
 
 Estos tipos son ideales para contadores, índices de arrays, y cualquier magnitud que por definición no pueda ser negativa.
 
@@ -78,25 +44,9 @@ let bytes_procesados: u64 = 18_446_744_073_709_551_615;
 
 ===== Tipos Dependientes de Arquitectura: `usize` e `isize`
 
-A diferencia de los tipos de tamaño fijo, `usize` e `isize` tienen un tamaño que se adapta automáticamente a la arquitectura del procesador donde se compila el programa. En sistemas de 32 bits, estos tipos equivalen a `u32` e `i32` respectivamente; en sistemas de 64 bits, a `u64` e `i64`.
+A diferencia de los tipos de tamaño fijo, `usize` e `isize` tienen un tamaño que se adapta automáticamente a la arquitectura del procesador donde se compila el programa. En sistemas de 32 bits, estos tipos equivalen a `u32` e `i32` respectivamente; en sistemas de 64 bits, a `u64` e `i64`. La @tabla-enteros-arquitectura muestra los valores de la máquina donde se generó este libro.
 
-#figure(
-  table(
-    columns: (auto, auto, auto),
-    align: (center, right, right),
-    inset: 10pt,
-    stroke: 0.5pt + black,
-    table.header(
-      [*Tipo*],
-      [*Valor mínimo*],
-      [*Valor máximo*]
-    ),
-    [#raw("usize", lang: "")], [0], [#raw("2³² − 1", lang: "") o #raw("2⁶⁴ − 1", lang: "")],
-    [#raw("isize", lang: "")], [#raw("−2³¹", lang: "") o #raw("−2⁶³", lang: "")], [#raw("2³¹ − 1", lang: "") o #raw("2⁶³ − 1", lang: "")],
-  ),
-  caption: [Rangos numéricos de los tipos enteros dependientes de arquitectura],
-  supplement: [Tabla]
-) <tabla-enteros-arquitectura>
+#include "/build/generado/enteros/arquitectura.typ"
 
 Estos tipos se utilizan principalmente para:
 - Indexar colecciones (vectores, arrays, slices)
@@ -142,6 +92,10 @@ let c = 1_000i64;    // i64 explícito
 let d = 500usize;    // usize explícito
 ```
 
+La @tabla-enteros-sufijos muestra el tipo que el compilador asigna a cada literal y el espacio que ocupa:
+
+#include "/build/generado/enteros/sufijos.typ"
+
 Esta sintaxis es especialmente útil en expresiones donde la inferencia de tipos podría ser ambigua.
 
 ==== Representación de Literales Enteros
@@ -154,7 +108,7 @@ Para mejorar la legibilidad de números grandes, Rust permite el uso del guion b
 let millones = 1_000_000;
 let billion = 1_000_000_000;
 let bits = 0b1111_0000_1010_1010;
-let hex = 0xdead_beef;
+let hex: u32 = 0xdead_beef;
 ```
 
 ===== Bases Numéricas
@@ -183,9 +137,15 @@ El desbordamiento ocurre cuando una operación aritmética produce un resultado
 
 Rust inserta verificaciones automáticas que provocan un pánico en tiempo de ejecución cuando se detecta un desbordamiento, facilitando la detección temprana de errores.
 
+// libro: panico=attempt to add with overflow
 ```rust
-let x: u8 = 255;
-let y = x + 1;  // Pánico: intento de sumar con desbordamiento
+fn siguiente(x: u8) -> u8 {
+    x + 1
+}
+
+fn main() {
+    let y = siguiente(255);  // Pánico: attempt to add with overflow
+}
 ```
 
 *Modo release*:
@@ -193,15 +153,28 @@ let y = x + 1;  // Pánico: intento de sumar con desbordamiento
 Por razones de rendimiento, las verificaciones se eliminan y el desbordamiento produce un comportamiento
 de *wrapping*, donde el valor "da la vuelta" al rango válido.
 
+// libro: afirmaciones, release
 ```rust
-let x: u8 = 255;
-let y = x + 1;  // y = 0 (wrapping sin pánico)
+fn siguiente(x: u8) -> u8 {
+    x + 1
+}
+
+fn main() {
+    let y = siguiente(255);  // 0 (wrapping sin pánico)
+}
 ```
 
+Ambos ejemplos pasan el valor a través de una función a propósito. Si el desbordamiento
+se puede calcular al compilar, como en `let x: u8 = 255; let y = x + 1;`, `rustc` ni
+siquiera genera el programa: lo rechaza con el error _this arithmetic operation will overflow_.
+
+#include "/build/generado/enteros/desbordamiento.typ"
+
 ===== Control Explícito del Desbordamiento
 
 Para manejar el desbordamiento de forma predecible independientemente del perfil de compilación, Rust proporciona métodos específicos:
 
+// libro: afirmaciones
 ```rust
 let x: u8 = 255;
 
@@ -222,6 +195,7 @@ let (d, overflow) = x.overflowing_add(1); // (0, true)
 
 Rust no realiza conversiones implícitas entre tipos enteros, incluso cuando la conversión sería segura. Todas las conversiones deben ser explícitas utilizando el operador `as`:
 
+// libro: afirmaciones
 ```rust
 let a: u8 = 100;
 let b: u16 = a as u16;  // Conversión segura (widening)
@@ -253,17 +227,23 @@ println!("Rango de i16: {} a {}", i16::MIN, i16::MAX);
 println!("Rango de u32: {} a {}", u32::MIN, u32::MAX);
 println!("Rango de isize: {} a {}", isize::MIN, isize::MAX);
 ```
+
+La @tabla-enteros-constantes reúne estas constantes, junto con `BITS` y el tamaño en memoria, para todos los tipos enteros:
+
+#include "/build/generado/enteros/constantes.typ"
+
 ==== Operaciones y métodos comunes
 
+// libro: permitir=to_string_in_format_args
 ```rust
 let x: i32 = 42;
 
 println!("Abs: {}", x.abs());        // valor absoluto
 println!("Pow: {}", x.pow(3));       // potencia (42^3)
-println!("{}",x.is_positive());      // es positivo?
-println!("{}",x.is_negative());      // es negativo?
-println!("{}",x.to_string());        // convierte a una cadena de texto
-println!("{}",x.signum());
+println!("{}", x.is_positive());     // es positivo?
+println!("{}", x.is_negative());     // es negativo?
+println!("{}", x.to_string());       // convierte a una cadena de texto
+println!("{}", x.signum());
 ```
 
 #figure(
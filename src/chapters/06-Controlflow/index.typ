@@ -14,6 +14,7 @@ En Rust, if no es solo una estructura de control es una expresión, lo que signi
 
 Sintaxis de un if expression:
 
+// libro: ignore
 ```rust
 if condicion {
     // rama verdadera
@@ -41,6 +42,7 @@ fn main() {
 Como if devuelve un valor, puedes asignarlo directamente a una variable:
 
 ```rust
+# let numero = 10;
 let mensaje = if numero > 5 {
     "El numero 10 es mayor que 5"
 } else {
@@ -58,14 +60,10 @@ También puedes encadenar múltiples condiciones:
 ```rust
 let numero = 15;
 
-let resultado:&str = if numero < 0 {
-
+let resultado: &str = if numero < 0 {
     "Negativo"
-
 } else if numero == 0 {
-
     "Cero"
-
 } else {
     "Positivo"
 };
@@ -80,18 +78,21 @@ Regla importante en Rust:
 
 Todas las ramas de un if deben devolver el mismo tipo.
 ```rust
-let x:i32 = 5;
-let y:i32 = if x > 0 { 1 } else { -1 }; // ambas ramas son i32
+let x: i32 = 5;
+let y: i32 = if x > 0 { 1 } else { -1 }; // ambas ramas son i32
 ```
 
 Esto seria un error:
 
+// libro: id=if-tipos-distintos, no_compila=E0308
 ```rust
 let x = 5;
 let y = if x > 0 { 1 } else { "menor o igual a cero" };
 // Error: tipos distintos (i32 vs &str)
 ```
 
+#include "/build/generado/diagnosticos/if-tipos-distintos.typ"
+
 
 
 #text(
@@ -123,6 +124,7 @@ println!("Resultado: {}", res);
   14pt
 )[*if con shadowing*]
 
+// libro: permitir=rustfmt
 ```rust
 let edad = 20;
 
@@ -141,7 +143,9 @@ println!("{}", edad);
 
 if es 100% una expresión, no solo un control de flujo.
 
+// libro: permitir=rustfmt
 ```rust
+# let x = 5;
 let mensaje = format!(
     "Estado: {}",
     if x > 0 { "positivo" } else { "no positivo" }
@@ -168,15 +172,15 @@ let x = false;
 let y = true;
 
 if x && (2 > 1) {
-  println!("No se ejecuta");
+    println!("No se ejecuta");
 }
 
 if y || (2 < 1) {
-  println!("Se ejecuta sin evaluar 2 < 1");
+    println!("Se ejecuta sin evaluar 2 < 1");
 }
 
 if !x {
-  println!("!x = true");
+    println!("!x = true");
 }
 ```
 
@@ -195,6 +199,7 @@ se cumpla una condición o hasta que se indique explícitamente que debe detener
 
 Sintaxis básica:
 
+// libro: no_termina, permitir=empty_loop
 ```rust
 loop {
     // código que se repite
@@ -233,7 +238,6 @@ Contador: 2
 Contador: 3
 Contador: 4
 Contador: 5
-Fin del loop
 ```
 
 #text(
@@ -307,6 +311,7 @@ especialmente en bucles anidados.
 
 Sintaxis básica:
 
+// libro: no_termina, permitir=empty_loop
 ```rust
 'mi_bucle: loop {
     // ...
@@ -321,6 +326,7 @@ Sin labels, break y continue solo afectan al bucle más interno.
 
 Ejemplo sin label:
 
+// libro: no_termina, permitir=never_loop
 ```rust
 loop {
     loop {
@@ -347,7 +353,10 @@ fn main() {
         let valor = numeros[indice];
 
         if valor == 30 {
-            println!("¡Encontramos el número {} en la posición {}!", valor, indice);
+            println!(
+                "¡Encontramos el número {} en la posición {}!",
+                valor, indice
+            );
             break 'busqueda;
         }
 
@@ -375,15 +384,18 @@ Ejemplo de loops anidados:
 
 Este ejemplo muestra por qué existen las etiquetas: para poder romper bucles externos desde dentro de bucles internos.
 
+// libro: permitir=never_loop
 ```rust
 fn main() {
     let mut fila = 0;
     let mut columna = 0;
 
-    'outer: loop {       // loop externo
+    'outer: loop {
+        // loop externo
         println!("Fila {}", fila);
 
-        'inner: loop {   // loop interno
+        'inner: loop {
+            // loop interno
             println!("  Columna {}", columna);
 
             if columna == 2 {
@@ -423,6 +435,7 @@ Fin del programa.
 El bucle while repite un bloque de código mientras una condición booleana sea verdadera. Es útil cuando no sabes de antemano cuántas iteraciones habrá, pero sí tienes una condición que determina cuándo detenerte.
 
 Sintaxis:
+// libro: ignore
 ```rust
 while condicion {
     // código que se ejecuta mientras condicion sea true
@@ -540,6 +553,7 @@ Los patrones permiten describir de forma declarativa qué valores queremos captu
 
 Patrón literal
 ```rust
+# let x = 5;
 match x {
     0 => println!("Cero"),
     5 => println!("Cinco"),
@@ -547,7 +561,9 @@ match x {
 }
 ```
 Patrón con múltiples alternativas
+// libro: permitir=manual_range_patterns
 ```rust
+# let x = 2;
 match x {
     1 | 2 | 3 => println!("Uno, dos o tres"),
     _ => println!("Otro"),
@@ -555,6 +571,7 @@ match x {
 ```
 Patrones con variables
 ```rust
+# let x = 7;
 match x {
     10 => println!("Diez"),
     otro => println!("Otro valor: {}", otro),
@@ -611,11 +628,11 @@ Está en el eje Y, valor: 5
 
 ```rust
 fn main() {
-    let numero:i8 = 8;
+    let numero: i8 = 8;
     match numero {
         value if value % 2 == 0 => println!("El numero {numero} es par"),
         value if value % 2 == 1 => println!("El numero {numero} es impar"),
-         _ => unreachable!(), // aquí decimos que nunca debería entrar
+        _ => unreachable!(), // aquí decimos que nunca debería entrar
     }
 }
 ```
@@ -982,6 +999,7 @@ Salida:
 
 #text(14pt)[*Iterar sobre arrays multidimensionales*]
 
+// libro: permitir=rustfmt
 ```rust
 let matriz = [
     [1, 2, 3],
@@ -1170,7 +1188,8 @@ Ventajas del `for` idiomático:
 ```rust
 let vec = vec![1, 2, 3];
 
-for item in vec {  // vec se mueve
+for item in vec {
+    // vec se mueve
     println!("{}", item);
 }
 
@@ -1182,7 +1201,8 @@ for item in vec {  // vec se mueve
 ```rust
 let vec = vec![1, 2, 3];
 
-for item in &vec {  // vec se presta
+for item in &vec {
+    // vec se presta
     println!("{}", item);
 }
 
@@ -1203,6 +1223,7 @@ println!("{:?}", vec);  // [2, 4, 6]
 
 #text(14pt)[*Resumen de for*]
 
+// libro: ignore
 ```rust
 // Rangos
 for i in 0..5 { }
@@ -4,6 +4,7 @@
 El bucle for es uno de los más utilizados en Rust para iterar de manera segura y eficiente sobre rangos de valores o colecciones de datos. A diferencia de lenguajes como C, Rust no usa for con índices manuales esto para evitar errores comunes como off-by-one, sino que se basa en el trait IntoIterator. Esto hace que el código sea más idiomático, genérico y libre de errores de memoria.
 
 Sintaxis básica:
+// libro: ignore
 ```rust
 for item in iterable {
     // Código que usa `item` en cada iteración
@@ -50,9 +51,7 @@ fn main() {
 - Paso personalizado: *"`.step_by(n)`"* salta elementos.
 ```rust
 fn main() {
-
     for i in (0..10).step_by(2) {
-
         println!("{}", i);  // Salida: 0 2 4 6 8
     }
 }
@@ -142,7 +141,8 @@ fn main() {
     let arr3 = [700, 800, 900];
 
     println!("Iterando con iter():");
-    for &x in arr3.iter() {  // Desreferenciamos (&i32 → i32)
+    for &x in arr3.iter() {
+        // Desreferenciamos (&i32 → i32)
         println!("{}", x);
     }
 
@@ -285,6 +285,7 @@ fn main() {
 
 A veces, también puedes iterar manualmente con índices si necesitas acceder a la posición.
 
+// libro: permitir=needless_range_loop
 ```rust
 fn main() {
     let vec5 = vec!["A", "B", "C"];
@@ -308,8 +309,9 @@ Una tupla en sí no es iterable.
 
 No puedes hacer directamente:
 
+// libro: no_compila=E0277
 ```rust
-for x in (1, 2, 3) { } // Error
+for x in (1, 2, 3) {} // Error
 ```
 
 Esto se debe a que una tupla es una estructura de longitud fija y heterogénea, no una secuencia.
@@ -334,7 +336,8 @@ Si queremos conservarlo:
 ```rust
 let mapa = vec![(1, 2), (3, 4)];
 
-for (a, b) in &mapa { // iterando por referencia
+for (a, b) in &mapa {
+    // iterando por referencia
     println!("{} + {} = {}", a, b, a + b);
 }
 ```
@@ -426,7 +429,8 @@ Ejemplo mutable:
 ```rust
 fn main() {
     let mut vec = vec![1, 2, 3];
-    for num in vec.iter_mut() {  // Itera con mut borrow
+    for num in vec.iter_mut() {
+        // Itera con mut borrow
         *num += 1;
     }
     println!("{:?}", vec); // [2, 3, 4]
@@ -466,6 +470,7 @@ let v = vec![1, 2, 3, 4, 5];
 let pares: Vec<_> = v.iter().filter(|x| *x % 2 == 0).collect();
 println!("{:?}", pares); // [2, 4]
 ```
+// libro: permitir=unnecessary_fold
 - *`fold()`* : acumula (reduce)```rust
 let suma = (1..=5).fold(0, |acc, x| acc + x);
 println!("{}", suma); // 15
@@ -509,7 +514,8 @@ fn main() {
 
     let b = vec!["uno", "dos", "tres"];
 
-    for (num, texto) in a.iter().zip(b.iter()) {  // Tupla (i32, &str)
+    for (num, texto) in a.iter().zip(b.iter()) {
+        // Tupla (i32, &str)
         println!("{}: {}", num, texto);
     }
 
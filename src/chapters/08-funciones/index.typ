@@ -5,6 +5,7 @@ Las funciones son bloques de código reutilizables diseñados para realizar una
 Se declaran con la palabra clave `fn`, y Rust enfatiza la seguridad de tipos: los parámetros siempre requieren tipos explícitos, y los retornos son opcionales pero tipados.
 
 Sintaxis básica:
+// libro: ignore
 ```rust
 fn nombre_funcion(param1: Tipo1, param2: Tipo2) -> TipoRetorno {
     // Cuerpo de la función
@@ -59,6 +60,7 @@ fn main() {
 ```
 
 Si agregas *"`;`"*:
+// libro: permitir=no_effect
 ```rust
 fn cuadrado_con_semi(x: i32) -> i32 {
     x * x;  // Con ";"  devuelve ()
@@ -70,25 +72,27 @@ fn cuadrado_con_semi(x: i32) -> i32 {
 
 Para salidas tempranas "early returns", usa *"`return expr;`"*. Es opcional al final, pero útil en condicionales.
 
+// libro: permitir=needless_return
 ```rust
 fn main() {
     let a = 15;
     let b = 9;
 
-    println!("El valor mayor de {a} y {b} es : {}", valor_mayor(a , b));
+    println!("El valor mayor de {a} y {b} es : {}", valor_mayor(a, b));
 }
 
-fn valor_mayor(a:i32, b:i32) -> i32{
+fn valor_mayor(a: i32, b: i32) -> i32 {
     if a > b {
-        return a
-    }else{
-        return b
+        return a;
+    } else {
+        return b;
     }
 }
 ```
 
 Otro ejemplo: Verificar paridad.
 
+// libro: permitir=needless_return
 ```rust
 fn es_par(n: i32) -> bool {
     if n % 2 == 0 {
@@ -110,7 +114,8 @@ fn es_par_ret(n: i32) -> bool {
 Rust permite definir funciones dentro de otras "*inner functions*", pero solo visibles en el scope padre. Útiles para helpers locales.
 ```rust
 fn principal() {
-    fn interna(x: i32) -> i32 {  // Anidada
+    fn interna(x: i32) -> i32 {
+        // Anidada
         x + 1
     }
     println!("{}", interna(5));  // 6
@@ -128,6 +133,7 @@ fn main() {
 Los closures son funciones anónimas que capturan el entorno "*variables externas*". Son como lambdas en Python/JS, pero con reglas estrictas de captura "*borrow/move*". Ideales para iteradores, callbacks y FP.
 
 Sintaxis:
+// libro: ignore
 ```rust
 let nombre_closure = |param1: Tipo1, param2: Tipo2| -> TipoRetorno {
     // Cuerpo (expresión o bloque)
@@ -136,8 +142,8 @@ let nombre_closure = |param1: Tipo1, param2: Tipo2| -> TipoRetorno {
 
 Ejemplo básico:
 ```rust
-let resta = |a: i32, b: i32| -> i32 {a - b};  // Explícito
-let suma  = |a: i32, b: i32| a + b;  // Infieren tipos si posible
+let resta = |a: i32, b: i32| -> i32 { a - b };  // Explícito
+let suma = |a: i32, b: i32| a + b;  // Infieren tipos si posible
 println!("{}", suma(2, 3));         // 5
 println!("{}", resta(5, 3));         // 2
 ```
@@ -199,7 +205,8 @@ Pasa datos grandes por &T para evitar copias/moves.
 
 ```rust
 /// Calcula longitud sin tomar ownership.
-fn longitud(texto: &String) -> usize {  // &String (o &str para más flex)
+fn longitud(texto: &str) -> usize {
+    // &str acepta tanto &String como literales
     texto.len()
 }
 
@@ -300,6 +307,7 @@ Puntos clave:
 
 #text(14pt)[Ejemplo 4: Intercambiar valores (swap)]
 
+// libro: permitir=manual_swap
 ```rust
 fn intercambiar(a: &mut i32, b: &mut i32) {
     let temp = *a;
@@ -342,6 +350,7 @@ Si intentas pasarle un `f64`, no compila.
 
 Ejemplo genérico básico
 
+// libro: permitir=needless_pass_by_value
 ```rust
 fn mostrar<T>(x: T) {
     println!("Tengo un valor!");
@@ -379,9 +388,9 @@ Veamos cómo intercambiar dos valores de cualquier tipo (sin importar si son i32
 
 ```rust
 fn intercambiar<T>(a: &mut T, b: &mut T) {
-    let temp = *a;     // mueve el valor de a
-    *a = *b;           // reemplaza a por b
-    *b = temp;         // reemplaza b por temp
+    // `let temp = *a;` no compila: no se puede mover un `T` fuera de
+    // una referencia. `std::mem::swap` intercambia los dos valores.
+    std::mem::swap(a, b);
 }
 
 fn main() {
@@ -34,6 +34,11 @@ struct Libro {
 Para crear una instancia del #raw("struct",lang:"rust"), se usa las llaves #raw("{ }",lang:"rust") indicando los valores de cada campo.
 
 ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
 fn main() {
     let libro = Libro {
         titulo: String::from("Domina Rust"),
@@ -55,11 +60,21 @@ Reglas importantes:
 
 Los campos se acceden con el operador #raw("`.`",lang:"bash"):
 ```rust
-  // --snip--
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
+# let libro = Libro {
+    # titulo: String::from("Domina Rust"),
+    # autor: String::from("Alex Villanueva"),
+    # anio_publicacion: 2025,
+# };
+// --snip--
 
-  println!("Título: {}", libro.titulo);   // "Domina Rust"
-  println!("Autor: {}", libro.autor);     // "Alex Villanueva"
-  println!("Año: {}", libro.anio_publicacion);  // 2025
+println!("Título: {}", libro.titulo);   // "Domina Rust"
+println!("Autor: {}", libro.autor);     // "Alex Villanueva"
+println!("Año: {}", libro.anio_publicacion);  // 2025
 ```
 
 == Structs Mutables
@@ -67,8 +82,16 @@ Los campos se acceden con el operador #raw("`.`",lang:"bash"):
 Para modificar los campos de un struct, la instancia debe ser mutable #raw("mut",lang:"rust"):
 
 ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
 let mut libro = Libro {
     // --snip--
+    # titulo: String::from("Domina Rust"),
+    # autor: String::from("Alex Villanueva"),
+    # anio_publicacion: 2025,
 };
 
 libro.anio_publicacion = 2026; // Modificamos un campo
@@ -81,6 +104,16 @@ Si la variable no es mutable, sus campos tampoco lo serán.
 Puedes crear una nueva instancia copiando algunos campos de otro struct con la sintaxis "#raw("..",lang:"rust")":
 
 ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
+# let libro = Libro {
+    # titulo: String::from("Domina Rust"),
+    # autor: String::from("Alex Villanueva"),
+    # anio_publicacion: 2026,
+# };
 // --snip--
 
 let libro2 = Libro {
@@ -132,9 +165,23 @@ let x = SinDatos;
 Podemos extraer los campos de un struct en variables individuales:
 
 ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
+# let libro2 = Libro {
+    # titulo: String::from("Aprende RusT"),
+    # autor: String::from("Alex Villanueva"),
+    # anio_publicacion: 2026,
+# };
 // --snip--
 
-let Libro { titulo, autor, anio_publicacion } = libro2;
+let Libro {
+    titulo,
+    autor,
+    anio_publicacion,
+} = libro2;
 println!("{} - {} ({})", titulo, autor, anio_publicacion);
 ```
 
@@ -146,22 +193,38 @@ Rust puede generar implementaciones comunes automáticamente con  #raw("#[derive
 #[derive(Debug, Clone, PartialEq)]
 struct Libro {
     // --snip--
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
 }
 
 fn main() {
     // --snip--
-    let Libro { titulo, autor, anio_publicacion } = libro2.clone();
+    # let libro = Libro {
+        # titulo: String::from("Domina Rust"),
+        # autor: String::from("Alex Villanueva"),
+        # anio_publicacion: 2025,
+    # };
+    # let libro2 = Libro {
+        # titulo: String::from("Aprende RusT"),
+        # ..libro.clone()
+    # };
+    let Libro {
+        titulo,
+        autor,
+        anio_publicacion,
+    } = libro2.clone();
     // --snip--
     println!("{:?}", libro); // Usa Debug
     println!("{:#?}", libro); // Usa Debug
 
-    let libro3 = Libro{
+    let libro3 = Libro {
         titulo: String::from("EFFECTIVE RUST"),
         autor: String::from("David Drysdale"),
         anio_publicacion: 2000,
     };
 
-    println!("{}",libro2 == libro3); // False
+    println!("{}", libro2 == libro3); // False
 }
 ```
 
@@ -178,6 +241,11 @@ Esto transforma nuestros structs de ser simples contenedores de datos estaticos
 Sintaxis:
 
 ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
 impl Libro {
     // Aquí van los métodos y funciones asociadas
 }
@@ -186,26 +254,30 @@ impl Libro {
 Ejemplo básico:
 
 ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
 // --snip--
 impl Libro {
-  fn new(titulo: String, autor: String, anio_publicacion: u32) -> Libro {
-    Libro {
-      titulo,
-      autor,
-      anio_publicacion
+    fn new(titulo: String, autor: String, anio_publicacion: u32) -> Libro {
+        Libro {
+            titulo,
+            autor,
+            anio_publicacion,
+        }
     }
-  }
-
 }
 
 fn main() {
-  // --snip--
-  let libro4 = Libro::new(
-    String::from("Domina Rust"),
-    String::from("Alex Villanueva"),
-    2025
-  );
-  // Usa :: para métodos asociados
+    // --snip--
+    let libro4 = Libro::new(
+        String::from("Domina Rust"),
+        String::from("Alex Villanueva"),
+        2025,
+    );
+    // Usa :: para métodos asociados
 }
 ```
 
@@ -219,6 +291,7 @@ Es una forma abreviada de escribir el nombre completo del tipo. #raw("Self = Lib
   columns: (1fr,1fr),
   gutter: 12pt,
   [
+// libro: ignore
 ```rust
 fn new(..) -> Self {
   Self {
@@ -228,6 +301,7 @@ fn new(..) -> Self {
 ```
   ],
   [
+// libro: ignore
 ```rust
 fn new(..) -> Libro {
   Libro {
@@ -253,8 +327,14 @@ Todos los métodos de instancia usan #raw("self",lang:"rust") como primer parám
   - Se traduce a: #raw("self: &Self",lang:"rust").
 
   Ejemplo:```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
 impl Libro {
-    fn es_reciente(&self) -> bool {  // &self: solo lee
+    fn es_reciente(&self) -> bool {
+        // &self: solo lee
         self.anio_publicacion >= 2020
     }
 }
@@ -265,18 +345,32 @@ fn es_reciente(&self)  ->  fn es_reciente(self: &Libro)
 ```
 
   ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
+# impl Libro {
+    # fn new(titulo: String, autor: String, anio_publicacion: u32) -> Libro {
+        # Libro {
+            # titulo,
+            # autor,
+            # anio_publicacion,
+        # }
+    # }
+    # fn es_reciente(&self) -> bool {
+        # self.anio_publicacion >= 2020
+    # }
+# }
 fn main() {
     // --snip--
 
-  let libro4 = Libro::nuevo(
-    String::from("Nuevo Libro"),
-    String::from("Autor X"),
-    2025
-  );
+    let libro4 =
+        Libro::new(String::from("Nuevo Libro"), String::from("Autor X"), 2025);
 
-  println!("¿Es reciente? {}", libro4.es_reciente());  // true
-  // libro sigue intacto, puedes usarlo después
-  println!("Año: {}", libro4.anio_publicacion);  // 2026
+    println!("¿Es reciente? {}", libro4.es_reciente());  // true
+    // libro sigue intacto, puedes usarlo después
+    println!("Año: {}", libro4.anio_publicacion);  // 2025
 }
 ```
 
@@ -288,8 +382,14 @@ fn main() {
   - Solo una mutación a la vez.
 
   Ejemplo:```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
 impl Libro {
-    fn actualizar_año(&mut self, nuevo_año: u32) {  // &mut self: modifica
+    fn actualizar_año(&mut self, nuevo_año: u32) {
+        // &mut self: modifica
         self.anio_publicacion = nuevo_año;
     }
 }
@@ -304,12 +404,34 @@ fn actualizar_año(&mut self,..)  ->  fn actualizar_año(self: &mut Libro,..)
 
     - #raw("&mut self",lang:"rust").
   ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
+# impl Libro {
+    # fn new(titulo: String, autor: String, anio_publicacion: u32) -> Libro {
+        # Libro {
+            # titulo,
+            # autor,
+            # anio_publicacion,
+        # }
+    # }
+    # fn actualizar_año(&mut self, nuevo_año: u32) {
+        # self.anio_publicacion = nuevo_año;
+    # }
+# }
 fn main() {
     // --snip--
-
-  libro4.actualizar_año(2027);  // Cambia vía método
-  println!("Año actualizado: {}", libro.anio_publicacion);  // 2027
-  // libro mut sigue vivo y modificado
+    # let mut libro4 = Libro::new(
+        # String::from("Domina Rust"),
+        # String::from("Alex Villanueva"),
+        # 2025,
+    # );
+
+    libro4.actualizar_año(2027);  // Cambia vía método
+    println!("Año actualizado: {}", libro4.anio_publicacion);  // 2027
+    // libro mut sigue vivo y modificado
 }
 ```
 
@@ -321,19 +443,28 @@ fn main() {
 
   Ejemplo:
   ```rust
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
 impl Libro {
-
-    fn a_string(self) -> String {  // self: consume
-        format!("{} por {} ({})",
-          self.titulo,
-          self.autor,
-          self.anio_publicacion
+    fn a_string(self) -> String {
+        // self: consume
+        format!(
+            "{} por {} ({})",
+            self.titulo, self.autor, self.anio_publicacion
         )
     }
 }
 
 fn main() {
     // --snip--
+    # let libro4 = Libro {
+        # titulo: String::from("Domina Rust"),
+        # autor: String::from("Alex Villanueva"),
+        # anio_publicacion: 2027,
+    # };
 
     let descripcion = libro4.a_string();  // Consume libro4
     println!("{}", descripcion);  // Domina Rust por Alex Villanueva (2027)
@@ -346,9 +477,14 @@ fn main() {
 
   Se usan normalmente en las firmas de métodos estáticos o asociados, o cuando defines tipos o traits genéricos, donde Self hace que tu código sea más genérico y expresivo.
   ```rust
-impl Libro{
-
-    fn comparar(&self, otro: &Self) -> bool {   // &Self = &Libro
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
+impl Libro {
+    fn comparar(&self, otro: &Self) -> bool {
+        // &Self = &Libro
         self.titulo == otro.titulo
     }
 
@@ -360,13 +496,36 @@ impl Libro{
   Uso:
 
   ```rust
-fn main(){
-  // --snip--
-
-  libro_y.incrementar(&mut libro_x);
-  println!("{}", libro_y.anio_publicacion);
-
-  println!("{}",libro_y.comparar(&libro_x));
+# struct Libro {
+    # titulo: String,
+    # autor: String,
+    # anio_publicacion: u32,
+# }
+# impl Libro {
+    # fn comparar(&self, otro: &Self) -> bool {
+        # self.titulo == otro.titulo
+    # }
+    # fn incrementar(&mut self, otro: &mut Self) {
+        # otro.anio_publicacion += self.anio_publicacion;
+    # }
+# }
+fn main() {
+    // --snip--
+    # let mut libro_x = Libro {
+        # titulo: String::from("Domina Rust"),
+        # autor: String::from("Alex Villanueva"),
+        # anio_publicacion: 2025,
+    # };
+    # let mut libro_y = Libro {
+        # titulo: String::from("Aprende RusT"),
+        # autor: String::from("Alex Villanueva"),
+        # anio_publicacion: 2026,
+    # };
+
+    libro_y.incrementar(&mut libro_x);
+    println!("{}", libro_y.anio_publicacion);
+
+    println!("{}", libro_y.comparar(&libro_x));
 }
 ```
 
@@ -376,7 +535,21 @@ En Rust, es posible llamar varios métodos de forma consecutiva sobre el mismo v
 
 Este patrón es muy común en APIs modernas, ya que hace el código más expresivo y fácil de leer.
 
+// libro: permitir=rustfmt
 ```rust
+# struct Persona {
+    # edad: u8,
+# }
+# impl Persona {
+    # fn incrementar(&mut self, x: u8) -> &mut Self {
+        # self.edad += x;
+        # self
+    # }
+    # fn resetear(&mut self) {
+        # self.edad = 0;
+    # }
+# }
+# let mut persona = Persona { edad: 20 };
 persona
     .incrementar(1)
     .incrementar(2)
@@ -432,6 +605,7 @@ fn main() {
 ```
 
 Salida:
+// libro: ignore
 ```rust
 Edad: 50
 ```
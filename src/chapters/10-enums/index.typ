@@ -45,6 +45,11 @@ match permite comparar un valor contra patrones.
 Ejemplo básico:
 
 ```rust
+# enum Color {
+    # Rojo,
+    # Verde,
+    # Azul,
+# }
 fn imprimir_color(color: Color) {
     match color {
         Color::Rojo => println!("Rojo!"),
@@ -119,6 +124,15 @@ impl Estado {
 Uso:
 
 ```rust
+# enum Estado {
+    # Online,
+    # Offline,
+# }
+# impl Estado {
+    # fn esta_activo(&self) -> bool {
+        # matches!(self, Estado::Online)
+    # }
+# }
 let e = Estado::Online;
 
 println!("{}", e.esta_activo()); // true
@@ -39,6 +39,9 @@ Esto significa que la función funciona para:
 Ejemplo:
 
 ```rust
+# fn identidad<T>(valor: T) -> T {
+    # valor
+# }
 let n = identidad(10);
 let s = identidad("hola");
 let b = identidad(true);
@@ -60,9 +63,16 @@ struct Punto<T> {
 Uso:
 
 ```rust
+# struct Punto<T> {
+    # x: T,
+    # y: T,
+# }
 let p1 = Punto { x: 10, y: 20 };
 let p2 = Punto { x: 3.5, y: 6.1 };
-let p3 = Punto { x: "hola", y: "mundo" };
+let p3 = Punto {
+    x: "hola",
+    y: "mundo",
+};
 ```
 
 Incluso diferentes tipos en un mismo struct:
@@ -95,6 +105,9 @@ impl<T> Contenedor<T> {
 Incluso métodos adicionales para un tipo concreto:
 
 ```rust
+# struct Contenedor<T> {
+    # valor: T,
+# }
 impl Contenedor<i32> {
     fn es_par(&self) -> bool {
         self.valor % 2 == 0
@@ -31,7 +31,10 @@ struct Persona {
 }
 
 fn main() {
-    let p = Persona { nombre: "Ana".into(), edad: 30 };
+    let p = Persona {
+        nombre: "Ana".into(),
+        edad: 30,
+    };
     println!("{:?}", p);
 }
 ```
@@ -71,7 +74,7 @@ let b = a.clone();
 #[derive(Copy, Clone)]
 struct Coordenada(i32, i32);
 
-let a = Coordenada(1,2);
+let a = Coordenada(1, 2);
 let b = a; // copia automática
 ```
 
@@ -132,6 +135,9 @@ Si un tipo implementa #raw("Saludo",lang: "bash"), debe tener el método saludar
 *Implementando un Trait en un Struct*
 
 ```rust
+# trait Saludo {
+    # fn saludar(&self) -> String;
+# }
 struct Persona {
     nombre: String,
 }
@@ -146,7 +152,20 @@ impl Saludo for Persona {
 uso:
 
 ```rust
-let p = Persona { nombre: "Luis".into() };
+# trait Saludo {
+    # fn saludar(&self) -> String;
+# }
+# struct Persona {
+    # nombre: String,
+# }
+# impl Saludo for Persona {
+    # fn saludar(&self) -> String {
+        # format!("Hola, soy {}", self.nombre)
+    # }
+# }
+let p = Persona {
+    nombre: "Luis".into(),
+};
 println!("{}", p.saludar());
 ```
 
@@ -155,6 +174,9 @@ println!("{}", p.saludar());
 Puedes tener muchos structs diferentes con el mismo trait:
 
 ```rust
+# trait Saludo {
+    # fn saludar(&self) -> String;
+# }
 struct Robot {
     id: u32,
 }
@@ -169,11 +191,32 @@ impl Saludo for Robot {
 Así tienes polimorfismo:
 
 ```rust
+# trait Saludo {
+    # fn saludar(&self) -> String;
+# }
+# struct Persona {
+    # nombre: String,
+# }
+# impl Saludo for Persona {
+    # fn saludar(&self) -> String {
+        # format!("Hola, soy {}", self.nombre)
+    # }
+# }
+# struct Robot {
+    # id: u32,
+# }
+# impl Saludo for Robot {
+    # fn saludar(&self) -> String {
+        # format!("Unidad {} reportándose", self.id)
+    # }
+# }
 fn presentar(s: &impl Saludo) {
     println!("{}", s.saludar());
 }
 
-presentar(&Persona { nombre: "Ana".into() });
+presentar(&Persona {
+    nombre: "Ana".into(),
+});
 presentar(&Robot { id: 42 });
 ```
 
@@ -181,11 +224,11 @@ presentar(&Robot { id: 42 });
 
 Estas dos formas son equivalentes:
 
-+ Forma corta (impl Trait)```rust
++ Forma corta (impl Trait)```rust,ignore
 fn presentar(s: &impl Saludo) { ... }
 ```
 
-+ Forma larga (generics + trait bound)```rust
++ Forma larga (generics + trait bound)```rust,ignore
 fn presentar<T: Saludo>(s: &T) { ... }
 ```
 Las dos hacen lo mismo:
@@ -235,6 +278,9 @@ Esto permite traits más flexibles.
 Sí, los enums pueden implementar traits igual que los structs:
 
 ```rust
+# trait Saludo {
+    # fn saludar(&self) -> String;
+# }
 enum Estado {
     Activo,
     Inactivo,
@@ -253,7 +299,7 @@ impl Saludo for Estado {
 
 Un trait puede depender de otro:
 
-```rust
+```rust,fragment
 trait A: B { }
 ```
 
@@ -265,6 +311,7 @@ Sin bounds, un genérico no puede usar ninguna funcionalidad del trait.
 
 Sintaxis básica:
 ```rust
+# use std::fmt::Debug;
 fn mostrar<T: Debug>(x: T) {
     println!("{:?}", x);
 }
@@ -279,6 +326,7 @@ fn procesar<T: Clone + PartialEq>(x: T, y: T) -> bool {
 
 Útil cuando hay varios bounds:
 ```rust
+# use std::fmt::Debug;
 fn combinar<T, U>(t: T, u: U)
 where
     T: Debug,
@@ -296,14 +344,22 @@ struct Punto<T: Copy> {
 ```
 *Bounds en impl*
 ```rust
+# struct Maximo<T> {
+    # valor: T,
+# }
 impl<T: PartialOrd> Maximo<T> {
     fn max(a: T, b: T) -> T {
-        if a > b { a } else { b }
+        if a > b {
+            a
+        } else {
+            b
+        }
     }
 }
 ```
 *Bounds en enums*
 ```rust
+# use std::fmt::Debug;
 enum Contenedor<T: Debug> {
     Valor(T),
 }
@@ -311,6 +367,7 @@ enum Contenedor<T: Debug> {
 *Bounds súper-traits*
 Un trait que requiere otro trait:
 ```rust
+# use std::fmt::Display;
 trait Imprimible: Display {
     fn imprimir(&self) {
         println!("{}", self);
@@ -7,6 +7,7 @@ No es un valor, no se almacena en memoria, no existe en tiempo de ejecución.
 Solo es información estática para que el compilador asegure que no haya referencias colgantes.
 
 Ejemplo de referencia peligrosa:
+// libro: no_compila=E0597
 ```rust
 let r;
 
@@ -23,6 +24,7 @@ El compilador detecta este tipo de errores antes de que el programa se ejecute.
 
 Una lifetime se escribe con un apóstrofo:
 
+// libro: ignore
 ```rust
 &'a i32
 ```
@@ -37,9 +39,14 @@ Cuando una función recibe y devuelve referencias, Rust no sabe por sí solo qu
 
 Ejemplo:
 
+// libro: no_compila=E0106
 ```rust
 fn mayor(a: &str, b: &str) -> &str {
-    if a.len() > b.len() { a } else { b }
+    if a.len() > b.len() {
+        a
+    } else {
+        b
+    }
 }
 ```
 Esto produce error:
@@ -50,7 +57,11 @@ Esto produce error:
 Para solucionarlo:
 ```rust
 fn mayor<'a>(a: &'a str, b: &'a str) -> &'a str {
-    if a.len() > b.len() { a } else { b }
+    if a.len() > b.len() {
+        a
+    } else {
+        b
+    }
 }
 ```
 
@@ -78,6 +89,13 @@ fn el_mas_largo<'a>(x: &'a str, y: &'a str) -> &'a str {
 uso:
 
 ```rust
+# fn el_mas_largo<'a>(x: &'a str, y: &'a str) -> &'a str {
+    # if x.len() > y.len() {
+        # x
+    # } else {
+        # y
+    # }
+# }
 let s1 = String::from("hola");
 let s2 = String::from("rustaceo");
 
@@ -133,12 +151,14 @@ Rust aplica estas reglas cuando puede inferir sin ambigüedad:
 Regla 1
 
 Cada parámetro de entrada obtiene su propio lifetime.
+// libro: ignore
 ```rust
 fn f(x: &i32, y: &i32);
 ```
 
 Se convierte en:
 
+// libro: ignore
 ```rust
 fn f<'a, 'b>(x: &'a i32, y: &'b i32);
 ```
@@ -147,21 +167,25 @@ Regla 2
 
 Si hay un solo parámetro de entrada, el retorno recibe su lifetime.
 
+// libro: ignore
 ```rust
 fn id(x: &i32) -> &i32;  // lifetime elision
 ```
 
 Equivalente a:
+// libro: ignore
 ```rust
 fn id<'a>(x: &'a i32) -> &'a i32;
 ```
 Regla 3
 
 Si el método tiene &self o &mut self, el retorno hereda ese lifetime.
+// libro: ignore
 ```rust
 fn valor(&self) -> &str;
 ```
 Equivalente a:
+// libro: ignore
 ```rust
 fn valor<'a>(&'a self) -> &'a str;
 ```
@@ -180,6 +204,7 @@ T: 'a significa:
 *Múltiples lifetimes*
 
 Puede haber varias lifetimes independientes:
+// libro: permitir=needless_lifetimes
 ```rust
 fn elegir<'a, 'b>(a: &'a str, b: &'b str) -> &'b str {
     b
@@ -205,6 +230,7 @@ Los literales string siempre son 'static.
 
 
 *Ejemplo Real: dividir texto en partes*
+// libro: permitir=needless_lifetimes
 ```rust
 fn primera_palabra<'a>(s: &'a str) -> &'a str {
     let bytes = s.as_bytes();
@@ -231,7 +257,11 @@ trait Logger<'a> {
 }
 ```
 O implementar traits para tipos con lifetimes:
+// libro: permitir=derivable_impls
 ```rust
+# struct Texto<'a> {
+    # contenido: &'a str,
+# }
 impl<'a> Default for Texto<'a> {
     fn default() -> Self {
         Self { contenido: "" }
@@ -241,6 +271,7 @@ impl<'a> Default for Texto<'a> {
 
 *Bounds con lifetimes*
 ```rust
+# use std::fmt::Debug;
 fn mostrar<'a, T>(x: &'a T)
 where
     T: Debug + 'a,
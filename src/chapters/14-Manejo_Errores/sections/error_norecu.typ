@@ -23,32 +23,42 @@ Detener el programa de forma controlada
 
 panic! es una macro que termina la ejecución y muestra un mensaje.
 
+// libro: id=panic-basico, transcripcion
 ```rust
 fn main() {
     panic!("Ocurrió un error inesperado");
 }
 ```
-Salida típica:
-```rust
-thread 'main' panicked at 'Ocurrió un error inesperado', src/main.rs:2:5
-```
+Salida:
+
+#include "/build/generado/panicos/panic-basico.typ"
+
+El mensaje indica el hilo (`main`), el archivo con la línea y columna donde
+ocurrió el panic, y después el texto que pasamos a la macro. El proceso
+termina con el código de salida 101.
 
 ¿Cuándo ocurre un panic automáticamente?
 + Índices fuera de rango
+// libro: id=panic-indice, transcripcion
 ```rust
 let v = vec![1, 2, 3];
 println!("{}", v[10]); // panic!
 ```
+#include "/build/generado/panicos/panic-indice.typ"
 + unwrap() sobre None
+// libro: id=panic-unwrap, transcripcion, permitir=unnecessary_literal_unwrap
 ```rust
 let x: Option<i32> = None;
 x.unwrap(); // panic
 ```
+#include "/build/generado/panicos/panic-unwrap.typ"
 + expect() también provoca panic
+// libro: id=panic-expect, transcripcion
 ```rust
 let r: Result<i32, _> = "hola".parse();
 r.expect("No se pudo convertir"); // panic con mensaje personalizado
 ```
+#include "/build/generado/panicos/panic-expect.typ"
 Cuándo no usar  unwrap() y expect()
 
 Ambos métodos causan panic si:
@@ -78,16 +88,38 @@ seguir el origen del panic
 Rust puede mostrar la ruta de llamadas que llevó al error.
 
 Para activarlo:
-```rust
+```bash
 RUST_BACKTRACE=1 cargo run
 ```
-Ejemplo de salida:
+Por ejemplo, con un panic dentro de una función llamada desde `main`:
+
+// libro: id=panic-backtrace, transcripcion=backtrace
 ```rust
-thread 'main' panicked at 'fallo X', src/main.rs:12:9
-stack backtrace:
-   0: rust_begin_unwind
-   1: core::panicking::panic_fmt
-   2: my_project::funcion
-   3: my_project::main
+fn main() {
+    println!("Inicio");
+    funcion();
+}
+
+fn funcion() {
+    panic!("fallo X");
+}
 ```
-Esto es útil para depurar.
+
+Sin la variable, Rust solo muestra el mensaje y sugiere activarla:
+
+#include "/build/generado/panicos/panic-backtrace.typ"
+
+Con `RUST_BACKTRACE=1` aparece la pila de llamadas, de la más reciente a la
+más antigua. Cada marco dice la función y, debajo, el archivo y la línea.
+Aquí solo dejamos los marcos de nuestro proyecto; los de la biblioteca
+estándar están resumidos con `...`:
+
+#include "/build/generado/panicos/panic-backtrace-backtrace.typ"
+
+`RUST_BACKTRACE=full` añade las direcciones de memoria y el sufijo con el
+hash de cada símbolo:
+
+#include "/build/generado/panicos/panic-backtrace-full.typ"
+
+Esto es útil para depurar: basta con buscar el primer marco que pertenece a
+nuestro código.
@@ -42,12 +42,15 @@ Sirve para modelar cosas que pueden o no existir.
 
 *Obtener el primer elemento de un vector*
 ```rust
-fn obtener_primero(nums: &Vec<i32>) -> Option<i32> {
+fn obtener_primero(nums: &[i32]) -> Option<i32> {
     nums.first().copied()
 }
 ```
 Uso:
 ```rust
+# fn obtener_primero(nums: &[i32]) -> Option<i32> {
+    # nums.first().copied()
+# }
 match obtener_primero(&vec![10, 20, 30]) {
     Some(n) => println!("Primer número: {}", n),
     None => println!("El vector está vacío"),
@@ -74,6 +77,7 @@ Métodos importantes de Option\<T\>
 
 Ejemplo: evitar panic usando unwrap_or
 
+// libro: permitir=unnecessary_literal_unwrap
 ```rust
 let nombre = Some("Juan");
 let n = nombre.unwrap_or("Invitado");
@@ -105,6 +109,9 @@ fn convertir(s: &str) -> Result<i32, std::num::ParseIntError> {
 Uso:
 
 ```rust
+# fn convertir(s: &str) -> Result<i32, std::num::ParseIntError> {
+    # s.parse::<i32>()
+# }
 match convertir("42") {
     Ok(n) => println!("Número: {}", n),
     Err(e) => println!("Error: {}", e),
@@ -141,7 +148,11 @@ El operador ?:
 
 Ejemplo sin ?
 
+// libro: escenario=leer-existe, escenario=leer-no-existe
 ```rust
+# use std::fs::File;
+# use std::io::Read;
+#
 fn leer_archivo() -> Result<String, std::io::Error> {
     let mut archivo = File::open("hola.txt");
     match archivo {
@@ -155,21 +166,76 @@ fn leer_archivo() -> Result<String, std::io::Error> {
         Err(e) => Err(e),
     }
 }
+#
+# fn main() {
+#     match leer_archivo() {
+#         Ok(contenido) => print!("Contenido: {contenido}"),
+#         Err(e) => println!("Error: {e}"),
+#     }
+# }
 ```
+
+Llamada desde un `main` que imprime el contenido o el error, la función
+recorre las dos ramas según exista o no `hola.txt`:
+
+#include "/build/generado/escenarios/leer-existe.typ"
+#include "/build/generado/escenarios/leer-no-existe.typ"
+
 Ejemplo con ?
+// libro: escenario=leer-varias-lineas, escenario=leer-directorio
 ```rust
+# use std::fs::File;
+# use std::io::Read;
+#
 fn leer_archivo() -> Result<String, std::io::Error> {
     let mut contenido = String::new();
     File::open("hola.txt")?.read_to_string(&mut contenido)?;
     Ok(contenido)
 }
+#
+# fn main() {
+#     match leer_archivo() {
+#         Ok(contenido) => print!("Contenido: {contenido}"),
+#         Err(e) => println!("Error: {e}"),
+#     }
+# }
 ```
 
+Cada `?` puede cortar la función. Si `hola.txt` tiene varias líneas, las
+devuelve todas; si es un directorio, `File::open` funciona pero falla el
+segundo `?`, el de `read_to_string`:
+
+#include "/build/generado/escenarios/leer-varias-lineas.typ"
+#include "/build/generado/escenarios/leer-directorio.typ"
+
+`main` también puede devolver un `Result` y usar `?`. Si hay un error, el
+programa lo imprime en la salida de error con `{:?}` y termina con código 1.
+Aquí el puerto sale de la variable de entorno `PUERTO`:
+
+// libro: escenario=puerto-definido, escenario=puerto-sin-definir, escenario=puerto-invalido
+```rust
+use std::env;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let puerto: u16 = env::var("PUERTO")?.parse()?;
+    println!("Escuchando en el puerto {puerto}");
+    Ok(())
+}
+```
+
+Con `PUERTO=8080`, sin la variable y con `PUERTO=abc`:
+
+#include "/build/generado/escenarios/puerto-definido.typ"
+#include "/build/generado/escenarios/puerto-sin-definir.typ"
+#include "/build/generado/escenarios/puerto-invalido.typ"
+
 Patrones para manejar errores correctamente
 
 match
 
 ```rust
+# let archivo = std::fs::File::open("hola.txt");
 match archivo {
     Ok(a) => println!("Abierto"),
     Err(e) => println!("Error: {}", e),
@@ -178,6 +244,7 @@ match archivo {
 
 if let
 ```rust
+# let x = Some(5);
 if let Some(n) = x {
     println!("El número es {}", n);
 }
@@ -186,8 +253,9 @@ if let Some(n) = x {
 while let
 
 Ideal para iteradores:
+// libro: permitir=while_let_on_iterator
 ```rust
-let mut iter = vec![1,2,3].into_iter();
+let mut iter = vec![1, 2, 3].into_iter();
 while let Some(v) = iter.next() {
     println!("{}", v);
 }
@@ -56,7 +56,7 @@ pub fn suma(a: i32, b: i32) -> i32 {
     a + b
 }
 ```
-  Se puede usar desde otro crate con:```rust
+  Se puede usar desde otro crate con:```rust,fragment
 use mi_lib::suma;
 ```
 
@@ -65,7 +65,7 @@ use mi_lib::suma;
 Los crates externos son librerías publicadas en crates.io.
 
 Se añaden en Cargo.toml:
-```rust
+```toml
 [dependencies]
 rand = "0.8"
 ```
@@ -143,6 +143,7 @@ fn main() {
 }
 ```
 También puedes importar todo:
+// libro: fragment
 ```rust
 use matematicas::*;
 ```
@@ -152,12 +153,9 @@ use matematicas::*;
 
 Cuando contamos con mas archivos como por ejemplo este:
 
-```yaml
-src/
- ├── main.rs
- ├── utilidades.rs
-```
+#include "/build/generado/arboles/varios-archivos.typ"
 
+// libro: proyecto=varios-archivos, archivo=src/main.rs
 + `main.rs````rust
 mod utilidades; // le decimos que existe ese archivo
 
@@ -166,6 +164,7 @@ fn main() {
 }
 ```
 
+// libro: proyecto=varios-archivos, archivo=src/utilidades.rs
 + `utilidades.rs````rust
 pub fn saludo(nombre: &str) {
     println!("Hola, {}!", nombre);
@@ -176,15 +175,10 @@ Ahora el código está separado.
 === Varios archivos en carpetas
 
 Supongamos esta estructura:
-```yaml
-src/
- ├── main.rs
- ├── matematicas/
- │     ├── mod.rs
- │     ├── suma.rs
- │     └── resta.rs
-```
 
+#include "/build/generado/arboles/carpetas.typ"
+
+// libro: proyecto=carpetas, archivo=src/main.rs
 + #raw("main.rs:",lang: "yaml")```rust
 mod matematicas;
 
@@ -193,17 +187,20 @@ fn main() {
 }
 ```
 
+// libro: proyecto=carpetas, archivo=src/matematicas/mod.rs
 + #raw("matematicas/mod.rs:",lang: "yaml")```rust
 pub mod suma;
 pub mod resta;
 ```
 
+// libro: proyecto=carpetas, archivo=src/matematicas/suma.rs
 + #raw("matematicas/suma.rs:",lang: "yaml")```rust
 pub fn sumar(a: i32, b: i32) -> i32 {
     a + b
 }
 ```
 
+// libro: proyecto=carpetas, archivo=src/matematicas/resta.rs
 + #raw("matematicas/resta.rs:",lang: "yaml")```rust
 pub fn restar(a: i32, b: i32) -> i32 {
     a - b
@@ -388,9 +385,7 @@ fn main() {
 Salida:
 ```yaml
 👶 Hola desde el hijo
-
 👴 Hola desde el padre
-
 🧓 Hola desde el abuelo
 ```
 
@@ -589,6 +584,7 @@ pub mod calculadora {
 ```
 
 main.rs:
+// libro: fragment
 ```rust
 fn main() {
     let resultado = crate::calculadora::sumar(5, 3);
@@ -626,11 +622,11 @@ src/
 ```
 
 + #raw("main.rs",lang: "rust") :
-  Aquí arrancamos el programa y usamos los módulos.```rust
+  Aquí arrancamos el programa y usamos los módulos.```rust,fragment
 mod utilidades; // importamos el módulo "utilidades"
 
-use utilidades::texto;
 use utilidades::numeros;
+use utilidades::texto;
 
 fn main() {
     println!("=== Proyecto con módulos ===");
@@ -658,7 +654,7 @@ fn main() {
   - Aquí agrupamos los submódulos.
 
 + #raw("utilidades/texto.rs",lang: "rust") :
-  Aquí jugamos con `pub` y `pub(super)`.```rust
+  Aquí jugamos con `pub` y `pub(super)`.```rust,fragment
 pub fn saludo(nombre: &str) {
     println!("Hola, {}!", nombre);
 }
@@ -2,6 +2,27 @@
 #import "@preview/codly:1.3.0": *
 #import "@preview/codly-languages:0.1.1": *
 #import "@preview/cetz:0.4.2"
+#import "/build/generado/verificacion.typ": verificacion
+#import "/build/generado/msrv.typ": msrv
+#let resultados = json("/build/generado/resultados.json")
+
+// Insignia de cada estado de `resultados`: texto, color y fondo.
+#let insignias = (
+  compila: ("✓ compila", rgb("#2E7D32"), rgb("#E8F5E9")),
+  no_compila: ("✗ no compila", rgb("#CE412B"), rgb("#FFD8C9")),
+  panico: ("⚠ panic", rgb("#B26A00"), rgb("#FFF3D6")),
+  no_termina: ("⚠ no termina", rgb("#B26A00"), rgb("#FFF3D6")),
+  desborda_pila: ("⚠ desborda el stack", rgb("#B26A00"), rgb("#FFF3D6")),
+  fragmento: ("fragmento", rgb("#6B6B6B"), rgb("#F0F0F0")),
+)
+
+// Resaltado de cada tipo de comentario de `resultados`: fondo y etiqueta.
+// La salida usa el verde de los bloques «Output».
+#let resaltes = (
+  salida: (rgb("#D6FFCB"), none),
+  error: (rgb("#FFD8C9"), "error"),
+  panico: (rgb("#FFF3D6"), "panic"),
+)
 
 #let title = "Diviértete con Rust 🦀"
 
@@ -23,6 +44,65 @@
     it
   }
 
+  // Atributos de la línea de apertura (```rust,no_run): Typst los deja como
+  // primera línea del código. Se quitan para mostrar un bloque de Rust
+  // normal, junto con las líneas ocultas (`# ...`), que solo se compilan
+  // (ver `herramientas/src/fragmentos.rs`).
+  let oculta(linea) = {
+    let linea = linea.trim(at: start)
+    linea == "#" or linea.starts-with("# ")
+  }
+  show raw.where(block: true, lang: "rust"): it => {
+    let lineas = it.text.split("\n")
+    if it.text.starts-with(",") { lineas = lineas.slice(1) }
+    if not it.text.starts-with(",") and not lineas.any(oculta) { return it }
+    raw(lineas.filter(l => not oculta(l)).join("\n"), block: true, lang: "rust")
+  }
+
+  // Insignia «desde Rust 1.xx» en el margen de los ejemplos que necesitan
+  // un compilador posterior a la edición 2021 (ver `libro generar msrv`).
+  show raw.where(block: true, lang: "rust"): it => {
+    let minima = msrv.at(it.text, default: none)
+    if minima == none { return it }
+    block(breakable: true, width: 100%, {
+      place(top + right, dx: 1.7cm, box(
+        width: 1.5cm,
+        inset: 2pt,
+        radius: 2pt,
+        fill: rgb("#FFD8C9"),
+        align(center, text(size: 6pt, fill: rgb("#CE412B"), weight: "bold")[desde Rust #minima.version]),
+      ))
+      it
+    })
+  }
+
+  // Insignia con el resultado de la verificación en el borde superior de
+  // cada ejemplo, y los comentarios de salida, error y pánico resaltados
+  // (ver `libro generar ediciones`).
+  show raw.where(block: true, lang: "rust"): it => {
+    let resultado = resultados.at(it.text, default: none)
+    if resultado == none { return it }
+    let (etiqueta, color, fondo) = insignias.at(resultado.estado)
+    if "error" in resultado { etiqueta += " (" + resultado.error + ")" }
+    block(breakable: true, width: 100%, {
+      place(top + right, dx: -6pt, dy: -5pt, box(
+        inset: (x: 3pt, y: 1.5pt),
+        radius: 2pt,
+        fill: fondo,
+        stroke: 0.5pt + color,
+        text(size: 6pt, fill: color, weight: "bold", etiqueta),
+      ))
+      let comentarios = resultado.at("comentarios", default: ())
+      if comentarios.len() > 0 {
+        codly(highlights: comentarios.map(c => {
+          let (fondo, etiqueta) = resaltes.at(c.tipo)
+          (line: c.linea, start: c.columna, fill: fondo, tag: etiqueta)
+        }))
+      }
+      it
+    })
+  }
+
   let rust-orange = rgb("#CE412B")
   let rust-dark = rgb("#1C1C1C")
   let rust-mid = rgb("#6B6B6B")
@@ -72,7 +152,23 @@
     margin: (x: 1.8cm, y: 1.5cm),
     header: align(right + horizon, title),
     numbering: "1",
-    number-align: right,
+    // Al pie de la primera página de cada capítulo, con qué Rust se
+    // verificaron sus ejemplos (la última vez que pasó `libro comprobar`).
+    footer: context {
+      let inicio = query(heading.where(level: 1, outlined: true)).any(h => h.location().page() == here().page())
+      set text(size: 8pt, fill: rust-mid)
+      grid(
+        columns: (1fr, auto),
+        align: (left, right),
+        if inicio {
+          [Ejemplos verificados con rustc #verificacion.rustc, edición #verificacion.edicion]
+          // Si no era la versión fijada, se dice cuál lo es.
+          let distintas = verificacion.at("distintas", default: ()).filter(d => d.nombre == "rustc")
+          if distintas.len() > 0 [ (fijada: #distintas.first().fijada)]
+        },
+        text(size: 12pt, fill: black, counter(page).display()),
+      )
+    },
   )
   set table(
     stroke: (x, y) => if y == 0 { (bottom: 2pt + white) },
@@ -114,6 +210,14 @@
   box(text(size: 18pt, weight: "bold", fill: rgb("#ff4aa1"), contenido)),
 )
 
+// Salida real de un fragmento con `id`, capturada por `libro generar
+// salidas`, con el estilo «Output» de los bloques `yaml`.
+#let salida(id) = raw(
+  read("/build/outputs/" + id + ".txt").trim(at: end),
+  block: true,
+  lang: "yaml",
+)
+
 #let meander_lib(imagen_url, texto) = {
   meander.reflow({
     import meander: *
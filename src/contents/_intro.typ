@@ -310,6 +310,7 @@ cd mundorust
 
 El comando #raw("cargo new mundorust", lang: "yaml") crea un nuevo proyecto desde cero con la estructura básica lista para programar.
 
+// libro: arbol=cargo-new/mundorust
 ```bash
 mundorust/
 ├── Cargo.toml      # Archivo de configuración del proyecto
@@ -323,7 +324,7 @@ mundorust/
 [package]
 name = "mundorust"
 version = "0.1.0"
-edition = "2021"  # Edición estable actual 2021
+edition = "2021"  # Edición de los ejemplos del libro (la última es 2024)
 
 [dependencies]
 rand = "0.8"  # Ejemplo: Librería para números aleatorios
@@ -479,7 +480,9 @@ Rust infiere el tipo automáticamente en la mayoría de casos, pero puedes espec
       length: 100%,
       stroke: 2pt + red,
     )
+    // libro: no_compila=E0384
     ```rust
+    # let saludo = "Hola";
     saludo = "¡Hola, Rust!"; //ERROR
     ```
   ],
@@ -510,6 +513,7 @@ Una variable mutable es aquella cuyo valor sí puede modificarse durante la ejec
       length: 100%,
       stroke: 2pt + red,
     )
+    // libro: no_compila=E0384
     ```rust
     let y = 5; // No es mutable
 
@@ -547,7 +551,7 @@ Las constantes #raw("const", lang: "rust") y los valores estáticos #raw("static
   - Solo puede almacenar valores que el compilador pueda evaluar como constantes
 
   - Usa #raw("SCREAMING_SNAKE_CASE", lang: "rust") por convención```rust
-    const ESPACIO: &'static str = "rust";
+    const ESPACIO: &str = "rust";
     ```
 
 + #raw("static", lang: "rust")
@@ -596,6 +600,7 @@ Rust permite redeclarar una variable con el mismo nombre usando *let*. Esto no m
       length: 100%,
       stroke: 2pt + red,
     )
+    // libro: no_compila=E0384
     ```rust
     let x = 5;
     x = x + 1;
@@ -697,6 +702,7 @@ Puedes nombrar los valores y usarlos dentro del string:
 
 
 
+// libro: permitir=print_literal
 ```rust
 println!(
     "Usuario {nombre} tiene {edad} años",
@@ -874,6 +880,7 @@ fn main() {
 
 Salida:
 
+// libro: ignore
 ```rust
 Scope principal: numero = 5
 Dentro del bloque: numero = 20
@@ -909,13 +916,15 @@ En Rust, casi todo es una expresión. Solo unas pocas construcciones son declara
   let x = 5;          // Vincula el nombre `x` al valor `5`
   ```
 
-  let no es una expresión. No se puede usar en asignaciones anidadas: ```rust
+  let no es una expresión. No se puede usar en asignaciones anidadas: ```rust,compile_fail
   // Error de compilación
   let y = (let x = 5);  // `let` no devuelve valor, no se puede asignar
   ```
 
-
+// libro: permitir=no_effect
 + Declaraciones de expresiones descartadas. ```rust
+  # let x = 1;
+  # let y = 2;
   x + y;              // La expresión `x + y` se evalúa, pero su resultado se descarta
   println!("Hola");   // `println!` devuelve `()`, y la llamada como declaración descarta ese valor
   ```
@@ -932,6 +941,7 @@ Características:
 
 Ejemplos comunes:
 
+// libro: ignore
 ```rust
 5 + 2                // valor: 7, tipo: `i32`
 "Rust"               // valor: &str, tipo: `&'static str`
@@ -0,0 +1,33 @@
+#import "/build/generado/verificacion.typ": verificacion
+
+#pagebreak()
+
+#heading(numbering: none)[Colofón]
+
+Este libro se escribió en #link("https://typst.app/")[Typst] y se compuso con
+Typst #str(sys.version) (fijado: #verificacion.typst).
+
+Los ejemplos, tablas, diagramas y salidas de terminal no se copiaron a mano:
+las herramientas del libro (`herramientas/`) ejecutan cada fragmento y
+guardan lo que produce. La última verificación completa (`just comprobar`)
+pasó el #verificacion.fecha con:
+
+#table(
+  columns: 2,
+  [*Herramienta*], [*Versión*],
+  [Compilador], raw(verificacion.rustc-completo),
+  [Cargo], raw(verificacion.cargo),
+  [Edición], [Rust #verificacion.edicion],
+  [Plataforma], raw(verificacion.plataforma),
+)
+
+#let distintas = verificacion.at("distintas", default: ())
+#if distintas.len() > 0 [
+  Esa verificación no se hizo con las versiones fijadas:
+  #for d in distintas [#d.nombre #d.instalada en lugar de #d.fijada. ]
+]
+
+La versión de Rust fijada está en `rust-toolchain.toml`, y la de Typst en
+`herramientas/Cargo.toml`. Con otro compilador, los mensajes de error, los
+backtraces o el tamaño de algunos tipos pueden cambiar ligeramente; lo que
+el libro explica sobre el lenguaje, no.
@@ -0,0 +1,37 @@
+// Bloque con la salida de rustc tal como se ve en la terminal.
+// Lo usan los archivos de build/generado/diagnosticos/, que produce
+// `libro generar diagnosticos` a partir de la salida JSON del compilador.
+
+// Colores ANSI del tema por defecto de rustc, algo oscurecidos para papel.
+#let colores = (
+  rojo: rgb("#C0392B"),
+  verde: rgb("#2E8B57"),
+  amarillo: rgb("#B8860B"),
+  azul: rgb("#2255AA"),
+  magenta: rgb("#9B30A0"),
+  cian: rgb("#1F8A99"),
+)
+
+// `lineas`: una lista por línea de tramos `(texto, color, negrita)`, donde
+// `color` es una clave de `colores` o `none`.
+#let diagnostico(lineas) = block(
+  width: 100%,
+  inset: 0.32em,
+  fill: white,
+  stroke: 1pt + rgb("#c2c2c2"),
+  breakable: true,
+  {
+    set par(justify: false, leading: 0.65em)
+    set text(size: 0.8em)
+    for (i, linea) in lineas.enumerate() {
+      if i > 0 { linebreak() }
+      for (texto, color, negrita) in linea {
+        text(
+          fill: if color == none { black } else { colores.at(color) },
+          weight: if negrita { "bold" } else { "regular" },
+          raw(texto),
+        )
+      }
+    }
+  },
+)
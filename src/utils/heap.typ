@@ -0,0 +1,58 @@
+#import "@preview/fletcher:0.5.8" as fletcher: diagram, node, edge
+
+#let rust-orange = rgb("#ff6b35")
+#let stack-blue = rgb("#4a90e2")
+#let heap-green = rgb("#27ae60")
+
+// Triple (ptr, len, cap) que un `String` o `Vec` guarda en el stack.
+// `valid: false` marca una variable cuyo valor fue movido.
+#let stack-box(pos, var-name, ptr, len, cap, valid: true) = {
+  let fill-color = if valid { stack-blue.lighten(90%) } else { gray.lighten(80%) }
+  let text-color = if valid { black } else { gray }
+
+  node(
+    pos,
+    align(left)[
+      #text(size: 9pt, weight: "bold", fill: if valid { rust-orange } else { gray })[#var-name]
+      #if not valid { text(size: 7pt, fill: red)[ ✗] } \
+      #line(length: 100%, stroke: 0.5pt + gray) \
+      #text(size: 7pt, fill: text-color)[ptr: #text(font: "monospace", fill: stack-blue)[#ptr]] \
+      #text(size: 7pt, fill: text-color)[len: #text(font: "monospace")[#len]] \
+      #text(size: 7pt, fill: text-color)[cap: #text(font: "monospace")[#cap]]
+    ],
+    width: 35mm,
+    fill: fill-color,
+    stroke: 2pt + if valid { stack-blue } else { gray },
+    corner-radius: 5pt,
+    inset: 8pt,
+  )
+}
+
+// Buffer en el heap. `celdas` es un arreglo de pares (contenido, bytes):
+// uno por carácter de un `String` o por elemento de un `Vec`. `libres` es
+// la capacidad reservada que aún no se usa (`cap - len`).
+#let heap-box(pos, celdas, addr, libres: 0) = node(
+  pos,
+  align(left)[
+    #text(size: 8pt, weight: "bold", fill: heap-green)[Datos en Heap] \
+    #line(length: 100%, stroke: 0.5pt + gray) \
+    #grid(
+      columns: celdas.len() + if libres > 0 { 1 } else { 0 },
+      stroke: 0.5pt + heap-green,
+      inset: 3pt,
+      align: center,
+      ..celdas.map(((contenido, bytes)) => [
+        #text(size: 7.5pt, font: "monospace")[#contenido] \
+        #text(size: 5.5pt, fill: gray, font: "monospace")[#bytes]
+      ]),
+      ..if libres > 0 {
+        (text(size: 6.5pt, fill: gray)[+#libres libres],)
+      },
+    )
+    #text(size: 6.5pt, fill: gray)[addr: #text(font: "monospace")[#addr]]
+  ],
+  fill: heap-green.lighten(90%),
+  stroke: 2pt + heap-green,
+  corner-radius: 5pt,
+  inset: 8pt,
+)
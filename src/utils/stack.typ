@@ -4,7 +4,10 @@
 #let rust-orange = rgb("#ff6b35")
 #let memory-blue = rgb("#4a90e2")
 
-#let mem-cell(pos, var-name, value, address, ..args) = node(
+// Celda de una variable en el stack. Los diagramas que usan `mem-cell` los
+// genera `libro generar` (ver `build/generado/memoria/`) a partir de una
+// ejecución real del fragmento, con `tipo` y `tamano` incluidos.
+#let mem-cell(pos, var-name, value, address, tipo: none, tamano: none, ..args) = node(
   pos,
   align(left)[
     #grid(
@@ -18,6 +21,14 @@
       text(weight: "bold", size: 8pt)[Valor:],
       text(size: 8pt, font: "monospace")[#value],
 
+      ..if tipo != none {
+        (text(weight: "bold", size: 8pt)[Tipo:], text(size: 8pt)[#tipo])
+      },
+
+      ..if tamano != none {
+        (text(weight: "bold", size: 8pt)[Tamaño:], text(size: 8pt)[#tamano])
+      },
+
       text(weight: "bold", size: 8pt)[Dirección:],
       text(size: 7pt, font: "monospace", fill: memory-blue)[#address],
     )
@@ -29,38 +40,3 @@
   inset: 8pt,
   ..args,
 )
-
-#figure(
-  kind: "diagram",
-  supplement: [Diagrama],
-  caption: [Representación del stack de memoria en Rust],
-
-  diagram(
-    spacing: 10mm,
-    edge-stroke: 1.5pt,
-    mark-scale: 80%,
-
-    // Título del stack
-    node((0.5, -0.5), text(size: 11pt, weight: "bold", fill: memory-blue)[STACK]),
-
-    // Variable numero
-    mem-cell((0,0), [numero], [42], [0x7fff5fbff8c0]),
-
-    edge((0,0), (1,0), "-|>", label: text(size: 8pt)[Copy]),
-
-    // Variable numero2
-    mem-cell((1,0), [numero2], [42], [0x7fff5fbff8c4]),
-
-    // Anotación
-    node((0.5, 1),
-      rect(
-        fill: yellow.lighten(80%),
-        inset: 6pt,
-        radius: 4pt,
-        text(size: 8pt, style: "italic")[
-          Los valores se copian porque `i32` implementa `Copy`
-        ]
-      )
-    ),
-  )
-) <diag-stack>